@@ -9,6 +9,7 @@ use k256::elliptic_curve::sec1::ToEncodedPoint;
 use k256::PublicKey;
 use sha3::{Digest, Keccak256};
 
+const BECH32_CONST: u32 = 1;
 const BECH32M_CONST: u32 = 0x2bc8_30a3;
 const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
 const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
@@ -131,7 +132,20 @@ pub fn encode_segwit_v1_bech32m(hrp: &str, witness_program: &[u8]) -> WalletResu
 
     let mut data = vec![1u8];
     data.extend(convert_bits(witness_program, 8, 5, true)?);
-    bech32m_encode(hrp, &data)
+    bech32_encode(hrp, &data, BECH32M_CONST)
+}
+
+/// BIP-173 (bech32, not bech32m) address for a SegWit v0 P2WPKH program.
+pub fn encode_segwit_v0_bech32(hrp: &str, pubkey_hash: &[u8]) -> WalletResult<String> {
+    if pubkey_hash.len() != 20 {
+        return Err(WalletError::invalid_input(
+            "p2wpkh witness program must be 20 bytes",
+        ));
+    }
+
+    let mut data = vec![0u8];
+    data.extend(convert_bits(pubkey_hash, 8, 5, true)?);
+    bech32_encode(hrp, &data, BECH32_CONST)
 }
 
 fn convert_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> WalletResult<Vec<u8>> {
@@ -165,7 +179,7 @@ fn convert_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> WalletR
     Ok(out)
 }
 
-fn bech32m_encode(hrp: &str, data: &[u8]) -> WalletResult<String> {
+fn bech32_encode(hrp: &str, data: &[u8], checksum_const: u32) -> WalletResult<String> {
     if hrp.is_empty() {
         return Err(WalletError::invalid_input("bech32 hrp is required"));
     }
@@ -183,7 +197,7 @@ fn bech32m_encode(hrp: &str, data: &[u8]) -> WalletResult<String> {
         ));
     }
 
-    let checksum = bech32m_checksum(hrp, data);
+    let checksum = bech32_checksum(hrp, data, checksum_const);
     let mut out = String::with_capacity(hrp.len() + 1 + data.len() + checksum.len());
     out.push_str(hrp);
     out.push('1');
@@ -196,11 +210,11 @@ fn bech32m_encode(hrp: &str, data: &[u8]) -> WalletResult<String> {
     Ok(out)
 }
 
-fn bech32m_checksum(hrp: &str, data: &[u8]) -> [u8; 6] {
+fn bech32_checksum(hrp: &str, data: &[u8], checksum_const: u32) -> [u8; 6] {
     let mut values = hrp_expand(hrp);
     values.extend_from_slice(data);
     values.extend_from_slice(&[0u8; 6]);
-    let polymod = bech32_polymod(&values) ^ BECH32M_CONST;
+    let polymod = bech32_polymod(&values) ^ checksum_const;
     let mut out = [0u8; 6];
     for (i, slot) in out.iter_mut().enumerate() {
         *slot = ((polymod >> (5 * (5 - i))) & 0x1f) as u8;
@@ -1,18 +1,26 @@
 use candid::Principal;
 
 use crate::chains::{
-    aptos_mainnet, bitcoin, ethereum, internet_computer, near_mainnet, sepolia, solana,
+    aptos_mainnet, bitcoin, bitcoin_regtest, bitcoin_signet, bitcoin_testnet, ethereum,
+    internet_computer, near_mainnet, sepolia, solana, solana_devnet, solana_localnet,
     solana_testnet, sui_mainnet, ton_mainnet, tron,
 };
 use crate::config;
 use crate::error::{WalletError, WalletResult};
 use crate::types::{
-    AddConfiguredTokenRequest, AddressResponse, ConfiguredExplorerResponse,
-    ConfiguredRpcResponse, ConfiguredTokenResponse, NetworkModuleStatus,
-    RemoveConfiguredRpcRequest, RemoveConfiguredTokenRequest, ServiceInfoResponse,
-    SetConfiguredRpcRequest, TransferRequest, TransferResponse, WalletNetworkInfoResponse,
+    AddConfiguredNftCollectionRequest, AddConfiguredTokenRequest, AddEvmNetworkRequest,
+    AddressResponse, BitcoinDataSource, BitcoinPsbtResponse, BitcoinSentTxRecord,
+    CkbtcUpdateBalanceResponse, ConfiguredExplorerResponse, ConfiguredNftCollectionResponse,
+    ConfiguredRpcResponse, ConfiguredTokenResponse, CustomEvmNetworkResponse,
+    Erc20AllowanceRequest, Erc20AllowanceResponse, Erc20ApprovalsRequest, Erc20ApprovalsResponse,
+    EvmBatchTransferRequest, EvmBatchTransferResponse, EvmFeeEstimateResponse,
+    EvmSwapQuoteResponse, EvmSwapRequest, EvmSwapResponse, NameResolutionRecord,
+    NetworkModuleStatus, NftOwnershipRequest, NftOwnershipResponse,
+    RemoveConfiguredNftCollectionRequest, RemoveConfiguredRpcRequest, RemoveConfiguredTokenRequest,
+    ServiceInfoResponse, SetConfiguredRpcRequest, SolanaCloseTokenAccountsResponse,
+    SolanaNonceAccountResponse, TransferRequest, TransferResponse, WalletNetworkInfoResponse,
 };
-use crate::{evm_rpc, state, token_registry};
+use crate::{evm_rpc, name_service, state, token_registry};
 
 const API_VERSION: &str = "0.1.0";
 
@@ -146,14 +154,43 @@ fn wallet_networks() -> Vec<WalletNetworkInfoResponse> {
             supports_balance: info.supports_balance,
             default_rpc_url: config::rpc_config::configured_rpc(info.id).map(|c| c.rpc_url),
         })
+        .chain(
+            state::custom_evm_networks()
+                .into_iter()
+                .map(|n| WalletNetworkInfoResponse {
+                    default_rpc_url: config::rpc_config::configured_rpc(&n.id).map(|c| c.rpc_url),
+                    id: n.id,
+                    primary_symbol: n.native_symbol,
+                    address_family: "evm".to_string(),
+                    shared_address_group: "evm-secp256k1-hex-v1".to_string(),
+                    supports_send: true,
+                    supports_balance: true,
+                }),
+        )
         .collect()
 }
 
+#[ic_cdk::query]
+fn custom_evm_networks() -> Vec<CustomEvmNetworkResponse> {
+    state::custom_evm_networks()
+}
+
 #[ic_cdk::query]
 fn configured_rpcs() -> Vec<ConfiguredRpcResponse> {
     state::configured_rpcs()
 }
 
+#[ic_cdk::query]
+fn bitcoin_data_source() -> BitcoinDataSource {
+    state::bitcoin_data_source()
+}
+
+#[ic_cdk::update]
+fn set_bitcoin_data_source(source: BitcoinDataSource) -> WalletResult<BitcoinDataSource> {
+    require_owner()?;
+    Ok(state::set_bitcoin_data_source(source))
+}
+
 #[ic_cdk::query]
 fn configured_tokens(network: String) -> Vec<ConfiguredTokenResponse> {
     let request_network = normalize_network_name_key(&network);
@@ -177,14 +214,28 @@ fn configured_tokens(network: String) -> Vec<ConfiguredTokenResponse> {
     merged
 }
 
+#[ic_cdk::query]
+fn configured_nft_collections(network: String) -> Vec<ConfiguredNftCollectionResponse> {
+    state::nft_collections_for_network(&normalize_network_name_key(&network))
+}
+
 #[ic_cdk::query]
 fn configured_explorer(network: String) -> Option<ConfiguredExplorerResponse> {
     let request_network = normalize_network_name_key(&network);
-    config::explorer_config::configured_explorer(&network).map(|c| ConfiguredExplorerResponse {
-        network: request_network,
-        address_url_template: c.address_url_template.to_string(),
-        token_url_template: c.token_url_template.map(ToString::to_string),
-    })
+    if let Some(c) = config::explorer_config::configured_explorer(&network) {
+        return Some(ConfiguredExplorerResponse {
+            network: request_network,
+            address_url_template: c.address_url_template.to_string(),
+            token_url_template: c.token_url_template.map(ToString::to_string),
+        });
+    }
+    config::rpc_config::custom_evm_network(&request_network)
+        .and_then(|n| n.explorer_template)
+        .map(|template| ConfiguredExplorerResponse {
+            network: request_network,
+            address_url_template: template,
+            token_url_template: None,
+        })
 }
 
 #[ic_cdk::update]
@@ -212,6 +263,33 @@ fn remove_configured_token(req: RemoveConfiguredTokenRequest) -> WalletResult<bo
     Ok(state::remove_token(&network, token_address))
 }
 
+#[ic_cdk::update]
+async fn add_configured_nft_collection(
+    req: AddConfiguredNftCollectionRequest,
+) -> WalletResult<ConfiguredNftCollectionResponse> {
+    require_owner()?;
+    ensure_not_paused()?;
+    let normalized_network = normalize_network_name_key(&req.network);
+    let mut discovered =
+        token_registry::discover_nft_collection(&normalized_network, &req.contract_address).await?;
+    discovered.network = normalized_network;
+    state::upsert_nft_collection(discovered.clone());
+    Ok(discovered)
+}
+
+#[ic_cdk::update]
+fn remove_configured_nft_collection(
+    req: RemoveConfiguredNftCollectionRequest,
+) -> WalletResult<bool> {
+    require_owner()?;
+    let network = normalize_network_name_key(&req.network);
+    let contract_address = req.contract_address.trim().to_lowercase();
+    if contract_address.is_empty() {
+        return Err(WalletError::invalid_input("contract_address is required"));
+    }
+    Ok(state::remove_nft_collection(&network, &contract_address))
+}
+
 #[ic_cdk::update]
 fn set_configured_rpc(req: SetConfiguredRpcRequest) -> WalletResult<ConfiguredRpcResponse> {
     require_owner()?;
@@ -219,7 +297,9 @@ fn set_configured_rpc(req: SetConfiguredRpcRequest) -> WalletResult<ConfiguredRp
     if network.is_empty() {
         return Err(WalletError::invalid_input("network is required"));
     }
-    if config::rpc_config::wallet_network_info(&network).is_none() {
+    if config::rpc_config::wallet_network_info(&network).is_none()
+        && config::rpc_config::custom_evm_network(&network).is_none()
+    {
         return Err(WalletError::invalid_input("unsupported network"));
     }
     let rpc_url = req.rpc_url.trim();
@@ -243,6 +323,105 @@ fn remove_configured_rpc(req: RemoveConfiguredRpcRequest) -> WalletResult<bool>
     Ok(state::remove_configured_rpc(&network))
 }
 
+#[ic_cdk::update]
+async fn resolve_name(network: String, name: String) -> WalletResult<NameResolutionRecord> {
+    require_owner()?;
+    ensure_not_paused()?;
+    name_service::resolve_recipient(&network, &name)
+        .await?
+        .ok_or_else(|| {
+            WalletError::invalid_input(format!("{name} is not a supported name on {network}"))
+        })
+}
+
+#[ic_cdk::query]
+fn name_resolutions() -> Vec<NameResolutionRecord> {
+    state::name_resolutions()
+}
+
+#[ic_cdk::update]
+fn add_evm_network(req: AddEvmNetworkRequest) -> WalletResult<CustomEvmNetworkResponse> {
+    require_owner()?;
+    let id = normalize_network_name_key(&req.id);
+    if id.is_empty() {
+        return Err(WalletError::invalid_input("id is required"));
+    }
+    if !id
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+    {
+        return Err(WalletError::invalid_input(
+            "id may only contain letters, digits, '_' and '-'",
+        ));
+    }
+    if config::rpc_config::is_builtin_network(&id) {
+        return Err(WalletError::invalid_input(format!(
+            "id conflicts with built-in network: {id}"
+        )));
+    }
+    if req.chain_id == 0 {
+        return Err(WalletError::invalid_input("chain_id must be > 0"));
+    }
+    let rpc_url = req.rpc_url.trim();
+    // HTTPS outcalls reject plain-http endpoints, so catch them at registration.
+    if !rpc_url.starts_with("https://") || rpc_url.len() == "https://".len() {
+        return Err(WalletError::invalid_input(
+            "rpc_url must be an https:// URL",
+        ));
+    }
+    let native_symbol = req.native_symbol.trim();
+    if native_symbol.is_empty() {
+        return Err(WalletError::invalid_input("native_symbol is required"));
+    }
+    let explorer_template = req
+        .explorer_template
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(ToString::to_string);
+    if let Some(template) = explorer_template.as_deref() {
+        if !template.contains("{address}") {
+            return Err(WalletError::invalid_input(
+                "explorer_template must contain {address}",
+            ));
+        }
+    }
+
+    let network = CustomEvmNetworkResponse {
+        id: id.clone(),
+        chain_id: req.chain_id,
+        native_symbol: native_symbol.to_string(),
+        explorer_template,
+    };
+    state::upsert_custom_evm_network(network.clone());
+    state::upsert_configured_rpc(&id, rpc_url);
+    Ok(network)
+}
+
+#[ic_cdk::update]
+fn remove_evm_network(id: String) -> WalletResult<bool> {
+    require_owner()?;
+    let id = normalize_network_name_key(&id);
+    if id.is_empty() {
+        return Err(WalletError::invalid_input("id is required"));
+    }
+    let removed = state::remove_custom_evm_network(&id);
+    if removed {
+        state::remove_configured_rpc(&id);
+    }
+    Ok(removed)
+}
+
+fn require_evm_network(network: &str) -> WalletResult<String> {
+    let network = normalize_network_name_key(network);
+    if !config::rpc_config::is_evm_network(&network) {
+        return Err(WalletError::invalid_input(format!(
+            "unsupported EVM network: {network}; register it with add_evm_network"
+        )));
+    }
+    Ok(network)
+}
+
 fn normalize_network_name_key(input: &str) -> String {
     token_registry::normalize_network_name(input)
 }
@@ -287,7 +466,12 @@ macro_rules! evm_native_transfer_update {
         #[ic_cdk::update]
         async fn $name(req: TransferRequest) -> WalletResult<TransferResponse> {
             ensure_not_paused()?;
-            evm_rpc::transfer_native_eth($network, req).await
+            let (req, resolved_to) =
+                name_service::resolve_transfer_recipient($network, req).await?;
+            name_service::with_resolved_recipient(
+                evm_rpc::transfer_native_eth($network, req).await,
+                resolved_to,
+            )
         }
     };
 }
@@ -297,12 +481,97 @@ macro_rules! evm_token_transfer_update {
         #[ic_cdk::update]
         async fn $name(req: TransferRequest) -> WalletResult<TransferResponse> {
             ensure_not_paused()?;
-            evm_rpc::transfer_erc20($network, req).await
+            let (req, resolved_to) =
+                name_service::resolve_transfer_recipient($network, req).await?;
+            name_service::with_resolved_recipient(
+                evm_rpc::transfer_erc20($network, req).await,
+                resolved_to,
+            )
+        }
+    };
+}
+
+macro_rules! evm_nft_transfer_update {
+    ($name:ident, $network:literal) => {
+        #[ic_cdk::update]
+        async fn $name(req: TransferRequest) -> WalletResult<TransferResponse> {
+            ensure_not_paused()?;
+            let (req, resolved_to) =
+                name_service::resolve_transfer_recipient($network, req).await?;
+            name_service::with_resolved_recipient(
+                evm_rpc::transfer_nft($network, req).await,
+                resolved_to,
+            )
+        }
+    };
+}
+
+macro_rules! evm_nft_ownership_update {
+    ($name:ident, $network:literal) => {
+        #[ic_cdk::update]
+        async fn $name(req: NftOwnershipRequest) -> WalletResult<NftOwnershipResponse> {
+            ensure_not_paused()?;
+            evm_rpc::get_nft_ownership($network, req).await
+        }
+    };
+}
+
+macro_rules! evm_erc20_approval_updates {
+    ($approve:ident, $allowance:ident, $revoke:ident, $approvals:ident, $network:literal) => {
+        #[ic_cdk::update]
+        async fn $approve(req: TransferRequest) -> WalletResult<TransferResponse> {
+            ensure_not_paused()?;
+            evm_rpc::approve_erc20($network, req).await
+        }
+
+        #[ic_cdk::update]
+        async fn $allowance(req: Erc20AllowanceRequest) -> WalletResult<Erc20AllowanceResponse> {
+            ensure_not_paused()?;
+            evm_rpc::get_erc20_allowance($network, req).await
+        }
+
+        #[ic_cdk::update]
+        async fn $revoke(req: Erc20AllowanceRequest) -> WalletResult<TransferResponse> {
+            ensure_not_paused()?;
+            evm_rpc::revoke_erc20($network, req).await
+        }
+
+        #[ic_cdk::update]
+        async fn $approvals(req: Erc20ApprovalsRequest) -> WalletResult<Erc20ApprovalsResponse> {
+            ensure_not_paused()?;
+            evm_rpc::list_erc20_approvals($network, req).await
         }
     };
 }
 
 address_update!(bitcoin_request_address, bitcoin);
+address_update!(bitcoin_testnet_request_address, bitcoin_testnet);
+address_update!(bitcoin_signet_request_address, bitcoin_signet);
+address_update!(bitcoin_regtest_request_address, bitcoin_regtest);
+
+#[ic_cdk::update]
+async fn bitcoin_request_p2wpkh_address() -> WalletResult<AddressResponse> {
+    ensure_not_paused()?;
+    bitcoin::request_p2wpkh_address().await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_testnet_request_p2wpkh_address() -> WalletResult<AddressResponse> {
+    ensure_not_paused()?;
+    bitcoin_testnet::request_p2wpkh_address().await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_signet_request_p2wpkh_address() -> WalletResult<AddressResponse> {
+    ensure_not_paused()?;
+    bitcoin_signet::request_p2wpkh_address().await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_regtest_request_p2wpkh_address() -> WalletResult<AddressResponse> {
+    ensure_not_paused()?;
+    bitcoin_regtest::request_p2wpkh_address().await
+}
 address_update!(ethereum_request_address, ethereum);
 address_update!(base_request_address, ethereum);
 address_update!(bsc_request_address, ethereum);
@@ -315,6 +584,8 @@ address_update!(internet_computer_request_address, internet_computer);
 address_update!(sepolia_request_address, sepolia);
 address_update!(solana_request_address, solana);
 address_update!(solana_testnet_request_address, solana_testnet);
+address_update!(solana_devnet_request_address, solana_devnet);
+address_update!(solana_localnet_request_address, solana_localnet);
 address_update!(tron_request_address, tron);
 address_update!(ton_mainnet_request_address, ton_mainnet);
 address_update!(near_mainnet_request_address, near_mainnet);
@@ -327,6 +598,245 @@ async fn bitcoin_transfer_btc(req: TransferRequest) -> WalletResult<TransferResp
     bitcoin::transfer(req).await
 }
 
+#[ic_cdk::update]
+async fn bitcoin_transfer_many(
+    outputs: Vec<(String, String)>,
+    confirmed_only: Option<bool>,
+) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin::transfer_many(outputs, confirmed_only.unwrap_or(false)).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_bump_fee(txid: String, new_fee_rate: u64) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin::bump_fee(&txid, new_fee_rate).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_consolidate(max_fee_rate: u64) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin::consolidate(max_fee_rate).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_create_psbt(req: TransferRequest) -> WalletResult<BitcoinPsbtResponse> {
+    ensure_not_paused()?;
+    bitcoin::create_psbt(req).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_sign_psbt(psbt: String) -> WalletResult<BitcoinPsbtResponse> {
+    ensure_not_paused()?;
+    bitcoin::sign_psbt(&psbt).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_testnet_transfer_btc(req: TransferRequest) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_testnet::transfer(req).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_testnet_transfer_many(
+    outputs: Vec<(String, String)>,
+    confirmed_only: Option<bool>,
+) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_testnet::transfer_many(outputs, confirmed_only.unwrap_or(false)).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_testnet_bump_fee(
+    txid: String,
+    new_fee_rate: u64,
+) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_testnet::bump_fee(&txid, new_fee_rate).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_testnet_consolidate(max_fee_rate: u64) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_testnet::consolidate(max_fee_rate).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_testnet_create_psbt(req: TransferRequest) -> WalletResult<BitcoinPsbtResponse> {
+    ensure_not_paused()?;
+    bitcoin_testnet::create_psbt(req).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_testnet_sign_psbt(psbt: String) -> WalletResult<BitcoinPsbtResponse> {
+    ensure_not_paused()?;
+    bitcoin_testnet::sign_psbt(&psbt).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_signet_transfer_btc(req: TransferRequest) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_signet::transfer(req).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_signet_transfer_many(
+    outputs: Vec<(String, String)>,
+    confirmed_only: Option<bool>,
+) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_signet::transfer_many(outputs, confirmed_only.unwrap_or(false)).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_signet_bump_fee(
+    txid: String,
+    new_fee_rate: u64,
+) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_signet::bump_fee(&txid, new_fee_rate).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_signet_consolidate(max_fee_rate: u64) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_signet::consolidate(max_fee_rate).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_signet_create_psbt(req: TransferRequest) -> WalletResult<BitcoinPsbtResponse> {
+    ensure_not_paused()?;
+    bitcoin_signet::create_psbt(req).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_signet_sign_psbt(psbt: String) -> WalletResult<BitcoinPsbtResponse> {
+    ensure_not_paused()?;
+    bitcoin_signet::sign_psbt(&psbt).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_regtest_transfer_btc(req: TransferRequest) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_regtest::transfer(req).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_regtest_transfer_many(
+    outputs: Vec<(String, String)>,
+    confirmed_only: Option<bool>,
+) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_regtest::transfer_many(outputs, confirmed_only.unwrap_or(false)).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_regtest_bump_fee(
+    txid: String,
+    new_fee_rate: u64,
+) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_regtest::bump_fee(&txid, new_fee_rate).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_regtest_consolidate(max_fee_rate: u64) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    bitcoin_regtest::consolidate(max_fee_rate).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_regtest_create_psbt(req: TransferRequest) -> WalletResult<BitcoinPsbtResponse> {
+    ensure_not_paused()?;
+    bitcoin_regtest::create_psbt(req).await
+}
+
+#[ic_cdk::update]
+async fn bitcoin_regtest_sign_psbt(psbt: String) -> WalletResult<BitcoinPsbtResponse> {
+    ensure_not_paused()?;
+    bitcoin_regtest::sign_psbt(&psbt).await
+}
+
+#[ic_cdk::query]
+fn bitcoin_sent_transactions() -> Vec<BitcoinSentTxRecord> {
+    state::bitcoin_sent_txs()
+}
+
+#[ic_cdk::update]
+async fn evm_transfer_native(
+    network: String,
+    req: TransferRequest,
+) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    let network = require_evm_network(&network)?;
+    let (req, resolved_to) = name_service::resolve_transfer_recipient(&network, req).await?;
+    name_service::with_resolved_recipient(
+        evm_rpc::transfer_native_eth(&network, req).await,
+        resolved_to,
+    )
+}
+
+#[ic_cdk::update]
+async fn evm_transfer_erc20(
+    network: String,
+    req: TransferRequest,
+) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    let network = require_evm_network(&network)?;
+    let (req, resolved_to) = name_service::resolve_transfer_recipient(&network, req).await?;
+    name_service::with_resolved_recipient(evm_rpc::transfer_erc20(&network, req).await, resolved_to)
+}
+
+#[ic_cdk::update]
+async fn evm_estimate_fee(
+    network: String,
+    req: TransferRequest,
+) -> WalletResult<EvmFeeEstimateResponse> {
+    ensure_not_paused()?;
+    let network = require_evm_network(&network)?;
+    let (req, _) = name_service::resolve_transfer_recipient(&network, req).await?;
+    evm_rpc::estimate_fee(&network, req).await
+}
+
+#[ic_cdk::update]
+async fn evm_batch_transfer(
+    network: String,
+    req: EvmBatchTransferRequest,
+) -> WalletResult<EvmBatchTransferResponse> {
+    ensure_not_paused()?;
+    let network = require_evm_network(&network)?;
+    let mut req = req;
+    let mut resolved = Vec::with_capacity(req.items.len());
+    for item in req.items.iter_mut() {
+        let record = name_service::resolve_recipient(&network, &item.to).await?;
+        if let Some(record) = &record {
+            item.to = record.address.clone();
+        }
+        resolved.push(record.map(|r| (r.name, r.address)));
+    }
+    name_service::with_resolved_batch_recipients(
+        evm_rpc::batch_transfer(&network, req).await,
+        resolved,
+    )
+}
+
+#[ic_cdk::update]
+async fn evm_swap_quote(
+    network: String,
+    req: EvmSwapRequest,
+) -> WalletResult<EvmSwapQuoteResponse> {
+    ensure_not_paused()?;
+    let network = require_evm_network(&network)?;
+    evm_rpc::swap_quote(&network, req).await
+}
+
+#[ic_cdk::update]
+async fn evm_swap(network: String, req: EvmSwapRequest) -> WalletResult<EvmSwapResponse> {
+    ensure_not_paused()?;
+    let network = require_evm_network(&network)?;
+    evm_rpc::swap(&network, req).await
+}
+
 evm_native_transfer_update!(ethereum_transfer_eth, "ethereum");
 evm_token_transfer_update!(ethereum_transfer_erc20, "ethereum");
 
@@ -354,6 +864,90 @@ evm_token_transfer_update!(okx_transfer_erc20, "okx");
 evm_native_transfer_update!(polygon_transfer_pol, "polygon");
 evm_token_transfer_update!(polygon_transfer_erc20, "polygon");
 
+evm_nft_transfer_update!(ethereum_transfer_nft, "ethereum");
+evm_nft_transfer_update!(sepolia_transfer_nft, "sepolia");
+evm_nft_transfer_update!(base_transfer_nft, "base");
+evm_nft_transfer_update!(bsc_transfer_nft, "bsc");
+evm_nft_transfer_update!(arbitrum_transfer_nft, "arbitrum");
+evm_nft_transfer_update!(optimism_transfer_nft, "optimism");
+evm_nft_transfer_update!(avalanche_transfer_nft, "avalanche");
+evm_nft_transfer_update!(okx_transfer_nft, "okx");
+evm_nft_transfer_update!(polygon_transfer_nft, "polygon");
+
+evm_nft_ownership_update!(ethereum_nft_ownership, "ethereum");
+evm_nft_ownership_update!(sepolia_nft_ownership, "sepolia");
+evm_nft_ownership_update!(base_nft_ownership, "base");
+evm_nft_ownership_update!(bsc_nft_ownership, "bsc");
+evm_nft_ownership_update!(arbitrum_nft_ownership, "arbitrum");
+evm_nft_ownership_update!(optimism_nft_ownership, "optimism");
+evm_nft_ownership_update!(avalanche_nft_ownership, "avalanche");
+evm_nft_ownership_update!(okx_nft_ownership, "okx");
+evm_nft_ownership_update!(polygon_nft_ownership, "polygon");
+
+evm_erc20_approval_updates!(
+    ethereum_erc20_approve,
+    ethereum_erc20_allowance,
+    ethereum_erc20_revoke,
+    ethereum_erc20_approvals,
+    "ethereum"
+);
+evm_erc20_approval_updates!(
+    sepolia_erc20_approve,
+    sepolia_erc20_allowance,
+    sepolia_erc20_revoke,
+    sepolia_erc20_approvals,
+    "sepolia"
+);
+evm_erc20_approval_updates!(
+    base_erc20_approve,
+    base_erc20_allowance,
+    base_erc20_revoke,
+    base_erc20_approvals,
+    "base"
+);
+evm_erc20_approval_updates!(
+    bsc_erc20_approve,
+    bsc_erc20_allowance,
+    bsc_erc20_revoke,
+    bsc_erc20_approvals,
+    "bsc"
+);
+evm_erc20_approval_updates!(
+    arbitrum_erc20_approve,
+    arbitrum_erc20_allowance,
+    arbitrum_erc20_revoke,
+    arbitrum_erc20_approvals,
+    "arbitrum"
+);
+evm_erc20_approval_updates!(
+    optimism_erc20_approve,
+    optimism_erc20_allowance,
+    optimism_erc20_revoke,
+    optimism_erc20_approvals,
+    "optimism"
+);
+evm_erc20_approval_updates!(
+    avalanche_erc20_approve,
+    avalanche_erc20_allowance,
+    avalanche_erc20_revoke,
+    avalanche_erc20_approvals,
+    "avalanche"
+);
+evm_erc20_approval_updates!(
+    okx_erc20_approve,
+    okx_erc20_allowance,
+    okx_erc20_revoke,
+    okx_erc20_approvals,
+    "okx"
+);
+evm_erc20_approval_updates!(
+    polygon_erc20_approve,
+    polygon_erc20_allowance,
+    polygon_erc20_revoke,
+    polygon_erc20_approvals,
+    "polygon"
+);
+
 #[ic_cdk::update]
 async fn internet_computer_transfer_icp(req: TransferRequest) -> WalletResult<TransferResponse> {
     ensure_not_paused()?;
@@ -366,26 +960,191 @@ async fn internet_computer_transfer_icrc(req: TransferRequest) -> WalletResult<T
     internet_computer::transfer_icrc(req).await
 }
 
+#[ic_cdk::update]
+async fn ckbtc_get_btc_address() -> WalletResult<AddressResponse> {
+    ensure_not_paused()?;
+    internet_computer::ckbtc_get_btc_address().await
+}
+
+#[ic_cdk::update]
+async fn ckbtc_update_balance() -> WalletResult<CkbtcUpdateBalanceResponse> {
+    ensure_not_paused()?;
+    internet_computer::ckbtc_update_balance().await
+}
+
+#[ic_cdk::update]
+async fn ckbtc_retrieve_btc_with_approval(
+    address: String,
+    amount: String,
+) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    internet_computer::ckbtc_retrieve_btc_with_approval(&address, &amount).await
+}
+
 #[ic_cdk::update]
 async fn solana_transfer_sol(req: TransferRequest) -> WalletResult<TransferResponse> {
     ensure_not_paused()?;
-    solana::transfer_sol(req).await
+    let (req, resolved_to) = name_service::resolve_transfer_recipient("solana", req).await?;
+    name_service::with_resolved_recipient(solana::transfer_sol(req).await, resolved_to)
 }
 #[ic_cdk::update]
 async fn solana_transfer_spl(req: TransferRequest) -> WalletResult<TransferResponse> {
     ensure_not_paused()?;
-    solana::transfer_spl(req).await
+    let (req, resolved_to) = name_service::resolve_transfer_recipient("solana", req).await?;
+    name_service::with_resolved_recipient(solana::transfer_spl(req).await, resolved_to)
 }
 
 #[ic_cdk::update]
 async fn solana_testnet_transfer_sol(req: TransferRequest) -> WalletResult<TransferResponse> {
     ensure_not_paused()?;
-    solana_testnet::transfer_sol(req).await
+    let (req, resolved_to) =
+        name_service::resolve_transfer_recipient("solana_testnet", req).await?;
+    name_service::with_resolved_recipient(solana_testnet::transfer_sol(req).await, resolved_to)
 }
 #[ic_cdk::update]
 async fn solana_testnet_transfer_spl(req: TransferRequest) -> WalletResult<TransferResponse> {
     ensure_not_paused()?;
-    solana_testnet::transfer_spl(req).await
+    let (req, resolved_to) =
+        name_service::resolve_transfer_recipient("solana_testnet", req).await?;
+    name_service::with_resolved_recipient(solana_testnet::transfer_spl(req).await, resolved_to)
+}
+
+#[ic_cdk::update]
+async fn solana_create_nonce_account() -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    solana::create_nonce_account().await
+}
+
+#[ic_cdk::update]
+async fn solana_nonce_account() -> WalletResult<SolanaNonceAccountResponse> {
+    ensure_not_paused()?;
+    solana::nonce_account().await
+}
+
+#[ic_cdk::update]
+async fn solana_close_token_accounts() -> WalletResult<SolanaCloseTokenAccountsResponse> {
+    ensure_not_paused()?;
+    solana::close_token_accounts().await
+}
+
+#[ic_cdk::update]
+async fn solana_sign_v0_message(message: String) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    solana::sign_v0_message(&message).await
+}
+
+#[ic_cdk::update]
+async fn solana_testnet_create_nonce_account() -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    solana_testnet::create_nonce_account().await
+}
+
+#[ic_cdk::update]
+async fn solana_testnet_nonce_account() -> WalletResult<SolanaNonceAccountResponse> {
+    ensure_not_paused()?;
+    solana_testnet::nonce_account().await
+}
+
+#[ic_cdk::update]
+async fn solana_testnet_close_token_accounts() -> WalletResult<SolanaCloseTokenAccountsResponse> {
+    ensure_not_paused()?;
+    solana_testnet::close_token_accounts().await
+}
+
+#[ic_cdk::update]
+async fn solana_testnet_sign_v0_message(message: String) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    solana_testnet::sign_v0_message(&message).await
+}
+
+#[ic_cdk::update]
+async fn solana_devnet_transfer_sol(req: TransferRequest) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    let (req, resolved_to) = name_service::resolve_transfer_recipient("solana_devnet", req).await?;
+    name_service::with_resolved_recipient(solana_devnet::transfer_sol(req).await, resolved_to)
+}
+
+#[ic_cdk::update]
+async fn solana_devnet_transfer_spl(req: TransferRequest) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    let (req, resolved_to) = name_service::resolve_transfer_recipient("solana_devnet", req).await?;
+    name_service::with_resolved_recipient(solana_devnet::transfer_spl(req).await, resolved_to)
+}
+
+#[ic_cdk::update]
+async fn solana_devnet_create_nonce_account() -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    solana_devnet::create_nonce_account().await
+}
+
+#[ic_cdk::update]
+async fn solana_devnet_nonce_account() -> WalletResult<SolanaNonceAccountResponse> {
+    ensure_not_paused()?;
+    solana_devnet::nonce_account().await
+}
+
+#[ic_cdk::update]
+async fn solana_devnet_close_token_accounts() -> WalletResult<SolanaCloseTokenAccountsResponse> {
+    ensure_not_paused()?;
+    solana_devnet::close_token_accounts().await
+}
+
+#[ic_cdk::update]
+async fn solana_devnet_sign_v0_message(message: String) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    solana_devnet::sign_v0_message(&message).await
+}
+
+#[ic_cdk::update]
+async fn solana_devnet_request_airdrop(amount: String) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    solana_devnet::request_airdrop(&amount).await
+}
+
+#[ic_cdk::update]
+async fn solana_localnet_transfer_sol(req: TransferRequest) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    let (req, resolved_to) =
+        name_service::resolve_transfer_recipient("solana_localnet", req).await?;
+    name_service::with_resolved_recipient(solana_localnet::transfer_sol(req).await, resolved_to)
+}
+
+#[ic_cdk::update]
+async fn solana_localnet_transfer_spl(req: TransferRequest) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    let (req, resolved_to) =
+        name_service::resolve_transfer_recipient("solana_localnet", req).await?;
+    name_service::with_resolved_recipient(solana_localnet::transfer_spl(req).await, resolved_to)
+}
+
+#[ic_cdk::update]
+async fn solana_localnet_create_nonce_account() -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    solana_localnet::create_nonce_account().await
+}
+
+#[ic_cdk::update]
+async fn solana_localnet_nonce_account() -> WalletResult<SolanaNonceAccountResponse> {
+    ensure_not_paused()?;
+    solana_localnet::nonce_account().await
+}
+
+#[ic_cdk::update]
+async fn solana_localnet_close_token_accounts() -> WalletResult<SolanaCloseTokenAccountsResponse> {
+    ensure_not_paused()?;
+    solana_localnet::close_token_accounts().await
+}
+
+#[ic_cdk::update]
+async fn solana_localnet_sign_v0_message(message: String) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    solana_localnet::sign_v0_message(&message).await
+}
+
+#[ic_cdk::update]
+async fn solana_localnet_request_airdrop(amount: String) -> WalletResult<TransferResponse> {
+    ensure_not_paused()?;
+    solana_localnet::request_airdrop(&amount).await
 }
 
 #[ic_cdk::update]
@@ -403,24 +1162,28 @@ async fn tron_transfer_trc20(req: TransferRequest) -> WalletResult<TransferRespo
 #[ic_cdk::update]
 async fn ton_mainnet_transfer_ton(req: TransferRequest) -> WalletResult<TransferResponse> {
     ensure_not_paused()?;
-    ton_mainnet::transfer(req).await
+    let (req, resolved_to) = name_service::resolve_transfer_recipient("ton_mainnet", req).await?;
+    name_service::with_resolved_recipient(ton_mainnet::transfer(req).await, resolved_to)
 }
 
 #[ic_cdk::update]
 async fn ton_mainnet_transfer_jetton(req: TransferRequest) -> WalletResult<TransferResponse> {
     ensure_not_paused()?;
-    ton_mainnet::transfer(req).await
+    let (req, resolved_to) = name_service::resolve_transfer_recipient("ton_mainnet", req).await?;
+    name_service::with_resolved_recipient(ton_mainnet::transfer(req).await, resolved_to)
 }
 
 #[ic_cdk::update]
 async fn near_mainnet_transfer_near(req: TransferRequest) -> WalletResult<TransferResponse> {
     ensure_not_paused()?;
-    near_mainnet::transfer(req).await
+    let (req, resolved_to) = name_service::resolve_transfer_recipient("near_mainnet", req).await?;
+    name_service::with_resolved_recipient(near_mainnet::transfer(req).await, resolved_to)
 }
 #[ic_cdk::update]
 async fn near_mainnet_transfer_nep141(req: TransferRequest) -> WalletResult<TransferResponse> {
     ensure_not_paused()?;
-    near_mainnet::transfer(req).await
+    let (req, resolved_to) = name_service::resolve_transfer_recipient("near_mainnet", req).await?;
+    name_service::with_resolved_recipient(near_mainnet::transfer(req).await, resolved_to)
 }
 
 #[ic_cdk::update]
@@ -196,6 +196,7 @@ pub async fn transfer(req: TransferRequest) -> WalletResult<TransferResponse> {
         }),
         message: "signed Aptos transaction prepared; frontend should POST to /transactions"
             .to_string(),
+        resolved_to: None,
     })
 }
 
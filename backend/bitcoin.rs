@@ -1,30 +1,47 @@
 use crate::addressing;
 use crate::config;
 use crate::error::{WalletError, WalletResult};
+use crate::sdk::btc_psbt::{self, Psbt};
+use crate::state;
 use crate::types::{
-    self, AddressResponse, BalanceRequest, BalanceResponse, BroadcastHttpRequest, TransferRequest,
-    TransferResponse,
+    self, AddressResponse, BalanceRequest, BalanceResponse, BitcoinDataSource, BitcoinPsbtResponse,
+    BitcoinSentTxRecord, BitcoinTxInputRecord, BitcoinTxOutputRecord, BroadcastHttpRequest,
+    TransferRequest, TransferResponse,
 };
 use candid::Nat;
-use ic_cdk::bitcoin_canister::{Outpoint, Utxo};
+use ic_cdk::bitcoin_canister::{
+    self as btc_canister, GetCurrentFeePercentilesRequest, GetUtxosRequest,
+    Network as BitcoinNetwork, Outpoint, SendTransactionRequest, Utxo, UtxosFilter,
+};
 use ic_cdk::management_canister::{
-    self, Bip341, SchnorrAlgorithm, SchnorrAux, SchnorrKeyId, SignWithSchnorrArgs,
+    self, Bip341, EcdsaCurve, EcdsaKeyId, SchnorrAlgorithm, SchnorrAux, SchnorrKeyId,
+    SignWithEcdsaArgs, SignWithSchnorrArgs,
 };
+use k256::ecdsa::Signature as EcdsaSignature;
 use k256::elliptic_curve::bigint::U256;
 use k256::elliptic_curve::ops::Reduce;
 use k256::elliptic_curve::sec1::ToEncodedPoint;
 use k256::schnorr::VerifyingKey as SchnorrVerifyingKey;
 use k256::{ProjectivePoint, PublicKey, Scalar};
+use ripemd::Ripemd160;
 use serde::Deserialize;
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 
-const NETWORK_NAME: &str = types::networks::BITCOIN;
 const BTC_DECIMALS: u8 = 8;
 const DEFAULT_FEE_RATE_SAT_PER_VB: u64 = 5;
 const MIN_CHANGE_SATS: u64 = 330;
+const BTC_TRANSFER_MANY_MAX_OUTPUTS: usize = 250;
+// `TransferRequest.metadata` flag ("true") restricting coin selection to confirmed UTXOs.
+const CONFIRMED_ONLY_METADATA_KEY: &str = "confirmed_only";
+const BTC_BNB_MAX_TRIES: usize = 100_000;
+const BTC_CONSOLIDATE_MAX_INPUTS: usize = 100;
+const BTC_OP_RETURN_MAX_BYTES: usize = 80;
 const SIGHASH_DEFAULT: u8 = 0x00;
+const SIGHASH_ALL: u8 = 0x01;
 const SEQUENCE_FINAL: u32 = 0xffff_ffff;
+// BIP-125 opt-in replace-by-fee: any input sequence below 0xffff_fffe.
+const SEQUENCE_RBF: u32 = 0xffff_fffd;
 const TX_VERSION: u32 = 2;
 const TX_LOCKTIME: u32 = 0;
 const BECH32_CONST: u32 = 1;
@@ -57,18 +74,140 @@ struct MempoolUtxoResponse {
     status: MempoolUtxoStatus,
 }
 
+#[derive(Deserialize)]
+struct MempoolPrevout {
+    scriptpubkey: String,
+    value: u64,
+}
+
+#[derive(Deserialize)]
+struct MempoolTxInput {
+    txid: String,
+    vout: u32,
+    prevout: MempoolPrevout,
+    sequence: u32,
+}
+
+#[derive(Deserialize)]
+struct MempoolTxOutput {
+    scriptpubkey: String,
+    value: u64,
+}
+
+#[derive(Deserialize)]
+struct MempoolTxResponse {
+    txid: String,
+    vin: Vec<MempoolTxInput>,
+    vout: Vec<MempoolTxOutput>,
+    weight: u64,
+    fee: u64,
+    status: MempoolUtxoStatus,
+}
+
+/// Per-network parameters; every network reuses the same Taproot key derivation.
+#[derive(Clone, Copy, Debug)]
+struct BtcNet {
+    name: &'static str,
+    hrp: &'static str,
+    p2pkh_version: u8,
+    p2sh_version: u8,
+    // `None` when the ICP Bitcoin canister does not serve the network (signet).
+    canister_network: Option<BitcoinNetwork>,
+}
+
+const BTC_NETWORKS: &[BtcNet] = &[
+    BtcNet {
+        name: types::networks::BITCOIN,
+        hrp: "bc",
+        p2pkh_version: 0x00,
+        p2sh_version: 0x05,
+        canister_network: Some(BitcoinNetwork::Mainnet),
+    },
+    BtcNet {
+        name: types::networks::BITCOIN_TESTNET,
+        hrp: "tb",
+        p2pkh_version: 0x6f,
+        p2sh_version: 0xc4,
+        canister_network: Some(BitcoinNetwork::Testnet),
+    },
+    BtcNet {
+        name: types::networks::BITCOIN_SIGNET,
+        hrp: "tb",
+        p2pkh_version: 0x6f,
+        p2sh_version: 0xc4,
+        canister_network: None,
+    },
+    BtcNet {
+        name: types::networks::BITCOIN_REGTEST,
+        hrp: "bcrt",
+        p2pkh_version: 0x6f,
+        p2sh_version: 0xc4,
+        canister_network: Some(BitcoinNetwork::Regtest),
+    },
+];
+
+impl BtcNet {
+    /// Bitcoin canister network to use, when the owner selected the canister data source.
+    fn bitcoin_canister(&self) -> Option<BitcoinNetwork> {
+        self.bitcoin_canister_for(state::bitcoin_data_source())
+    }
+
+    fn bitcoin_canister_for(&self, source: BitcoinDataSource) -> Option<BitcoinNetwork> {
+        match source {
+            BitcoinDataSource::BitcoinCanister => self.canister_network,
+            BitcoinDataSource::Esplora => None,
+        }
+    }
+}
+
+fn btc_net(network: &str) -> WalletResult<BtcNet> {
+    BTC_NETWORKS
+        .iter()
+        .find(|net| net.name == network)
+        .copied()
+        .ok_or_else(|| {
+            WalletError::invalid_input(format!("unsupported bitcoin network: {network}"))
+        })
+}
+
 #[derive(Clone, Debug)]
 struct WalletBtcKey {
     address: String,
     key_name: String,
     internal_key_x_only: [u8; 32],
     taproot_witness_program: [u8; 32],
+    p2wpkh: WalletP2wpkhKey,
+}
+
+/// SegWit v0 key: the threshold ECDSA secp256k1 key shared with EVM and TRON.
+#[derive(Clone, Debug)]
+struct WalletP2wpkhKey {
+    address: String,
+    key_name: String,
+    public_key: Vec<u8>,
+    pubkey_hash: [u8; 20],
+}
+
+impl WalletBtcKey {
+    fn taproot_script(&self) -> Vec<u8> {
+        script_pubkey_p2tr(&self.taproot_witness_program)
+    }
+
+    fn p2wpkh_script(&self) -> Vec<u8> {
+        script_pubkey_p2wpkh(&self.p2wpkh.pubkey_hash)
+    }
+
+    fn owns_script(&self, script: &[u8]) -> bool {
+        script == self.taproot_script() || script == self.p2wpkh_script()
+    }
 }
 
 #[derive(Clone, Debug)]
 struct TxInputTemplate {
     utxo: Utxo,
     sequence: u32,
+    /// Script of the output being spent; selects the signing path and witness size.
+    script_pubkey: Vec<u8>,
 }
 
 #[derive(Clone, Debug)]
@@ -78,10 +217,15 @@ struct TxOutputTemplate {
 }
 
 pub async fn request_address() -> WalletResult<AddressResponse> {
-    let wallet_key = derive_wallet_key().await?;
+    request_address_for_network(types::networks::BITCOIN).await
+}
+
+pub async fn request_address_for_network(network: &str) -> WalletResult<AddressResponse> {
+    let net = btc_net(network)?;
+    let wallet_key = derive_wallet_key(net).await?;
 
     Ok(AddressResponse {
-        network: NETWORK_NAME.to_string(),
+        network: net.name.to_string(),
         address: wallet_key.address,
         public_key_hex: addressing::hex_encode(&wallet_key.internal_key_x_only),
         key_name: wallet_key.key_name,
@@ -89,7 +233,26 @@ pub async fn request_address() -> WalletResult<AddressResponse> {
     })
 }
 
+pub async fn request_p2wpkh_address() -> WalletResult<AddressResponse> {
+    request_p2wpkh_address_for_network(types::networks::BITCOIN).await
+}
+
+/// Native SegWit v0 (`bc1q...`) address of the ECDSA key; spendable alongside the Taproot one.
+pub async fn request_p2wpkh_address_for_network(network: &str) -> WalletResult<AddressResponse> {
+    let net = btc_net(network)?;
+    let wallet_key = derive_wallet_key(net).await?;
+
+    Ok(AddressResponse {
+        network: net.name.to_string(),
+        address: wallet_key.p2wpkh.address,
+        public_key_hex: addressing::hex_encode(&wallet_key.p2wpkh.public_key),
+        key_name: wallet_key.p2wpkh.key_name,
+        message: Some("Derived P2WPKH address from management canister ECDSA public key".into()),
+    })
+}
+
 pub async fn get_balance(req: BalanceRequest) -> WalletResult<BalanceResponse> {
+    let net = btc_net(types::networks::BITCOIN)?;
     validate_account(&req.account)?;
     if req.token.as_deref().is_some_and(|t| !t.trim().is_empty()) {
         return Err(WalletError::invalid_input(
@@ -98,7 +261,7 @@ pub async fn get_balance(req: BalanceRequest) -> WalletResult<BalanceResponse> {
     }
 
     let address = req.account.trim().to_string();
-    let mempool_addr = fetch_address_stats(&address).await?;
+    let mempool_addr = fetch_address_stats(net, &address).await?;
     let confirmed_sats = mempool_addr
         .chain_stats
         .funded_txo_sum
@@ -110,7 +273,7 @@ pub async fn get_balance(req: BalanceRequest) -> WalletResult<BalanceResponse> {
     let sats = confirmed_sats.saturating_add(pending_delta);
 
     Ok(BalanceResponse {
-        network: NETWORK_NAME.to_string(),
+        network: net.name.to_string(),
         account: address,
         token: None,
         amount: Some(format_sats_btc(sats)),
@@ -122,71 +285,846 @@ pub async fn get_balance(req: BalanceRequest) -> WalletResult<BalanceResponse> {
 }
 
 pub async fn transfer(req: TransferRequest) -> WalletResult<TransferResponse> {
-    validate_transfer(&req)?;
+    transfer_for_network(types::networks::BITCOIN, req).await
+}
+
+pub async fn transfer_for_network(
+    network: &str,
+    req: TransferRequest,
+) -> WalletResult<TransferResponse> {
+    let net = btc_net(network)?;
+    let wallet_key = derive_wallet_key(net).await?;
+    let (payments, confirmed_only) = transfer_payments(net, &wallet_key, &req)?;
+    send_payments(net, &wallet_key, &payments, confirmed_only).await
+}
+
+fn transfer_payments(
+    net: BtcNet,
+    wallet_key: &WalletBtcKey,
+    req: &TransferRequest,
+) -> WalletResult<(Vec<TxOutputTemplate>, bool)> {
+    validate_transfer(req)?;
     if req.token.as_deref().is_some_and(|t| !t.trim().is_empty()) {
         return Err(WalletError::invalid_input(
             "btc_transfer_btc does not accept token parameter",
         ));
     }
 
-    let wallet_key = derive_wallet_key().await?;
     if let Some(from) = req.from.as_deref() {
         let from = from.trim();
-        if !from.is_empty() && !from.eq_ignore_ascii_case(&wallet_key.address) {
+        if !from.is_empty()
+            && !from.eq_ignore_ascii_case(&wallet_key.address)
+            && !from.eq_ignore_ascii_case(&wallet_key.p2wpkh.address)
+        {
             return Err(WalletError::invalid_input(
                 "from does not match canister-managed BTC address",
             ));
         }
     }
 
-    let amount_sats = parse_decimal_btc_to_sats(req.amount.trim())?;
+    let mut payments = vec![parse_payment(net, &req.to, &req.amount)?];
+    if let Some(memo) = req.memo.as_deref().filter(|m| !m.is_empty()) {
+        payments.push(TxOutputTemplate {
+            value: 0,
+            script_pubkey: script_pubkey_op_return(memo.as_bytes())?,
+        });
+    }
+    let confirmed_only = req
+        .metadata
+        .iter()
+        .any(|(k, v)| k == CONFIRMED_ONLY_METADATA_KEY && v.trim().eq_ignore_ascii_case("true"));
+    Ok((payments, confirmed_only))
+}
+
+pub async fn transfer_many(
+    outputs: Vec<(String, String)>,
+    confirmed_only: bool,
+) -> WalletResult<TransferResponse> {
+    transfer_many_for_network(types::networks::BITCOIN, outputs, confirmed_only).await
+}
+
+/// Pays several recipients from one transaction, sharing inputs, change and fee.
+pub async fn transfer_many_for_network(
+    network: &str,
+    outputs: Vec<(String, String)>,
+    confirmed_only: bool,
+) -> WalletResult<TransferResponse> {
+    let net = btc_net(network)?;
+    if outputs.is_empty() {
+        return Err(WalletError::invalid_input("outputs must not be empty"));
+    }
+    if outputs.len() > BTC_TRANSFER_MANY_MAX_OUTPUTS {
+        return Err(WalletError::invalid_input(format!(
+            "at most {BTC_TRANSFER_MANY_MAX_OUTPUTS} outputs per transaction"
+        )));
+    }
+    let payments = outputs
+        .iter()
+        .enumerate()
+        .map(|(index, (to, amount))| {
+            parse_payment(net, to, amount).map_err(|err| match err {
+                WalletError::InvalidInput(msg) => {
+                    WalletError::invalid_input(format!("outputs[{index}]: {msg}"))
+                }
+                other => other,
+            })
+        })
+        .collect::<WalletResult<Vec<_>>>()?;
+
+    let wallet_key = derive_wallet_key(net).await?;
+    send_payments(net, &wallet_key, &payments, confirmed_only).await
+}
+
+fn parse_payment(net: BtcNet, to: &str, amount: &str) -> WalletResult<TxOutputTemplate> {
+    if to.trim().is_empty() {
+        return Err(WalletError::invalid_input("to is required"));
+    }
+    let amount_sats = parse_decimal_btc_to_sats(amount.trim())?;
     if amount_sats == 0 {
         return Err(WalletError::invalid_input("amount must be > 0"));
     }
+    // Base58Check is case-sensitive; bech32 case is normalized by `decode_segwit_address`.
+    Ok(TxOutputTemplate {
+        value: amount_sats,
+        script_pubkey: script_pubkey_from_btc_address(to.trim(), net)?,
+    })
+}
 
-    let to_address = req.to.trim().to_lowercase();
-    let to_script = script_pubkey_from_btc_address(&to_address, &expected_hrp())?;
+async fn send_payments(
+    net: BtcNet,
+    wallet_key: &WalletBtcKey,
+    payments: &[TxOutputTemplate],
+    confirmed_only: bool,
+) -> WalletResult<TransferResponse> {
     let change_script = script_pubkey_p2tr(&wallet_key.taproot_witness_program);
-    let source_script = change_script.clone();
+    let plan = plan_payments(net, wallet_key, payments, confirmed_only).await?;
+
+    let signed = sign_tx(wallet_key, &plan.inputs, &plan.outputs).await?;
+    let record = sent_tx_record(net, &signed, &plan.inputs, &plan.outputs, &change_script);
+    let response = finish_signed_tx(
+        net,
+        signed,
+        &format!(
+            "{} output(s), fee={} sats, fee_rate={} sat/vB",
+            payments.len(),
+            plan.fee_sats,
+            plan.fee_rate_sat_per_vb
+        ),
+    )
+    .await?;
+    state::record_bitcoin_sent_tx(record);
+    Ok(response)
+}
 
-    let utxos = fetch_all_utxos(&wallet_key.address).await?;
+pub async fn consolidate(max_fee_rate_sat_per_vb: u64) -> WalletResult<TransferResponse> {
+    consolidate_for_network(types::networks::BITCOIN, max_fee_rate_sat_per_vb).await
+}
+
+/// Merges small confirmed UTXOs back into one wallet output, refusing to run while the network
+/// fee rate is above `max_fee_rate_sat_per_vb`.
+pub async fn consolidate_for_network(
+    network: &str,
+    max_fee_rate_sat_per_vb: u64,
+) -> WalletResult<TransferResponse> {
+    let net = btc_net(network)?;
+    if max_fee_rate_sat_per_vb == 0 {
+        return Err(WalletError::invalid_input("max_fee_rate must be > 0"));
+    }
+    let fee_rate = fetch_fee_rate_sat_per_vb(net).await?;
+    if fee_rate > max_fee_rate_sat_per_vb {
+        return Err(WalletError::invalid_input(format!(
+            "current fee rate {fee_rate} sat/vB exceeds max_fee_rate {max_fee_rate_sat_per_vb} sat/vB"
+        )));
+    }
+
+    let wallet_key = derive_wallet_key(net).await?;
+    let own_script = wallet_key.taproot_script();
+    let utxos = fetch_wallet_utxos(net, &wallet_key).await?;
+    let plan = build_consolidation_plan(&utxos, &own_script, fee_rate)?;
+
+    let signed = sign_tx(&wallet_key, &plan.inputs, &plan.outputs).await?;
+    let record = sent_tx_record(net, &signed, &plan.inputs, &plan.outputs, &own_script);
+    let response = finish_signed_tx(
+        net,
+        signed,
+        &format!(
+            "consolidated {} input(s), fee={} sats, fee_rate={} sat/vB",
+            plan.inputs.len(),
+            plan.fee_sats,
+            plan.fee_rate_sat_per_vb
+        ),
+    )
+    .await?;
+    state::record_bitcoin_sent_tx(record);
+    Ok(response)
+}
+
+async fn plan_payments(
+    net: BtcNet,
+    wallet_key: &WalletBtcKey,
+    payments: &[TxOutputTemplate],
+    confirmed_only: bool,
+) -> WalletResult<SpendPlan> {
+    let change_script = wallet_key.taproot_script();
+
+    let utxos = fetch_wallet_utxos(net, wallet_key).await?;
     if utxos.is_empty() {
         return Err(WalletError::Internal("no BTC UTXOs available".into()));
     }
 
-    let fee_rate = fetch_fee_rate_sat_per_vb()
+    let fee_rate = fetch_fee_rate_sat_per_vb(net)
         .await
         .unwrap_or(DEFAULT_FEE_RATE_SAT_PER_VB);
 
-    let plan = build_spend_plan(&utxos, amount_sats, &to_script, &change_script, fee_rate)?;
+    build_spend_plan(&utxos, payments, &change_script, fee_rate, confirmed_only)
+}
+
+pub async fn create_psbt(req: TransferRequest) -> WalletResult<BitcoinPsbtResponse> {
+    create_psbt_for_network(types::networks::BITCOIN, req).await
+}
+
+/// Builds the same spend as `transfer_for_network` but returns it unsigned as a BIP-174 PSBT
+/// with witness UTXOs and BIP-371 internal keys, for review or co-signing in external tools.
+pub async fn create_psbt_for_network(
+    network: &str,
+    req: TransferRequest,
+) -> WalletResult<BitcoinPsbtResponse> {
+    let net = btc_net(network)?;
+    let wallet_key = derive_wallet_key(net).await?;
+    let (payments, confirmed_only) = transfer_payments(net, &wallet_key, &req)?;
+    let plan = plan_payments(net, &wallet_key, &payments, confirmed_only).await?;
+    let psbt = build_psbt(&plan, &wallet_key);
+
+    Ok(BitcoinPsbtResponse {
+        network: net.name.to_string(),
+        psbt_base64: psbt.to_base64(),
+        fee_sats: plan.fee_sats,
+        input_count: plan.inputs.len() as u32,
+        signed_inputs: 0,
+        message: format!(
+            "unsigned BTC PSBT, fee={} sats, fee_rate={} sat/vB",
+            plan.fee_sats, plan.fee_rate_sat_per_vb
+        ),
+    })
+}
+
+pub async fn sign_psbt(psbt_base64: &str) -> WalletResult<BitcoinPsbtResponse> {
+    sign_psbt_for_network(types::networks::BITCOIN, psbt_base64).await
+}
+
+/// Signs every input paying to a canister-managed key: BIP-371 `tap_key_sig` for Taproot,
+/// BIP-174 `partial_sig` for P2WPKH. Inputs owned by other signers are left untouched, so the
+/// PSBT can be co-signed elsewhere.
+pub async fn sign_psbt_for_network(
+    network: &str,
+    psbt_base64: &str,
+) -> WalletResult<BitcoinPsbtResponse> {
+    let net = btc_net(network)?;
+    let mut psbt = Psbt::from_base64(psbt_base64)?;
+    let wallet_key = derive_wallet_key(net).await?;
+    let (tx, owned) = psbt_signing_inputs(&psbt, &wallet_key)?;
+    let taproot_script = wallet_key.taproot_script();
+
+    for &input_index in &owned {
+        let mut witness = sign_input(
+            &wallet_key,
+            tx.version,
+            tx.lock_time,
+            &tx.inputs,
+            &tx.outputs,
+            input_index,
+        )
+        .await?;
+        let sig = witness.swap_remove(0);
+        let map = &mut psbt.inputs[input_index];
+        if tx.inputs[input_index].script_pubkey == taproot_script {
+            btc_psbt::map_set(map, btc_psbt::PSBT_IN_TAP_KEY_SIG, sig);
+        } else {
+            let mut key = vec![btc_psbt::PSBT_IN_PARTIAL_SIG];
+            key.extend_from_slice(&wallet_key.p2wpkh.public_key);
+            map.retain(|(existing, _)| *existing != key);
+            map.push((key, sig));
+        }
+    }
+
+    let total_in: u64 = tx.inputs.iter().map(|i| i.utxo.value).sum();
+    let total_out: u64 = tx.outputs.iter().map(|o| o.value).sum();
+    Ok(BitcoinPsbtResponse {
+        network: net.name.to_string(),
+        psbt_base64: psbt.to_base64(),
+        fee_sats: total_in.saturating_sub(total_out),
+        input_count: tx.inputs.len() as u32,
+        signed_inputs: owned.len() as u32,
+        message: format!(
+            "signed {} of {} PSBT input(s) with the canister-managed BTC keys",
+            owned.len(),
+            tx.inputs.len()
+        ),
+    })
+}
+
+struct PsbtTx {
+    version: u32,
+    lock_time: u32,
+    inputs: Vec<TxInputTemplate>,
+    outputs: Vec<TxOutputTemplate>,
+}
+
+fn build_psbt(plan: &SpendPlan, wallet_key: &WalletBtcKey) -> Psbt {
+    let taproot_script = wallet_key.taproot_script();
+    let internal_key = wallet_key.internal_key_x_only.to_vec();
+    let unsigned_tx = serialize_tx(&plan.inputs, &plan.outputs, &[], false);
+    let mut psbt = Psbt::new(unsigned_tx, plan.inputs.len(), plan.outputs.len());
+    for (map, input) in psbt.inputs.iter_mut().zip(&plan.inputs) {
+        btc_psbt::map_set(
+            map,
+            btc_psbt::PSBT_IN_WITNESS_UTXO,
+            btc_psbt::encode_witness_utxo(input.utxo.value, &input.script_pubkey),
+        );
+        if input.script_pubkey == taproot_script {
+            btc_psbt::map_set(
+                map,
+                btc_psbt::PSBT_IN_TAP_INTERNAL_KEY,
+                internal_key.clone(),
+            );
+        }
+    }
+    for (map, output) in psbt.outputs.iter_mut().zip(&plan.outputs) {
+        if output.script_pubkey == taproot_script {
+            btc_psbt::map_set(
+                map,
+                btc_psbt::PSBT_OUT_TAP_INTERNAL_KEY,
+                internal_key.clone(),
+            );
+        }
+    }
+    psbt
+}
+
+/// Returns the transaction with every input's prevout (Taproot sighashes commit to all of them)
+/// and the indexes of inputs the managed keys can sign.
+fn psbt_signing_inputs(
+    psbt: &Psbt,
+    wallet_key: &WalletBtcKey,
+) -> WalletResult<(PsbtTx, Vec<usize>)> {
+    let taproot_script = wallet_key.taproot_script();
+    let unsigned = psbt.unsigned_tx()?;
+    let mut inputs = Vec::with_capacity(unsigned.inputs.len());
+    let mut owned = Vec::new();
+    for (index, (input, map)) in unsigned.inputs.iter().zip(&psbt.inputs).enumerate() {
+        let witness_utxo = btc_psbt::map_get(map, btc_psbt::PSBT_IN_WITNESS_UTXO)
+            .ok_or_else(|| {
+                WalletError::invalid_input(format!("PSBT input {index} is missing witness_utxo"))
+            })
+            .and_then(btc_psbt::decode_witness_utxo)?;
+        if wallet_key.owns_script(&witness_utxo.script_pubkey) {
+            let is_taproot = witness_utxo.script_pubkey == taproot_script;
+            if let Some(key) = btc_psbt::map_get(map, btc_psbt::PSBT_IN_TAP_INTERNAL_KEY) {
+                if !is_taproot || key != wallet_key.internal_key_x_only.as_slice() {
+                    return Err(WalletError::invalid_input(format!(
+                        "PSBT input {index} tap_internal_key does not match the managed key"
+                    )));
+                }
+            }
+            let expected_sighash = if is_taproot {
+                SIGHASH_DEFAULT
+            } else {
+                SIGHASH_ALL
+            };
+            if let Some(sighash) = btc_psbt::map_get(map, btc_psbt::PSBT_IN_SIGHASH_TYPE) {
+                if sighash != [expected_sighash, 0, 0, 0] {
+                    return Err(WalletError::invalid_input(format!(
+                        "PSBT input {index} requests an unsupported sighash type"
+                    )));
+                }
+            }
+            owned.push(index);
+        }
+        let mut txid = input.txid_le.to_vec();
+        txid.reverse();
+        inputs.push(TxInputTemplate {
+            utxo: Utxo {
+                outpoint: Outpoint {
+                    txid,
+                    vout: input.vout,
+                },
+                value: witness_utxo.value,
+                height: 0,
+            },
+            sequence: input.sequence,
+            script_pubkey: witness_utxo.script_pubkey,
+        });
+    }
+    if owned.is_empty() {
+        return Err(WalletError::invalid_input(
+            "PSBT has no inputs belonging to the canister-managed BTC keys",
+        ));
+    }
+    let outputs = unsigned
+        .outputs
+        .into_iter()
+        .map(|o| TxOutputTemplate {
+            value: o.value,
+            script_pubkey: o.script_pubkey,
+        })
+        .collect();
+    Ok((
+        PsbtTx {
+            version: unsigned.version,
+            lock_time: unsigned.lock_time,
+            inputs,
+            outputs,
+        },
+        owned,
+    ))
+}
+
+/// Raises the fee of a transaction signed by this canister. Replaceable parents are re-signed
+/// over the same inputs with reduced change (BIP-125); otherwise a child spending our change
+/// output pays for the package (CPFP).
+pub async fn bump_fee(txid: &str, new_fee_rate_sat_per_vb: u64) -> WalletResult<TransferResponse> {
+    bump_fee_for_network(types::networks::BITCOIN, txid, new_fee_rate_sat_per_vb).await
+}
+
+pub async fn bump_fee_for_network(
+    network: &str,
+    txid: &str,
+    new_fee_rate_sat_per_vb: u64,
+) -> WalletResult<TransferResponse> {
+    let net = btc_net(network)?;
+    if new_fee_rate_sat_per_vb == 0 {
+        return Err(WalletError::invalid_input("new_fee_rate must be > 0"));
+    }
+    let wallet_key = derive_wallet_key(net).await?;
+    let own_script = wallet_key.taproot_script();
+    let parent = load_sent_tx(net, txid.trim(), &wallet_key).await?;
+    if let Some(by) = parent.replaced_by.as_deref() {
+        return Err(WalletError::invalid_input(format!(
+            "transaction {} was already replaced by {by}",
+            parent.txid
+        )));
+    }
+
+    if parent.replaceable {
+        replace_by_fee(
+            net,
+            &wallet_key,
+            &own_script,
+            &parent,
+            new_fee_rate_sat_per_vb,
+        )
+        .await
+    } else {
+        child_pays_for_parent(
+            net,
+            &wallet_key,
+            &own_script,
+            &parent,
+            new_fee_rate_sat_per_vb,
+        )
+        .await
+    }
+}
+
+async fn replace_by_fee(
+    net: BtcNet,
+    wallet_key: &WalletBtcKey,
+    own_script: &[u8],
+    parent: &BitcoinSentTxRecord,
+    fee_rate: u64,
+) -> WalletResult<TransferResponse> {
+    let inputs = parent
+        .inputs
+        .iter()
+        .map(|input| {
+            Ok(TxInputTemplate {
+                utxo: Utxo {
+                    outpoint: Outpoint {
+                        txid: parse_txid_hex_to_bytes(&input.txid)?,
+                        vout: input.vout,
+                    },
+                    value: input.value,
+                    height: 0,
+                },
+                sequence: SEQUENCE_RBF,
+                script_pubkey: match input.script_pubkey_hex.as_deref() {
+                    Some(hex) => decode_hex(hex)?,
+                    None => own_script.to_vec(),
+                },
+            })
+        })
+        .collect::<WalletResult<Vec<_>>>()?;
+    let mut outputs = Vec::with_capacity(parent.outputs.len());
+    let mut change_index = None;
+    for output in &parent.outputs {
+        if output.is_change {
+            change_index = Some(outputs.len());
+        }
+        outputs.push(TxOutputTemplate {
+            value: output.value,
+            script_pubkey: decode_hex(&output.script_pubkey_hex)?,
+        });
+    }
+    let change_index = change_index.ok_or_else(|| {
+        WalletError::invalid_input("transaction has no change output to fund a higher fee")
+    })?;
+    let total_in: u64 = inputs.iter().map(|i| i.utxo.value).sum();
+
+    // BIP-125 rule 4: the replacement must also pay for its own relay (1 sat/vB increment).
+    let vsize = estimate_signed_tx_vbytes(&inputs, &outputs) as u64;
+    let fee = (vsize * fee_rate).max(parent.fee_sats + vsize);
+    let delta = fee.saturating_sub(parent.fee_sats);
+    let change = outputs[change_index].value;
+    let fee_sats = if change >= delta + MIN_CHANGE_SATS {
+        outputs[change_index].value = change - delta;
+        fee
+    } else {
+        outputs.remove(change_index);
+        if outputs.iter().all(|o| o.value == 0) {
+            return Err(WalletError::invalid_input(
+                "transaction has no payment output left after dropping change",
+            ));
+        }
+        let vsize = estimate_signed_tx_vbytes(&inputs, &outputs) as u64;
+        let fee = total_in - outputs.iter().map(|o| o.value).sum::<u64>();
+        if fee < (vsize * fee_rate).max(parent.fee_sats + vsize) {
+            return Err(WalletError::invalid_input(format!(
+                "change output ({change} sats) is too small to raise the fee to {fee_rate} sat/vB"
+            )));
+        }
+        fee
+    };
+
+    let signed = sign_tx(wallet_key, &inputs, &outputs).await?;
+    let record = sent_tx_record(net, &signed, &inputs, &outputs, own_script);
+    let replacement_txid = record.txid.clone();
+    let response = finish_signed_tx(
+        net,
+        signed,
+        &format!(
+            "RBF replacement of {}, fee={} sats (was {}), fee_rate={} sat/vB",
+            parent.txid, fee_sats, parent.fee_sats, fee_rate
+        ),
+    )
+    .await?;
+    state::record_bitcoin_sent_tx(record);
+    state::mark_bitcoin_tx_replaced(&parent.txid, &replacement_txid);
+    Ok(response)
+}
+
+async fn child_pays_for_parent(
+    net: BtcNet,
+    wallet_key: &WalletBtcKey,
+    own_script: &[u8],
+    parent: &BitcoinSentTxRecord,
+    fee_rate: u64,
+) -> WalletResult<TransferResponse> {
+    let (vout, parent_output, parent_script) = parent
+        .outputs
+        .iter()
+        .enumerate()
+        .find_map(|(vout, o)| {
+            decode_hex(&o.script_pubkey_hex)
+                .ok()
+                .filter(|script| wallet_key.owns_script(script))
+                .map(|script| (vout, o, script))
+        })
+        .ok_or_else(|| {
+            WalletError::invalid_input(
+                "transaction is not replaceable and has no output to this wallet for CPFP",
+            )
+        })?;
+    if let Some(spender) = state::bitcoin_sent_txs().iter().find(|tx| {
+        tx.replaced_by.is_none()
+            && tx
+                .inputs
+                .iter()
+                .any(|i| i.txid.eq_ignore_ascii_case(&parent.txid) && i.vout == vout as u32)
+    }) {
+        return Err(WalletError::invalid_input(format!(
+            "output {}:{vout} is already spent by {}; bump that transaction instead",
+            parent.txid, spender.txid
+        )));
+    }
+
+    let inputs = vec![TxInputTemplate {
+        utxo: Utxo {
+            outpoint: Outpoint {
+                txid: parse_txid_hex_to_bytes(&parent.txid)?,
+                vout: vout as u32,
+            },
+            value: parent_output.value,
+            height: 0,
+        },
+        sequence: SEQUENCE_RBF,
+        script_pubkey: parent_script,
+    }];
+    let mut outputs = vec![TxOutputTemplate {
+        value: 0,
+        script_pubkey: own_script.to_vec(),
+    }];
+    let child_vsize = estimate_signed_tx_vbytes(&inputs, &outputs) as u64;
+    let package_fee = (parent.vsize + child_vsize) * fee_rate;
+    let child_fee = package_fee.saturating_sub(parent.fee_sats).max(child_vsize);
+    if parent_output.value < child_fee + MIN_CHANGE_SATS {
+        return Err(WalletError::invalid_input(format!(
+            "our output ({} sats) is too small to pay {child_fee} sats for CPFP at {fee_rate} sat/vB",
+            parent_output.value
+        )));
+    }
+    outputs[0].value = parent_output.value - child_fee;
+
+    let signed = sign_tx(wallet_key, &inputs, &outputs).await?;
+    let record = sent_tx_record(net, &signed, &inputs, &outputs, own_script);
+    let response = finish_signed_tx(
+        net,
+        signed,
+        &format!(
+            "CPFP child of {} (not replaceable), child fee={} sats, package fee_rate={} sat/vB",
+            parent.txid, child_fee, fee_rate
+        ),
+    )
+    .await?;
+    state::record_bitcoin_sent_tx(record);
+    Ok(response)
+}
+
+/// Looks up a transaction recorded at signing time, falling back to esplora for ones signed
+/// before records were kept. Confirmed transactions are rejected: via esplora's tx status, or
+/// via the Bitcoin canister's UTXO set in canister mode.
+/// The Bitcoin canister only sees mined blocks: while a transaction is unconfirmed its inputs are
+/// still in the UTXO set and its outputs are not. Anything else means it was mined (or conflicted).
+fn ensure_unconfirmed_in_utxo_set(
+    record: &BitcoinSentTxRecord,
+    utxos: &[Utxo],
+) -> WalletResult<()> {
+    let txid = parse_txid_hex_to_bytes(&record.txid)?;
+    if utxos.iter().any(|utxo| utxo.outpoint.txid == txid) {
+        return Err(WalletError::invalid_input(format!(
+            "transaction {} is already confirmed",
+            record.txid
+        )));
+    }
+    for input in &record.inputs {
+        let input_txid = parse_txid_hex_to_bytes(&input.txid)?;
+        let unspent = utxos
+            .iter()
+            .any(|utxo| utxo.outpoint.txid == input_txid && utxo.outpoint.vout == input.vout);
+        if !unspent {
+            return Err(WalletError::invalid_input(format!(
+                "transaction {} is already confirmed or conflicted: input {}:{} is spent",
+                record.txid, input.txid, input.vout
+            )));
+        }
+    }
+    Ok(())
+}
 
-    let mut witnesses: Vec<Vec<Vec<u8>>> = Vec::with_capacity(plan.inputs.len());
-    for _ in 0..plan.inputs.len() {
-        witnesses.push(Vec::new());
+async fn load_sent_tx(
+    net: BtcNet,
+    txid: &str,
+    wallet_key: &WalletBtcKey,
+) -> WalletResult<BitcoinSentTxRecord> {
+    parse_txid_hex_to_bytes(txid)?;
+    let esplora = net.bitcoin_canister().is_none();
+    if let Some(record) = state::bitcoin_sent_tx(net.name, txid) {
+        if esplora {
+            let status: MempoolUtxoStatus =
+                btc_rpc_get_json(net, &format!("/tx/{txid}/status")).await?;
+            if status.confirmed {
+                return Err(WalletError::invalid_input(format!(
+                    "transaction {txid} is already confirmed"
+                )));
+            }
+        } else {
+            let utxos: Vec<Utxo> = fetch_wallet_utxos(net, wallet_key)
+                .await?
+                .into_iter()
+                .map(|input| input.utxo)
+                .collect();
+            ensure_unconfirmed_in_utxo_set(&record, &utxos)?;
+        }
+        return Ok(record);
+    }
+    if !esplora {
+        return Err(WalletError::invalid_input(format!(
+            "transaction {txid} was not signed by this canister"
+        )));
     }
 
-    for input_index in 0..plan.inputs.len() {
-        let sighash = taproot_key_spend_sighash(
-            TX_VERSION,
-            TX_LOCKTIME,
-            &plan.inputs,
-            &plan.outputs,
+    let tx: MempoolTxResponse = btc_rpc_get_json(net, &format!("/tx/{txid}")).await?;
+    if tx.status.confirmed {
+        return Err(WalletError::invalid_input(format!(
+            "transaction {txid} is already confirmed"
+        )));
+    }
+    let own_script_hex = addressing::hex_encode(&wallet_key.taproot_script());
+    let all_inputs_ours = tx.vin.iter().all(|vin| {
+        decode_hex(&vin.prevout.scriptpubkey).is_ok_and(|script| wallet_key.owns_script(&script))
+    });
+    let signals_rbf = tx.vin.iter().any(|vin| vin.sequence < SEQUENCE_FINAL - 1);
+    Ok(BitcoinSentTxRecord {
+        network: Some(net.name.to_string()),
+        txid: tx.txid,
+        inputs: tx
+            .vin
+            .iter()
+            .map(|vin| BitcoinTxInputRecord {
+                txid: vin.txid.clone(),
+                vout: vin.vout,
+                value: vin.prevout.value,
+                script_pubkey_hex: Some(vin.prevout.scriptpubkey.clone()),
+            })
+            .collect(),
+        outputs: tx
+            .vout
+            .iter()
+            .map(|vout| BitcoinTxOutputRecord {
+                is_change: vout.scriptpubkey.eq_ignore_ascii_case(&own_script_hex),
+                script_pubkey_hex: vout.scriptpubkey.clone(),
+                value: vout.value,
+            })
+            .collect(),
+        vsize: tx.weight.div_ceil(4),
+        fee_sats: tx.fee,
+        replaceable: all_inputs_ours && signals_rbf,
+        created_at_ns: 0,
+        replaced_by: None,
+    })
+}
+
+struct SignedBtcTx {
+    tx_bytes: Vec<u8>,
+    txid: String,
+}
+
+async fn sign_tx(
+    wallet_key: &WalletBtcKey,
+    inputs: &[TxInputTemplate],
+    outputs: &[TxOutputTemplate],
+) -> WalletResult<SignedBtcTx> {
+    let mut witnesses: Vec<Vec<Vec<u8>>> = Vec::with_capacity(inputs.len());
+    for input_index in 0..inputs.len() {
+        witnesses.push(
+            sign_input(
+                wallet_key,
+                TX_VERSION,
+                TX_LOCKTIME,
+                inputs,
+                outputs,
+                input_index,
+            )
+            .await?,
+        );
+    }
+
+    let tx_bytes = serialize_tx(inputs, outputs, &witnesses, true);
+    let txid = txid_hex(&tx_bytes, inputs, outputs, &witnesses);
+    Ok(SignedBtcTx { tx_bytes, txid })
+}
+
+/// Witness stack for one wallet input: Taproot key path (BIP-341) or P2WPKH (BIP-143).
+async fn sign_input(
+    wallet_key: &WalletBtcKey,
+    version: u32,
+    lock_time: u32,
+    inputs: &[TxInputTemplate],
+    outputs: &[TxOutputTemplate],
+    input_index: usize,
+) -> WalletResult<Vec<Vec<u8>>> {
+    let script = &inputs[input_index].script_pubkey;
+    if *script == wallet_key.taproot_script() {
+        let sighash = taproot_key_spend_sighash(version, lock_time, inputs, outputs, input_index)?;
+        let sig = sign_taproot_keypath_sighash(&sighash, &wallet_key.key_name).await?;
+        return Ok(vec![sig]);
+    }
+    if *script == wallet_key.p2wpkh_script() {
+        let sighash = p2wpkh_sighash(
+            version,
+            lock_time,
+            inputs,
+            outputs,
             input_index,
-            &source_script,
+            &wallet_key.p2wpkh.pubkey_hash,
         )?;
-        let sig = sign_taproot_keypath_sighash(&sighash, &wallet_key.key_name).await?;
-        witnesses[input_index] = vec![sig];
+        let sig = sign_p2wpkh_sighash(&sighash, &wallet_key.p2wpkh).await?;
+        return Ok(vec![sig, wallet_key.p2wpkh.public_key.clone()]);
+    }
+    Err(WalletError::invalid_input(format!(
+        "input {input_index} does not belong to the canister-managed BTC keys"
+    )))
+}
+
+fn sent_tx_record(
+    net: BtcNet,
+    signed: &SignedBtcTx,
+    inputs: &[TxInputTemplate],
+    outputs: &[TxOutputTemplate],
+    change_script: &[u8],
+) -> BitcoinSentTxRecord {
+    let total_in: u64 = inputs.iter().map(|i| i.utxo.value).sum();
+    let total_out: u64 = outputs.iter().map(|o| o.value).sum();
+    BitcoinSentTxRecord {
+        network: Some(net.name.to_string()),
+        txid: signed.txid.clone(),
+        inputs: inputs
+            .iter()
+            .map(|i| BitcoinTxInputRecord {
+                txid: addressing::hex_encode(&i.utxo.outpoint.txid),
+                vout: i.utxo.outpoint.vout,
+                value: i.utxo.value,
+                script_pubkey_hex: Some(addressing::hex_encode(&i.script_pubkey)),
+            })
+            .collect(),
+        outputs: outputs
+            .iter()
+            .map(|o| BitcoinTxOutputRecord {
+                script_pubkey_hex: addressing::hex_encode(&o.script_pubkey),
+                value: o.value,
+                is_change: o.script_pubkey == change_script,
+            })
+            .collect(),
+        vsize: estimate_signed_tx_vbytes(inputs, outputs) as u64,
+        fee_sats: total_in.saturating_sub(total_out),
+        replaceable: inputs.iter().any(|i| i.sequence < SEQUENCE_FINAL - 1),
+        created_at_ns: ic_cdk::api::time(),
+        replaced_by: None,
     }
+}
 
-    let tx_bytes = serialize_tx(&plan.inputs, &plan.outputs, &witnesses, true);
-    let raw_tx_hex = addressing::hex_encode(&tx_bytes);
-    let txid = txid_hex(&tx_bytes, &plan.inputs, &plan.outputs, &witnesses);
-    let broadcast_url = format!("{}{}", bitcoin_rpc_base_url()?, "/tx");
+/// Broadcasts through the Bitcoin canister, or returns an esplora broadcast request for the frontend.
+async fn finish_signed_tx(
+    net: BtcNet,
+    signed: SignedBtcTx,
+    detail: &str,
+) -> WalletResult<TransferResponse> {
+    let raw_tx_hex = addressing::hex_encode(&signed.tx_bytes);
+
+    if let Some(canister_network) = net.bitcoin_canister() {
+        btc_canister::bitcoin_send_transaction(&SendTransactionRequest {
+            network: canister_network,
+            transaction: signed.tx_bytes,
+        })
+        .await
+        .map_err(|err| WalletError::Internal(format!("bitcoin_send_transaction failed: {err}")))?;
+        return Ok(TransferResponse {
+            network: net.name.to_string(),
+            accepted: true,
+            tx_id: Some(signed.txid),
+            signed_tx: Some(raw_tx_hex),
+            signed_tx_encoding: Some("hex".to_string()),
+            broadcast_request: None,
+            message: format!("BTC transaction broadcast via ICP Bitcoin canister ({detail})"),
+            resolved_to: None,
+        });
+    }
+
+    let broadcast_url = format!("{}{}", bitcoin_rpc_base_url(net)?, "/tx");
 
     Ok(TransferResponse {
-        network: NETWORK_NAME.to_string(),
+        network: net.name.to_string(),
         accepted: false,
-        tx_id: Some(txid),
+        tx_id: Some(signed.txid),
         signed_tx: Some(raw_tx_hex.clone()),
         signed_tx_encoding: Some("hex".to_string()),
         broadcast_request: Some(BroadcastHttpRequest {
@@ -199,9 +1137,9 @@ pub async fn transfer(req: TransferRequest) -> WalletResult<TransferResponse> {
             body: Some(raw_tx_hex),
         }),
         message: format!(
-            "signed BTC transaction prepared; frontend should POST raw hex to esplora /tx (fee={} sats, fee_rate={} sat/vB)",
-            plan.fee_sats, plan.fee_rate_sat_per_vb
+            "signed BTC transaction prepared; frontend should POST raw hex to esplora /tx ({detail})"
         ),
+        resolved_to: None,
     })
 }
 
@@ -214,127 +1152,321 @@ struct SpendPlan {
 }
 
 fn build_spend_plan(
-    utxos: &[Utxo],
-    amount_sats: u64,
-    to_script: &[u8],
+    utxos: &[TxInputTemplate],
+    payments: &[TxOutputTemplate],
     change_script: &[u8],
     fee_rate_sat_per_vb: u64,
+    confirmed_only: bool,
 ) -> WalletResult<SpendPlan> {
-    let mut selected: Vec<Utxo> = Vec::new();
-    let mut total_in: u64 = 0;
     let fee_rate_sat_per_vb = fee_rate_sat_per_vb.max(1);
+    let amount_sats = payments.iter().try_fold(0u64, |acc, p| {
+        acc.checked_add(p.value)
+            .ok_or_else(|| WalletError::Internal("BTC amount overflow".into()))
+    })?;
+
+    let candidates = utxos
+        .iter()
+        .filter(|u| !confirmed_only || u.utxo.height > 0)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if let Some(selected) =
+        select_changeless_bnb(&candidates, payments, change_script, fee_rate_sat_per_vb)
+    {
+        if let Some(plan) = plan_for_selection(
+            selected,
+            payments,
+            amount_sats,
+            change_script,
+            fee_rate_sat_per_vb,
+        )? {
+            return Ok(plan);
+        }
+    }
 
-    let mut sorted_utxos = utxos.to_vec();
-    sorted_utxos.sort_by_key(|u| u.value);
-
+    let mut sorted_utxos = candidates;
+    sorted_utxos.sort_by_key(|u| std::cmp::Reverse(u.utxo.value));
+    let mut selected: Vec<TxInputTemplate> = Vec::new();
     for utxo in sorted_utxos {
-        total_in = total_in
-            .checked_add(utxo.value)
-            .ok_or_else(|| WalletError::Internal("BTC input sum overflow".into()))?;
         selected.push(utxo);
+        if let Some(plan) = plan_for_selection(
+            selected.clone(),
+            payments,
+            amount_sats,
+            change_script,
+            fee_rate_sat_per_vb,
+        )? {
+            return Ok(plan);
+        }
+    }
 
-        let inputs = selected
-            .iter()
-            .cloned()
-            .map(|u| TxInputTemplate {
-                utxo: u,
-                sequence: SEQUENCE_FINAL,
-            })
-            .collect::<Vec<_>>();
+    if confirmed_only && utxos.iter().any(|u| u.utxo.height == 0) {
+        return Err(WalletError::Internal(
+            "insufficient confirmed BTC funds (including fee); unconfirmed UTXOs were excluded"
+                .into(),
+        ));
+    }
+    Err(WalletError::Internal(
+        "insufficient BTC funds (including fee)".into(),
+    ))
+}
 
-        let one_output = vec![TxOutputTemplate {
-            value: amount_sats,
-            script_pubkey: to_script.to_vec(),
-        }];
-        let fee_no_change =
-            estimate_signed_tx_vbytes(inputs.len(), &one_output) as u64 * fee_rate_sat_per_vb;
-        let needed_no_change = amount_sats
-            .checked_add(fee_no_change)
-            .ok_or_else(|| WalletError::Internal("BTC amount overflow".into()))?;
-
-        if total_in < needed_no_change {
-            continue;
-        }
-
-        let fee_with_change = estimate_signed_tx_vbytes(
-            inputs.len(),
-            &[
-                TxOutputTemplate {
-                    value: amount_sats,
-                    script_pubkey: to_script.to_vec(),
-                },
-                TxOutputTemplate {
-                    value: 0,
-                    script_pubkey: change_script.to_vec(),
-                },
-            ],
-        ) as u64
-            * fee_rate_sat_per_vb;
-
-        let needed_with_change = amount_sats
-            .checked_add(fee_with_change)
-            .ok_or_else(|| WalletError::Internal("BTC amount overflow".into()))?;
-
-        if total_in >= needed_with_change {
-            let change = total_in - needed_with_change;
-            if change >= MIN_CHANGE_SATS {
-                let outputs = vec![
-                    TxOutputTemplate {
-                        value: amount_sats,
-                        script_pubkey: to_script.to_vec(),
-                    },
-                    TxOutputTemplate {
-                        value: change,
-                        script_pubkey: change_script.to_vec(),
-                    },
-                ];
-                return Ok(SpendPlan {
-                    inputs,
-                    outputs,
-                    fee_sats: fee_with_change,
-                    fee_rate_sat_per_vb,
-                });
-            }
-        }
+/// Builds the transaction for a fixed input set, adding change only when it clears dust.
+/// Returns `None` when the inputs do not cover amount plus fee.
+fn plan_for_selection(
+    inputs: Vec<TxInputTemplate>,
+    payments: &[TxOutputTemplate],
+    amount_sats: u64,
+    change_script: &[u8],
+    fee_rate_sat_per_vb: u64,
+) -> WalletResult<Option<SpendPlan>> {
+    let total_in = inputs.iter().try_fold(0u64, |acc, i| {
+        acc.checked_add(i.utxo.value)
+            .ok_or_else(|| WalletError::Internal("BTC input sum overflow".into()))
+    })?;
+
+    let fee_no_change = estimate_signed_tx_vbytes(&inputs, payments) as u64 * fee_rate_sat_per_vb;
+    let needed_no_change = amount_sats
+        .checked_add(fee_no_change)
+        .ok_or_else(|| WalletError::Internal("BTC amount overflow".into()))?;
+    if total_in < needed_no_change {
+        return Ok(None);
+    }
 
-        let fee = fee_no_change;
-        if total_in >= amount_sats + fee {
-            let outputs = vec![TxOutputTemplate {
-                value: amount_sats,
-                script_pubkey: to_script.to_vec(),
-            }];
-            return Ok(SpendPlan {
+    let mut outputs_with_change = payments.to_vec();
+    outputs_with_change.push(TxOutputTemplate {
+        value: 0,
+        script_pubkey: change_script.to_vec(),
+    });
+    let fee_with_change =
+        estimate_signed_tx_vbytes(&inputs, &outputs_with_change) as u64 * fee_rate_sat_per_vb;
+    let needed_with_change = amount_sats
+        .checked_add(fee_with_change)
+        .ok_or_else(|| WalletError::Internal("BTC amount overflow".into()))?;
+
+    if total_in >= needed_with_change {
+        let change = total_in - needed_with_change;
+        if change >= MIN_CHANGE_SATS {
+            if let Some(change_output) = outputs_with_change.last_mut() {
+                change_output.value = change;
+            }
+            return Ok(Some(SpendPlan {
                 inputs,
-                outputs,
-                fee_sats: fee,
+                outputs: outputs_with_change,
+                fee_sats: fee_with_change,
                 fee_rate_sat_per_vb,
-            });
+            }));
         }
     }
 
-    Err(WalletError::Internal(
-        "insufficient BTC funds (including fee)".into(),
-    ))
+    Ok(Some(SpendPlan {
+        inputs,
+        outputs: payments.to_vec(),
+        fee_sats: total_in - amount_sats,
+        fee_rate_sat_per_vb,
+    }))
+}
+
+fn build_consolidation_plan(
+    utxos: &[TxInputTemplate],
+    own_script: &[u8],
+    fee_rate_sat_per_vb: u64,
+) -> WalletResult<SpendPlan> {
+    let fee_rate_sat_per_vb = fee_rate_sat_per_vb.max(1);
+    let mut inputs = utxos
+        .iter()
+        .filter(|u| {
+            u.utxo.height > 0 && u.utxo.value > input_vbytes(&u.script_pubkey) * fee_rate_sat_per_vb
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    inputs.sort_by_key(|u| u.utxo.value);
+    inputs.truncate(BTC_CONSOLIDATE_MAX_INPUTS);
+    if inputs.len() < 2 {
+        return Err(WalletError::invalid_input(
+            "nothing to consolidate: fewer than 2 confirmed UTXOs worth spending at the current fee rate",
+        ));
+    }
+
+    let total_in = inputs.iter().map(|u| u.utxo.value).sum::<u64>();
+    let mut outputs = vec![TxOutputTemplate {
+        value: 0,
+        script_pubkey: own_script.to_vec(),
+    }];
+    let fee_sats = estimate_signed_tx_vbytes(&inputs, &outputs) as u64 * fee_rate_sat_per_vb;
+    let value = total_in.saturating_sub(fee_sats);
+    if value < MIN_CHANGE_SATS {
+        return Err(WalletError::invalid_input(
+            "consolidated output would be below the dust limit",
+        ));
+    }
+    outputs[0].value = value;
+
+    Ok(SpendPlan {
+        inputs,
+        outputs,
+        fee_sats,
+        fee_rate_sat_per_vb,
+    })
+}
+
+/// Branch-and-bound search (as in Bitcoin Core) for an input set whose effective value lands
+/// within the cost of a change output above the target, so the spend needs no change.
+fn select_changeless_bnb(
+    utxos: &[TxInputTemplate],
+    payments: &[TxOutputTemplate],
+    change_script: &[u8],
+    fee_rate_sat_per_vb: u64,
+) -> Option<Vec<TxInputTemplate>> {
+    let mut pool = utxos
+        .iter()
+        .filter_map(|u| {
+            let input_fee = input_vbytes(&u.script_pubkey) * fee_rate_sat_per_vb;
+            u.utxo
+                .value
+                .checked_sub(input_fee)
+                .filter(|v| *v > 0)
+                .map(|v| (v, u))
+        })
+        .collect::<Vec<_>>();
+    pool.sort_by_key(|(v, _)| std::cmp::Reverse(*v));
+
+    let amount_sats = payments.iter().map(|p| p.value).sum::<u64>();
+    let target = amount_sats
+        .checked_add(estimate_signed_tx_vbytes(&[], payments) as u64 * fee_rate_sat_per_vb)?;
+    let change_output = TxOutputTemplate {
+        value: 0,
+        script_pubkey: change_script.to_vec(),
+    };
+    let cost_of_change = serialized_output_len(&change_output) as u64 * fee_rate_sat_per_vb
+        + input_vbytes(change_script) * fee_rate_sat_per_vb
+        + MIN_CHANGE_SATS;
+    let upper = target.checked_add(cost_of_change)?;
+
+    let mut remaining = pool.iter().map(|(v, _)| *v).sum::<u64>();
+    if remaining < target {
+        return None;
+    }
+
+    let mut best: Option<(u64, Vec<bool>)> = None;
+    let mut included = vec![false; pool.len()];
+    let mut depth = 0usize;
+    let mut current = 0u64;
+    let mut tries = 0usize;
+    loop {
+        tries += 1;
+        let backtrack = if current > upper || current + remaining < target {
+            true
+        } else if current >= target {
+            if best
+                .as_ref()
+                .is_none_or(|(waste, _)| current - target < *waste)
+            {
+                best = Some((current - target, included.clone()));
+            }
+            true
+        } else {
+            depth >= pool.len()
+        };
+        if tries >= BTC_BNB_MAX_TRIES {
+            break;
+        }
+
+        if backtrack {
+            // Walk back to the last included input and try the branch without it.
+            while depth > 0 && !included[depth - 1] {
+                depth -= 1;
+                remaining += pool[depth].0;
+            }
+            if depth == 0 {
+                break;
+            }
+            depth -= 1;
+            included[depth] = false;
+            current -= pool[depth].0;
+            depth += 1;
+        } else {
+            remaining -= pool[depth].0;
+            included[depth] = true;
+            current += pool[depth].0;
+            depth += 1;
+        }
+    }
+
+    best.map(|(_, included)| {
+        pool.iter()
+            .zip(included)
+            .filter(|(_, take)| *take)
+            .map(|((_, utxo), _)| (*utxo).clone())
+            .collect()
+    })
+}
+
+/// Weight-derived vbytes of one input spending `script_pubkey` (outpoint, empty scriptSig,
+/// sequence, plus its witness).
+fn input_vbytes(script_pubkey: &[u8]) -> u64 {
+    ((41 * 4 + input_witness_len(script_pubkey)) as u64).div_ceil(4)
 }
 
-async fn derive_wallet_key() -> WalletResult<WalletBtcKey> {
+async fn derive_wallet_key(net: BtcNet) -> WalletResult<WalletBtcKey> {
     let (public_key, key_name) =
         addressing::fetch_schnorr_public_key(SchnorrAlgorithm::Bip340secp256k1).await?;
     let internal_key = parse_bip340_internal_key(&public_key)?;
     let witness_program = taproot_output_key(&internal_key)?;
-    let address = addressing::encode_segwit_v1_bech32m(bitcoin_hrp(), &witness_program)?;
+    let address = addressing::encode_segwit_v1_bech32m(net.hrp, &witness_program)?;
+
+    let (ecdsa_public_key, ecdsa_key_name) = addressing::fetch_ecdsa_secp256k1_public_key().await?;
+    let compressed = PublicKey::from_sec1_bytes(&ecdsa_public_key)
+        .map_err(|err| WalletError::Internal(format!("invalid secp256k1 public key: {err}")))?
+        .to_encoded_point(true)
+        .as_bytes()
+        .to_vec();
+    let pubkey_hash = hash160(&compressed);
 
     Ok(WalletBtcKey {
         address,
         key_name,
         internal_key_x_only: internal_key,
         taproot_witness_program: witness_program,
+        p2wpkh: WalletP2wpkhKey {
+            address: addressing::encode_segwit_v0_bech32(net.hrp, &pubkey_hash)?,
+            key_name: ecdsa_key_name,
+            public_key: compressed,
+            pubkey_hash,
+        },
     })
 }
 
-async fn fetch_all_utxos(address: &str) -> WalletResult<Vec<Utxo>> {
+/// UTXOs of both wallet addresses, ready to spend with RBF signalling.
+async fn fetch_wallet_utxos(
+    net: BtcNet,
+    wallet_key: &WalletBtcKey,
+) -> WalletResult<Vec<TxInputTemplate>> {
+    let mut out = Vec::new();
+    for (address, script) in [
+        (&wallet_key.address, wallet_key.taproot_script()),
+        (&wallet_key.p2wpkh.address, wallet_key.p2wpkh_script()),
+    ] {
+        out.extend(
+            fetch_all_utxos(net, address)
+                .await?
+                .into_iter()
+                .map(|utxo| TxInputTemplate {
+                    utxo,
+                    sequence: SEQUENCE_RBF,
+                    script_pubkey: script.clone(),
+                }),
+        );
+    }
+    Ok(out)
+}
+
+async fn fetch_all_utxos(net: BtcNet, address: &str) -> WalletResult<Vec<Utxo>> {
+    if let Some(canister_network) = net.bitcoin_canister() {
+        return fetch_all_utxos_from_canister(canister_network, address).await;
+    }
     let rows: Vec<MempoolUtxoResponse> =
-        btc_rpc_get_json(&format!("/address/{address}/utxo")).await?;
+        btc_rpc_get_json(net, &format!("/address/{address}/utxo")).await?;
     let mut out = Vec::with_capacity(rows.len());
     for row in rows {
         let txid_bytes = parse_txid_hex_to_bytes(&row.txid)?;
@@ -354,8 +1486,50 @@ async fn fetch_all_utxos(address: &str) -> WalletResult<Vec<Utxo>> {
     Ok(out)
 }
 
-async fn fetch_fee_rate_sat_per_vb() -> WalletResult<u64> {
-    let fees: Value = btc_rpc_get_json("/fee-estimates").await?;
+async fn fetch_all_utxos_from_canister(
+    network: BitcoinNetwork,
+    address: &str,
+) -> WalletResult<Vec<Utxo>> {
+    let mut out = Vec::new();
+    let mut filter = None;
+    loop {
+        let page = btc_canister::bitcoin_get_utxos(&GetUtxosRequest {
+            network,
+            address: address.to_string(),
+            filter,
+        })
+        .await
+        .map_err(|err| WalletError::Internal(format!("bitcoin_get_utxos failed: {err}")))?;
+        // The Bitcoin canister returns txids in internal byte order; keep `Utxo` in display order
+        // like the esplora path so `serialize_outpoint_into` stays source-agnostic.
+        out.extend(page.utxos.into_iter().map(|mut utxo| {
+            utxo.outpoint.txid.reverse();
+            utxo
+        }));
+        match page.next_page {
+            Some(next) => filter = Some(UtxosFilter::Page(next)),
+            None => return Ok(out),
+        }
+    }
+}
+
+async fn fetch_fee_rate_sat_per_vb(net: BtcNet) -> WalletResult<u64> {
+    if let Some(canister_network) = net.bitcoin_canister() {
+        // Percentiles are millisatoshi/vbyte; regtest usually has none, so fall back to the default.
+        let percentiles =
+            btc_canister::bitcoin_get_current_fee_percentiles(&GetCurrentFeePercentilesRequest {
+                network: canister_network,
+            })
+            .await
+            .map_err(|err| {
+                WalletError::Internal(format!("bitcoin_get_current_fee_percentiles failed: {err}"))
+            })?;
+        return Ok(percentiles
+            .get(50)
+            .map(|msat| msat.div_ceil(1000).max(1))
+            .unwrap_or(DEFAULT_FEE_RATE_SAT_PER_VB));
+    }
+    let fees: Value = btc_rpc_get_json(net, "/fee-estimates").await?;
     let candidates = ["3", "2", "6", "1"];
     for key in candidates {
         if let Some(v) = fees.get(key).and_then(Value::as_f64) {
@@ -393,29 +1567,46 @@ async fn sign_taproot_keypath_sighash(
     Ok(result.signature)
 }
 
-fn expected_hrp() -> String {
-    bitcoin_hrp().to_string()
+/// Signs a BIP-143 sighash with the threshold ECDSA key; returns DER with SIGHASH_ALL appended.
+async fn sign_p2wpkh_sighash(sighash32: &[u8; 32], key: &WalletP2wpkhKey) -> WalletResult<Vec<u8>> {
+    let result = management_canister::sign_with_ecdsa(&SignWithEcdsaArgs {
+        message_hash: sighash32.to_vec(),
+        derivation_path: vec![],
+        key_id: EcdsaKeyId {
+            curve: EcdsaCurve::Secp256k1,
+            name: key.key_name.clone(),
+        },
+    })
+    .await
+    .map_err(|err| WalletError::Internal(format!("sign_with_ecdsa failed: {err}")))?;
+    p2wpkh_signature_from_compact(&result.signature)
 }
 
-fn bitcoin_hrp() -> &'static str {
-    "bc"
+fn p2wpkh_signature_from_compact(signature: &[u8]) -> WalletResult<Vec<u8>> {
+    let signature = EcdsaSignature::try_from(signature)
+        .map_err(|err| WalletError::Internal(format!("invalid secp256k1 signature: {err}")))?;
+    // Standardness (BIP-146) requires low-S signatures.
+    let signature = signature.normalize_s().unwrap_or(signature);
+    let mut der = signature.to_der().as_bytes().to_vec();
+    der.push(SIGHASH_ALL);
+    Ok(der)
 }
 
-async fn fetch_address_stats(address: &str) -> WalletResult<MempoolAddressResponse> {
-    btc_rpc_get_json(&format!("/address/{address}")).await
+async fn fetch_address_stats(net: BtcNet, address: &str) -> WalletResult<MempoolAddressResponse> {
+    btc_rpc_get_json(net, &format!("/address/{address}")).await
 }
 
-fn bitcoin_rpc_base_url() -> WalletResult<String> {
-    config::rpc_config::resolve_rpc_url(NETWORK_NAME, None)
+fn bitcoin_rpc_base_url(net: BtcNet) -> WalletResult<String> {
+    config::rpc_config::resolve_rpc_url(net.name, None)
         .map_err(|err| WalletError::Internal(format!("bitcoin rpc url resolve failed: {err}")))
 }
 
-async fn btc_rpc_get_json<T>(path: &str) -> WalletResult<T>
+async fn btc_rpc_get_json<T>(net: BtcNet, path: &str) -> WalletResult<T>
 where
     T: for<'de> Deserialize<'de>,
 {
     let http_res = crate::outcall::get_json(
-        format!("{}{}", bitcoin_rpc_base_url()?, path),
+        format!("{}{}", bitcoin_rpc_base_url(net)?, path),
         512 * 1024,
         "btc rpc",
     )
@@ -442,8 +1633,15 @@ fn parse_txid_hex_to_bytes(txid_hex: &str) -> WalletResult<Vec<u8>> {
             hex.len()
         )));
     }
-    let mut out = Vec::with_capacity(32);
-    let bytes = hex.as_bytes();
+    decode_hex(hex)
+}
+
+fn decode_hex(hex: &str) -> WalletResult<Vec<u8>> {
+    let bytes = hex.trim().as_bytes();
+    if !bytes.len().is_multiple_of(2) {
+        return Err(WalletError::Internal("hex string has odd length".into()));
+    }
+    let mut out = Vec::with_capacity(bytes.len() / 2);
     for i in (0..bytes.len()).step_by(2) {
         let hi = decode_hex_nibble(bytes[i])?;
         let lo = decode_hex_nibble(bytes[i + 1])?;
@@ -636,7 +1834,6 @@ fn taproot_key_spend_sighash(
     inputs: &[TxInputTemplate],
     outputs: &[TxOutputTemplate],
     input_index: usize,
-    source_script_pubkey: &[u8],
 ) -> WalletResult<[u8; 32]> {
     if input_index >= inputs.len() {
         return Err(WalletError::Internal(
@@ -651,8 +1848,8 @@ fn taproot_key_spend_sighash(
     for input in inputs {
         serialize_outpoint_into(&input.utxo, &mut prevouts_ser);
         amounts_ser.extend_from_slice(&input.utxo.value.to_le_bytes());
-        write_compact_size_into(source_script_pubkey.len() as u64, &mut scriptpubkeys_ser);
-        scriptpubkeys_ser.extend_from_slice(source_script_pubkey);
+        write_compact_size_into(input.script_pubkey.len() as u64, &mut scriptpubkeys_ser);
+        scriptpubkeys_ser.extend_from_slice(&input.script_pubkey);
         sequences_ser.extend_from_slice(&input.sequence.to_le_bytes());
     }
     let mut outputs_ser = Vec::new();
@@ -682,13 +1879,70 @@ fn taproot_key_spend_sighash(
     Ok(tagged_hash_sha256(b"TapSighash", &msg))
 }
 
-fn estimate_signed_tx_vbytes(input_count: usize, outputs: &[TxOutputTemplate]) -> usize {
-    let non_witness_len = serialized_tx_len_no_witness(input_count, outputs);
-    let witness_len = 2 + input_count * (1 + 1 + 64); // marker+flag + per-input [stack_count=1][push=64][sig]
+/// BIP-143 SIGHASH_ALL digest for a P2WPKH input.
+fn p2wpkh_sighash(
+    version: u32,
+    lock_time: u32,
+    inputs: &[TxInputTemplate],
+    outputs: &[TxOutputTemplate],
+    input_index: usize,
+    pubkey_hash: &[u8; 20],
+) -> WalletResult<[u8; 32]> {
+    let input = inputs
+        .get(input_index)
+        .ok_or_else(|| WalletError::Internal("p2wpkh sighash input index out of range".into()))?;
+
+    let mut prevouts_ser = Vec::new();
+    let mut sequences_ser = Vec::new();
+    for input in inputs {
+        serialize_outpoint_into(&input.utxo, &mut prevouts_ser);
+        sequences_ser.extend_from_slice(&input.sequence.to_le_bytes());
+    }
+    let mut outputs_ser = Vec::new();
+    for output in outputs {
+        serialize_output_into(output, &mut outputs_ser);
+    }
+
+    let mut preimage = Vec::with_capacity(4 + 32 * 2 + 36 + 26 + 8 + 4 + 32 + 4 + 4);
+    preimage.extend_from_slice(&version.to_le_bytes());
+    preimage.extend_from_slice(&double_sha256(&prevouts_ser));
+    preimage.extend_from_slice(&double_sha256(&sequences_ser));
+    serialize_outpoint_into(&input.utxo, &mut preimage);
+    // scriptCode: the P2PKH script for the key hash.
+    preimage.extend_from_slice(&[0x19, 0x76, 0xa9, 0x14]);
+    preimage.extend_from_slice(pubkey_hash);
+    preimage.extend_from_slice(&[0x88, 0xac]);
+    preimage.extend_from_slice(&input.utxo.value.to_le_bytes());
+    preimage.extend_from_slice(&input.sequence.to_le_bytes());
+    preimage.extend_from_slice(&double_sha256(&outputs_ser));
+    preimage.extend_from_slice(&lock_time.to_le_bytes());
+    preimage.extend_from_slice(&u32::from(SIGHASH_ALL).to_le_bytes());
+
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&double_sha256(&preimage));
+    Ok(out)
+}
+
+fn estimate_signed_tx_vbytes(inputs: &[TxInputTemplate], outputs: &[TxOutputTemplate]) -> usize {
+    let non_witness_len = serialized_tx_len_no_witness(inputs.len(), outputs);
+    let witness_len = 2 + inputs // marker+flag
+        .iter()
+        .map(|i| input_witness_len(&i.script_pubkey))
+        .sum::<usize>();
     let weight = non_witness_len * 4 + witness_len;
     weight.div_ceil(4)
 }
 
+fn input_witness_len(script_pubkey: &[u8]) -> usize {
+    if is_p2wpkh_script(script_pubkey) {
+        // [stack_count=2][push][DER sig (max 72 incl. sighash byte)][push=33][compressed pubkey]
+        1 + 1 + 72 + 1 + 33
+    } else {
+        // [stack_count=1][push=64][schnorr sig]
+        1 + 1 + 64
+    }
+}
+
 fn serialized_tx_len_no_witness(input_count: usize, outputs: &[TxOutputTemplate]) -> usize {
     4 + compact_size_len(input_count as u64)
         + input_count * 41
@@ -761,6 +2015,18 @@ fn serialize_output_into(output: &TxOutputTemplate, out: &mut Vec<u8>) {
     out.extend_from_slice(&output.script_pubkey);
 }
 
+fn script_pubkey_p2wpkh(pubkey_hash: &[u8; 20]) -> Vec<u8> {
+    let mut script = Vec::with_capacity(22);
+    script.push(0x00); // OP_0
+    script.push(0x14); // push 20
+    script.extend_from_slice(pubkey_hash);
+    script
+}
+
+fn is_p2wpkh_script(script: &[u8]) -> bool {
+    script.len() == 22 && script[0] == 0x00 && script[1] == 0x14
+}
+
 fn script_pubkey_p2tr(witness_program: &[u8; 32]) -> Vec<u8> {
     let mut script = Vec::with_capacity(34);
     script.push(0x51); // OP_1
@@ -769,8 +2035,33 @@ fn script_pubkey_p2tr(witness_program: &[u8; 32]) -> Vec<u8> {
     script
 }
 
-fn script_pubkey_from_btc_address(address: &str, expected_hrp: &str) -> WalletResult<Vec<u8>> {
-    let decoded = decode_segwit_address(address, expected_hrp)?;
+/// Provably unspendable data carrier output; 80 bytes is the standard relay limit.
+fn script_pubkey_op_return(data: &[u8]) -> WalletResult<Vec<u8>> {
+    if data.len() > BTC_OP_RETURN_MAX_BYTES {
+        return Err(WalletError::invalid_input(format!(
+            "memo must be at most {BTC_OP_RETURN_MAX_BYTES} bytes, got {}",
+            data.len()
+        )));
+    }
+    let mut script = Vec::with_capacity(3 + data.len());
+    script.push(0x6a); // OP_RETURN
+    if data.len() > 75 {
+        script.push(0x4c); // OP_PUSHDATA1
+    }
+    script.push(data.len() as u8);
+    script.extend_from_slice(data);
+    Ok(script)
+}
+
+fn script_pubkey_from_btc_address(address: &str, net: BtcNet) -> WalletResult<Vec<u8>> {
+    if !address
+        .trim()
+        .to_lowercase()
+        .starts_with(&format!("{}1", net.hrp))
+    {
+        return script_pubkey_from_base58_address(address, net);
+    }
+    let decoded = decode_segwit_address(address, net.hrp)?;
     let mut script = Vec::with_capacity(2 + decoded.program.len());
     let op = match decoded.version {
         0 => 0x00,
@@ -786,6 +2077,74 @@ fn script_pubkey_from_btc_address(address: &str, expected_hrp: &str) -> WalletRe
     Ok(script)
 }
 
+/// Legacy P2PKH (`1...` / `m,n...`) and P2SH (`3...` / `2...`) Base58Check addresses.
+fn script_pubkey_from_base58_address(address: &str, net: BtcNet) -> WalletResult<Vec<u8>> {
+    let raw = base58_decode(address.trim())?;
+    if raw.len() != 25 {
+        return Err(WalletError::invalid_input(
+            "BTC base58check address length invalid",
+        ));
+    }
+    let (payload, checksum) = raw.split_at(21);
+    if checksum != &double_sha256(payload)[..4] {
+        return Err(WalletError::invalid_input("BTC address checksum mismatch"));
+    }
+    let hash160 = &payload[1..];
+    let mut script = Vec::with_capacity(25);
+    if payload[0] == net.p2pkh_version {
+        // OP_DUP OP_HASH160 <20> OP_EQUALVERIFY OP_CHECKSIG
+        script.extend_from_slice(&[0x76, 0xa9, 0x14]);
+        script.extend_from_slice(hash160);
+        script.extend_from_slice(&[0x88, 0xac]);
+    } else if payload[0] == net.p2sh_version {
+        // OP_HASH160 <20> OP_EQUAL
+        script.extend_from_slice(&[0xa9, 0x14]);
+        script.extend_from_slice(hash160);
+        script.push(0x87);
+    } else {
+        return Err(WalletError::invalid_input(format!(
+            "BTC address version byte 0x{:02x} is not valid for this network",
+            payload[0]
+        )));
+    }
+    Ok(script)
+}
+
+fn base58_decode(input: &str) -> WalletResult<Vec<u8>> {
+    const BASE58_ALPHABET: &[u8; 58] =
+        b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    if input.is_empty() {
+        return Err(WalletError::invalid_input("BTC address is required"));
+    }
+
+    let zeros = input
+        .bytes()
+        .take_while(|ch| *ch == BASE58_ALPHABET[0])
+        .count();
+    // Little-endian base-256 accumulator.
+    let mut acc: Vec<u8> = Vec::new();
+    for ch in input.bytes() {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|c| *c == ch)
+            .ok_or_else(|| WalletError::invalid_input("invalid base58 character in BTC address"))?
+            as u32;
+        for byte in acc.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            acc.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut out = vec![0u8; zeros];
+    out.extend(acc.iter().rev());
+    Ok(out)
+}
+
 #[derive(Clone, Debug)]
 struct DecodedSegwitAddress {
     version: u8,
@@ -961,6 +2320,13 @@ fn sha256_once(data: &[u8]) -> [u8; 32] {
     out
 }
 
+fn hash160(data: &[u8]) -> [u8; 20] {
+    let digest = Ripemd160::digest(sha256_once(data));
+    let mut out = [0u8; 20];
+    out.copy_from_slice(&digest);
+    out
+}
+
 fn double_sha256(data: &[u8]) -> Vec<u8> {
     let first = Sha256::digest(data);
     let second = Sha256::digest(first);
@@ -971,6 +2337,40 @@ fn double_sha256(data: &[u8]) -> Vec<u8> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn data_source_selects_bitcoin_canister_per_network() {
+        let canister = BitcoinDataSource::BitcoinCanister;
+        let esplora = BitcoinDataSource::Esplora;
+        for (network, expected) in [
+            (types::networks::BITCOIN, Some(BitcoinNetwork::Mainnet)),
+            (
+                types::networks::BITCOIN_TESTNET,
+                Some(BitcoinNetwork::Testnet),
+            ),
+            (
+                types::networks::BITCOIN_REGTEST,
+                Some(BitcoinNetwork::Regtest),
+            ),
+            // No Bitcoin canister serves signet; it stays on esplora.
+            (types::networks::BITCOIN_SIGNET, None),
+        ] {
+            let net = btc_net(network).unwrap();
+            assert_eq!(net.bitcoin_canister_for(canister), expected, "{network}");
+            assert_eq!(net.bitcoin_canister_for(esplora), None, "{network}");
+        }
+    }
+
+    #[test]
+    fn data_source_is_runtime_state() {
+        assert_eq!(state::bitcoin_data_source(), BitcoinDataSource::Esplora);
+        let prev = state::set_bitcoin_data_source(BitcoinDataSource::BitcoinCanister);
+        assert_eq!(prev, BitcoinDataSource::Esplora);
+        let net = btc_net(types::networks::BITCOIN).unwrap();
+        assert_eq!(net.bitcoin_canister(), Some(BitcoinNetwork::Mainnet));
+        state::set_bitcoin_data_source(BitcoinDataSource::Esplora);
+        assert_eq!(net.bitcoin_canister(), None);
+    }
+
     #[test]
     fn formats_and_parses_btc_units() {
         assert_eq!(format_sats_btc(3_890_000_000), "38.9");
@@ -982,10 +2382,396 @@ mod tests {
     fn builds_segwit_scripts() {
         let witness = [7u8; 32];
         let addr = addressing::encode_segwit_v1_bech32m("bc", &witness).unwrap();
-        let script = script_pubkey_from_btc_address(&addr, "bc").unwrap();
+        let script = script_pubkey_from_btc_address(&addr, btc_net("bitcoin").unwrap()).unwrap();
         assert_eq!(script.len(), 34);
         assert_eq!(script[0], 0x51);
         assert_eq!(script[1], 0x20);
         assert_eq!(&script[2..], &witness);
     }
+
+    #[test]
+    fn builds_legacy_base58_scripts() {
+        let mainnet = btc_net("bitcoin").unwrap();
+        let script =
+            script_pubkey_from_btc_address("1111111111111111111114oLvT2", mainnet).unwrap();
+        let mut expected = vec![0x76, 0xa9, 0x14];
+        expected.extend_from_slice(&[0u8; 20]);
+        expected.extend_from_slice(&[0x88, 0xac]);
+        assert_eq!(script, expected);
+
+        let mut payload = vec![0x05];
+        payload.extend_from_slice(&[9u8; 20]);
+        let checksum = double_sha256(&payload);
+        payload.extend_from_slice(&checksum[..4]);
+        let p2sh = addressing::base58_encode(&payload);
+        assert!(p2sh.starts_with('3'));
+        let script = script_pubkey_from_btc_address(&p2sh, mainnet).unwrap();
+        assert_eq!(script[..2], [0xa9, 0x14]);
+        assert_eq!(script[2..22], [9u8; 20]);
+        assert_eq!(script[22], 0x87);
+
+        assert!(
+            script_pubkey_from_btc_address(&p2sh, btc_net("bitcoin_testnet").unwrap()).is_err()
+        );
+        assert!(script_pubkey_from_btc_address(&p2sh.to_lowercase(), mainnet).is_err());
+    }
+
+    #[test]
+    fn memo_becomes_op_return_output() {
+        let script = script_pubkey_op_return(b"invoice-42").unwrap();
+        assert_eq!(script[..2], [0x6a, 10]);
+        assert_eq!(&script[2..], b"invoice-42");
+
+        let long = script_pubkey_op_return(&[b'a'; 80]).unwrap();
+        assert_eq!(long[..3], [0x6a, 0x4c, 80]);
+        assert_eq!(long.len(), 83);
+        assert!(script_pubkey_op_return(&[b'a'; 81]).is_err());
+
+        let utxos = vec![test_utxo(1, 100_000, 1)];
+        let pay = TxOutputTemplate {
+            value: 40_000,
+            script_pubkey: script_pubkey_p2tr(&[2u8; 32]),
+        };
+        let memo = TxOutputTemplate {
+            value: 0,
+            script_pubkey: script,
+        };
+        let change_script = script_pubkey_p2tr(&[3u8; 32]);
+        let plain =
+            build_spend_plan(&utxos, std::slice::from_ref(&pay), &change_script, 2, false).unwrap();
+        let tagged =
+            build_spend_plan(&utxos, &[pay, memo.clone()], &change_script, 2, false).unwrap();
+        assert_eq!(tagged.outputs.len(), 3);
+        assert_eq!(tagged.outputs[1].script_pubkey, memo.script_pubkey);
+        assert_eq!(
+            tagged.fee_sats - plain.fee_sats,
+            serialized_output_len(&memo) as u64 * 2
+        );
+
+        let plain_sighash =
+            taproot_key_spend_sighash(2, 0, &plain.inputs, &plain.outputs, 0).unwrap();
+        let tagged_sighash =
+            taproot_key_spend_sighash(2, 0, &tagged.inputs, &tagged.outputs, 0).unwrap();
+        assert_ne!(plain_sighash, tagged_sighash);
+    }
+
+    #[test]
+    fn psbt_roundtrip_selects_owned_inputs_for_signing() {
+        let wallet_key = test_wallet_key();
+        let utxos = vec![
+            test_utxo(1, 30_000, 1),
+            test_p2wpkh_utxo(&wallet_key, 2, 50_000),
+        ];
+        let payments = vec![TxOutputTemplate {
+            value: 60_000,
+            script_pubkey: script_pubkey_p2tr(&[2u8; 32]),
+        }];
+        let own_script = wallet_key.taproot_script();
+        let plan = build_spend_plan(&utxos, &payments, &own_script, 2, false).unwrap();
+        let psbt = build_psbt(&plan, &wallet_key);
+        let parsed = Psbt::from_base64(&psbt.to_base64()).unwrap();
+        assert_eq!(
+            btc_psbt::map_get(&parsed.outputs[1], btc_psbt::PSBT_OUT_TAP_INTERNAL_KEY),
+            Some(wallet_key.internal_key_x_only.as_slice())
+        );
+
+        let (tx, owned) = psbt_signing_inputs(&parsed, &wallet_key).unwrap();
+        assert_eq!(owned, vec![0, 1]);
+        assert_eq!(tx.inputs[0].script_pubkey, plan.inputs[0].script_pubkey);
+        assert_eq!(tx.inputs[1].script_pubkey, plan.inputs[1].script_pubkey);
+        let expected =
+            taproot_key_spend_sighash(TX_VERSION, TX_LOCKTIME, &plan.inputs, &plan.outputs, 0)
+                .unwrap();
+        let actual =
+            taproot_key_spend_sighash(tx.version, tx.lock_time, &tx.inputs, &tx.outputs, 0)
+                .unwrap();
+        assert_eq!(actual, expected);
+
+        let mut other_key = test_wallet_key();
+        other_key.internal_key_x_only = [4u8; 32];
+        assert!(psbt_signing_inputs(&parsed, &other_key).is_err());
+        let mut foreign = test_wallet_key();
+        foreign.taproot_witness_program = [7u8; 32];
+        foreign.p2wpkh.pubkey_hash = [7u8; 20];
+        assert!(psbt_signing_inputs(&parsed, &foreign).is_err());
+    }
+
+    #[test]
+    fn spend_plan_signals_rbf_and_keeps_change() {
+        let utxos = vec![test_utxo(1, 100_000, 1)];
+        let to_script = script_pubkey_p2tr(&[2u8; 32]);
+        let change_script = script_pubkey_p2tr(&[3u8; 32]);
+        let payments = vec![TxOutputTemplate {
+            value: 40_000,
+            script_pubkey: to_script,
+        }];
+        let plan = build_spend_plan(&utxos, &payments, &change_script, 2, false).unwrap();
+        assert!(plan.inputs.iter().all(|i| i.sequence == SEQUENCE_RBF));
+        assert_eq!(plan.outputs.len(), 2);
+        assert_eq!(
+            plan.outputs[1].value,
+            100_000 - 40_000 - plan.fee_sats,
+            "change absorbs everything but amount and fee"
+        );
+    }
+
+    fn test_wallet_key() -> WalletBtcKey {
+        // Compressed secp256k1 generator point; its hash160 is the BIP-173 P2WPKH test vector.
+        let public_key =
+            decode_hex("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        let pubkey_hash = hash160(&public_key);
+        WalletBtcKey {
+            address: String::new(),
+            key_name: "test_key".to_string(),
+            internal_key_x_only: [5u8; 32],
+            taproot_witness_program: [6u8; 32],
+            p2wpkh: WalletP2wpkhKey {
+                address: addressing::encode_segwit_v0_bech32("bc", &pubkey_hash).unwrap(),
+                key_name: "test_key".to_string(),
+                public_key,
+                pubkey_hash,
+            },
+        }
+    }
+
+    fn test_utxo(seed: u8, value: u64, height: u32) -> TxInputTemplate {
+        TxInputTemplate {
+            utxo: Utxo {
+                outpoint: Outpoint {
+                    txid: vec![seed; 32],
+                    vout: 0,
+                },
+                value,
+                height,
+            },
+            sequence: SEQUENCE_RBF,
+            script_pubkey: script_pubkey_p2tr(&[6u8; 32]),
+        }
+    }
+
+    fn test_p2wpkh_utxo(wallet_key: &WalletBtcKey, seed: u8, value: u64) -> TxInputTemplate {
+        TxInputTemplate {
+            script_pubkey: wallet_key.p2wpkh_script(),
+            ..test_utxo(seed, value, 1)
+        }
+    }
+
+    #[test]
+    fn derives_p2wpkh_address_and_scripts() {
+        let wallet_key = test_wallet_key();
+        assert_eq!(
+            wallet_key.p2wpkh.address,
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        let script =
+            script_pubkey_from_btc_address(&wallet_key.p2wpkh.address, btc_net("bitcoin").unwrap())
+                .unwrap();
+        assert_eq!(script, wallet_key.p2wpkh_script());
+        assert!(wallet_key.owns_script(&script));
+
+        let taproot_only = [test_utxo(1, 10_000, 1)];
+        let mixed = [
+            test_utxo(1, 10_000, 1),
+            test_p2wpkh_utxo(&wallet_key, 2, 10_000),
+        ];
+        let outputs = [TxOutputTemplate {
+            value: 1_000,
+            script_pubkey: script_pubkey_p2tr(&[2u8; 32]),
+        }];
+        assert_eq!(
+            estimate_signed_tx_vbytes(&mixed, &outputs)
+                - estimate_signed_tx_vbytes(&taproot_only, &outputs),
+            input_vbytes(&wallet_key.p2wpkh_script()) as usize
+        );
+        assert_eq!(input_vbytes(&wallet_key.p2wpkh_script()), 68);
+        assert_eq!(input_vbytes(&wallet_key.taproot_script()), 58);
+    }
+
+    #[test]
+    fn computes_bip143_p2wpkh_sighash() {
+        // Native P2WPKH example from BIP-143.
+        let input = |txid_le: &str, vout: u32, value: u64, sequence: u32| {
+            let mut txid = decode_hex(txid_le).unwrap();
+            txid.reverse();
+            TxInputTemplate {
+                utxo: Utxo {
+                    outpoint: Outpoint { txid, vout },
+                    value,
+                    height: 0,
+                },
+                sequence,
+                script_pubkey: Vec::new(),
+            }
+        };
+        let inputs = [
+            input(
+                "fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f",
+                0,
+                625_000_000,
+                0xffff_ffee,
+            ),
+            input(
+                "ef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a",
+                1,
+                600_000_000,
+                0xffff_ffff,
+            ),
+        ];
+        let outputs = [
+            TxOutputTemplate {
+                value: 112_340_000,
+                script_pubkey: decode_hex("76a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac")
+                    .unwrap(),
+            },
+            TxOutputTemplate {
+                value: 223_450_000,
+                script_pubkey: decode_hex("76a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac")
+                    .unwrap(),
+            },
+        ];
+        let mut pubkey_hash = [0u8; 20];
+        pubkey_hash
+            .copy_from_slice(&decode_hex("1d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap());
+        let sighash = p2wpkh_sighash(1, 0x11, &inputs, &outputs, 1, &pubkey_hash).unwrap();
+        assert_eq!(
+            addressing::hex_encode(&sighash),
+            "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670"
+        );
+    }
+
+    #[test]
+    fn p2wpkh_signatures_are_low_s_der() {
+        // r = 1, s = n - 1 (high-S) normalizes to s = 1.
+        let mut compact = [0u8; 64];
+        compact[31] = 1;
+        compact[32..].copy_from_slice(
+            &decode_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140")
+                .unwrap(),
+        );
+        let sig = p2wpkh_signature_from_compact(&compact).unwrap();
+        assert_eq!(
+            sig,
+            vec![0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, SIGHASH_ALL]
+        );
+    }
+
+    #[test]
+    fn spend_plan_prefers_changeless_subset() {
+        let utxos = vec![
+            test_utxo(1, 10_000, 1),
+            test_utxo(2, 25_000, 1),
+            test_utxo(3, 60_000, 1),
+            test_utxo(4, 5_000, 1),
+        ];
+        let payments = vec![TxOutputTemplate {
+            value: 34_500,
+            script_pubkey: script_pubkey_p2tr(&[2u8; 32]),
+        }];
+        let change_script = script_pubkey_p2tr(&[3u8; 32]);
+        let plan = build_spend_plan(&utxos, &payments, &change_script, 2, false).unwrap();
+        assert_eq!(plan.outputs.len(), 1, "no change output");
+        let mut values = plan.inputs.iter().map(|i| i.utxo.value).collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, vec![10_000, 25_000]);
+        assert!(plan.fee_sats >= estimate_signed_tx_vbytes(&plan.inputs, &payments) as u64 * 2);
+    }
+
+    #[test]
+    fn spend_plan_falls_back_to_largest_first_and_skips_unconfirmed() {
+        let utxos = vec![
+            test_utxo(1, 1_000, 1),
+            test_utxo(2, 2_000, 1),
+            test_utxo(3, 90_000, 0),
+            test_utxo(4, 70_000, 1),
+        ];
+        let payments = vec![TxOutputTemplate {
+            value: 40_000,
+            script_pubkey: script_pubkey_p2tr(&[2u8; 32]),
+        }];
+        let change_script = script_pubkey_p2tr(&[3u8; 32]);
+        let plan = build_spend_plan(&utxos, &payments, &change_script, 2, false).unwrap();
+        assert_eq!(plan.inputs.len(), 1);
+        assert_eq!(plan.inputs[0].utxo.value, 90_000);
+
+        let plan = build_spend_plan(&utxos, &payments, &change_script, 2, true).unwrap();
+        assert_eq!(plan.inputs.len(), 1);
+        assert_eq!(plan.inputs[0].utxo.value, 70_000);
+
+        let large = vec![TxOutputTemplate {
+            value: 80_000,
+            script_pubkey: script_pubkey_p2tr(&[2u8; 32]),
+        }];
+        assert!(build_spend_plan(&utxos, &large, &change_script, 2, true).is_err());
+    }
+
+    #[test]
+    fn consolidation_merges_confirmed_utxos_worth_spending() {
+        let utxos = vec![
+            test_utxo(1, 50, 1),
+            test_utxo(2, 3_000, 1),
+            test_utxo(3, 4_000, 0),
+            test_utxo(4, 5_000, 1),
+        ];
+        let own_script = script_pubkey_p2tr(&[3u8; 32]);
+        let plan = build_consolidation_plan(&utxos, &own_script, 1).unwrap();
+        assert_eq!(plan.inputs.len(), 2);
+        assert_eq!(plan.outputs.len(), 1);
+        assert_eq!(plan.outputs[0].script_pubkey, own_script);
+        assert_eq!(plan.outputs[0].value + plan.fee_sats, 8_000);
+
+        assert!(build_consolidation_plan(&utxos[..2], &own_script, 1).is_err());
+    }
+
+    #[test]
+    fn spend_plan_pays_multiple_recipients_with_one_change() {
+        let utxos = vec![test_utxo(1, 30_000, 1), test_utxo(2, 50_000, 1)];
+        let payments = (0u8..3)
+            .map(|i| TxOutputTemplate {
+                value: 20_000,
+                script_pubkey: script_pubkey_p2tr(&[i + 10; 32]),
+            })
+            .collect::<Vec<_>>();
+        let change_script = script_pubkey_p2tr(&[3u8; 32]);
+        let plan = build_spend_plan(&utxos, &payments, &change_script, 3, false).unwrap();
+        assert_eq!(plan.inputs.len(), 2);
+        assert_eq!(plan.outputs.len(), 4);
+        assert_eq!(plan.outputs[3].script_pubkey, change_script);
+        assert_eq!(
+            plan.fee_sats,
+            estimate_signed_tx_vbytes(&plan.inputs, &plan.outputs) as u64 * 3
+        );
+        let total_out: u64 = plan.outputs.iter().map(|o| o.value).sum();
+        assert_eq!(total_out + plan.fee_sats, 80_000);
+    }
+
+    #[test]
+    fn canister_utxo_set_detects_confirmed_parents() {
+        let hex_of = |seed: u8| addressing::hex_encode(&[seed; 32]);
+        let record = BitcoinSentTxRecord {
+            network: Some(types::networks::BITCOIN.to_string()),
+            txid: hex_of(9),
+            inputs: vec![BitcoinTxInputRecord {
+                txid: hex_of(1),
+                vout: 0,
+                value: 30_000,
+                script_pubkey_hex: None,
+            }],
+            outputs: Vec::new(),
+            vsize: 150,
+            fee_sats: 300,
+            replaceable: true,
+            created_at_ns: 0,
+            replaced_by: None,
+        };
+        let unconfirmed = vec![test_utxo(1, 30_000, 1).utxo, test_utxo(2, 50_000, 1).utxo];
+        assert!(ensure_unconfirmed_in_utxo_set(&record, &unconfirmed).is_ok());
+
+        // Mined: its input left the UTXO set and its output entered it.
+        let mined = vec![test_utxo(9, 29_700, 5).utxo, test_utxo(2, 50_000, 1).utxo];
+        assert!(ensure_unconfirmed_in_utxo_set(&record, &mined).is_err());
+        // Input spent elsewhere (conflict), nothing of ours confirmed.
+        let conflicted = vec![test_utxo(2, 50_000, 1).utxo];
+        assert!(ensure_unconfirmed_in_utxo_set(&record, &conflicted).is_err());
+    }
 }
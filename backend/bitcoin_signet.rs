@@ -0,0 +1,40 @@
+use crate::chains::bitcoin;
+use crate::error::WalletResult;
+use crate::types::{self, AddressResponse, BitcoinPsbtResponse, TransferRequest, TransferResponse};
+
+const NETWORK_NAME: &str = types::networks::BITCOIN_SIGNET;
+
+pub async fn request_address() -> WalletResult<AddressResponse> {
+    bitcoin::request_address_for_network(NETWORK_NAME).await
+}
+
+pub async fn request_p2wpkh_address() -> WalletResult<AddressResponse> {
+    bitcoin::request_p2wpkh_address_for_network(NETWORK_NAME).await
+}
+
+pub async fn transfer(req: TransferRequest) -> WalletResult<TransferResponse> {
+    bitcoin::transfer_for_network(NETWORK_NAME, req).await
+}
+
+pub async fn transfer_many(
+    outputs: Vec<(String, String)>,
+    confirmed_only: bool,
+) -> WalletResult<TransferResponse> {
+    bitcoin::transfer_many_for_network(NETWORK_NAME, outputs, confirmed_only).await
+}
+
+pub async fn bump_fee(txid: &str, new_fee_rate: u64) -> WalletResult<TransferResponse> {
+    bitcoin::bump_fee_for_network(NETWORK_NAME, txid, new_fee_rate).await
+}
+
+pub async fn consolidate(max_fee_rate: u64) -> WalletResult<TransferResponse> {
+    bitcoin::consolidate_for_network(NETWORK_NAME, max_fee_rate).await
+}
+
+pub async fn create_psbt(req: TransferRequest) -> WalletResult<BitcoinPsbtResponse> {
+    bitcoin::create_psbt_for_network(NETWORK_NAME, req).await
+}
+
+pub async fn sign_psbt(psbt: &str) -> WalletResult<BitcoinPsbtResponse> {
+    bitcoin::sign_psbt_for_network(NETWORK_NAME, psbt).await
+}
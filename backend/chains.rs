@@ -2,6 +2,12 @@
 pub mod aptos_mainnet;
 #[path = "bitcoin.rs"]
 pub mod bitcoin;
+#[path = "bitcoin_regtest.rs"]
+pub mod bitcoin_regtest;
+#[path = "bitcoin_signet.rs"]
+pub mod bitcoin_signet;
+#[path = "bitcoin_testnet.rs"]
+pub mod bitcoin_testnet;
 #[path = "ethereum.rs"]
 pub mod ethereum;
 #[path = "internet_computer.rs"]
@@ -12,6 +18,10 @@ pub mod near_mainnet;
 pub mod sepolia;
 #[path = "solana.rs"]
 pub mod solana;
+#[path = "solana_devnet.rs"]
+pub mod solana_devnet;
+#[path = "solana_localnet.rs"]
+pub mod solana_localnet;
 #[path = "solana_testnet.rs"]
 pub mod solana_testnet;
 #[path = "sui_mainnet.rs"]
@@ -1,5 +1,7 @@
 use candid::Principal;
 
+use crate::types::BitcoinDataSource;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AppMode {
     Dev,
@@ -8,7 +10,15 @@ pub enum AppMode {
 
 pub const MODE: AppMode = AppMode::Dev;
 
+/// Used until the owner picks one with `set_bitcoin_data_source`.
+pub const DEFAULT_BITCOIN_DATA_SOURCE: BitcoinDataSource = BitcoinDataSource::Esplora;
+
 pub const ICP_LEDGER_MAINNET_PRINCIPAL_TEXT: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai";
+pub const CKBTC_MINTER_PRINCIPAL_TEXT: &str = "mqygn-kiaaa-aaaar-qaadq-cai";
+pub const CKBTC_LEDGER_PRINCIPAL_TEXT: &str = "mxzaz-hqaaa-aaaar-qaada-cai";
+// ckTESTBTC (Bitcoin testnet) minter and ledger, used outside prod.
+pub const CKTESTBTC_MINTER_PRINCIPAL_TEXT: &str = "ml52i-qqaaa-aaaar-qaaba-cai";
+pub const CKTESTBTC_LEDGER_PRINCIPAL_TEXT: &str = "mc6ru-gyaaa-aaaar-qaaaq-cai";
 pub const ICP_LEDGER_LOCAL_PRINCIPAL_TEXT: &str = "xjaw7-xp777-77774-qaajq-cai"; // "umunu-kh777-77774-qaaca-cai";
 
 pub fn is_dev_mode() -> bool {
@@ -19,6 +29,10 @@ pub fn auth_enabled() -> bool {
     !is_dev_mode()
 }
 
+pub fn default_bitcoin_data_source() -> BitcoinDataSource {
+    DEFAULT_BITCOIN_DATA_SOURCE
+}
+
 pub fn icp_ledger_mainnet_principal() -> Principal {
     Principal::from_text(ICP_LEDGER_MAINNET_PRINCIPAL_TEXT)
         .expect("invalid ICP_LEDGER_MAINNET_PRINCIPAL_TEXT")
@@ -29,6 +43,22 @@ pub fn icp_ledger_local_principal() -> Principal {
         .expect("invalid ICP_LEDGER_LOCAL_PRINCIPAL_TEXT")
 }
 
+pub fn ckbtc_minter_principal() -> Principal {
+    let text = match MODE {
+        AppMode::Dev => CKTESTBTC_MINTER_PRINCIPAL_TEXT,
+        AppMode::Prod => CKBTC_MINTER_PRINCIPAL_TEXT,
+    };
+    Principal::from_text(text).expect("invalid ckBTC minter principal text")
+}
+
+pub fn ckbtc_ledger_principal() -> Principal {
+    let text = match MODE {
+        AppMode::Dev => CKTESTBTC_LEDGER_PRINCIPAL_TEXT,
+        AppMode::Prod => CKBTC_LEDGER_PRINCIPAL_TEXT,
+    };
+    Principal::from_text(text).expect("invalid ckBTC ledger principal text")
+}
+
 pub fn default_icp_ledger_use_mainnet() -> bool {
     matches!(MODE, AppMode::Prod)
 }
@@ -66,5 +96,20 @@ mod tests {
         assert_eq!(default_http_cycles(), 30_000_000_000);
         assert_eq!(default_ecdsa_key_name(), "dfx_test_key");
         assert_eq!(default_schnorr_key_name(), "test_key_1");
+        assert_eq!(default_bitcoin_data_source(), BitcoinDataSource::Esplora);
+    }
+
+    #[test]
+    fn dev_mode_uses_cktestbtc_canisters() {
+        assert_eq!(
+            ckbtc_minter_principal(),
+            Principal::from_text(CKTESTBTC_MINTER_PRINCIPAL_TEXT).unwrap()
+        );
+        assert_eq!(
+            ckbtc_ledger_principal(),
+            Principal::from_text(CKTESTBTC_LEDGER_PRINCIPAL_TEXT).unwrap()
+        );
+        Principal::from_text(CKBTC_MINTER_PRINCIPAL_TEXT).unwrap();
+        Principal::from_text(CKBTC_LEDGER_PRINCIPAL_TEXT).unwrap();
     }
 }
@@ -0,0 +1,164 @@
+use crate::config::rpc_config;
+use crate::types::networks;
+
+// Both contracts are deployed at the same address on most EVM chains (including custom
+// networks); callers check `eth_getCode` before relying on them.
+pub const MULTICALL3_ADDRESS: &str = "0xca11bde05977b3631167028862be2a173976ca11";
+pub const DISPERSE_ADDRESS: &str = "0xd152f549545093347a162dce210e7293f1452150";
+
+// OP-stack predeploy exposing `getL1Fee(bytes)`.
+pub const OP_STACK_GAS_PRICE_ORACLE_ADDRESS: &str = "0x420000000000000000000000000000000000000f";
+
+pub const ENS_REGISTRY_ADDRESS: &str = "0x00000000000c2e074ec69a0bfb2997ba6c7d2e1e";
+pub const BASENAMES_REGISTRY_ADDRESS: &str = "0xb94704422c2a1e396835a571837aa5ae53285a95";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UniswapV3Config {
+    pub network: &'static str,
+    pub quoter_v2: &'static str,
+    pub swap_router02: &'static str,
+    pub wrapped_native: &'static str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NameRegistry {
+    pub network: &'static str,
+    pub registry_address: &'static str,
+}
+
+pub fn multicall3_address(network: &str) -> Option<&'static str> {
+    rpc_config::is_evm_network(network).then_some(MULTICALL3_ADDRESS)
+}
+
+pub fn disperse_address(network: &str) -> Option<&'static str> {
+    rpc_config::is_evm_network(network).then_some(DISPERSE_ADDRESS)
+}
+
+pub fn op_stack_gas_price_oracle(network: &str) -> Option<&'static str> {
+    match rpc_config::normalize_network(network).as_str() {
+        networks::OPTIMISM | networks::BASE => Some(OP_STACK_GAS_PRICE_ORACLE_ADDRESS),
+        _ => None,
+    }
+}
+
+pub fn uniswap_v3(network: &str) -> Option<UniswapV3Config> {
+    let network = rpc_config::normalize_network(network);
+    let cfg = match network.as_str() {
+        networks::ETHEREUM => UniswapV3Config {
+            network: networks::ETHEREUM,
+            quoter_v2: "0x61ffe014ba17989e743c5f6cb21bf9697530b21e",
+            swap_router02: "0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45",
+            wrapped_native: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+        },
+        networks::SEPOLIA => UniswapV3Config {
+            network: networks::SEPOLIA,
+            quoter_v2: "0xed1f6473345f45b75f8179591dd5ba1888cf2fb3",
+            swap_router02: "0x3bfa4769fb09eefc5a80d6e87c3b9c650f7ae48e",
+            wrapped_native: "0xfff9976782d46cc05630d1f6ebab18b2324d6b14",
+        },
+        networks::BASE => UniswapV3Config {
+            network: networks::BASE,
+            quoter_v2: "0x3d4e44eb1374240ce5f1b871ab261cd16335b76a",
+            swap_router02: "0x2626664c2603336e57b271c5c0b26f421741e481",
+            wrapped_native: "0x4200000000000000000000000000000000000006",
+        },
+        networks::ARBITRUM => UniswapV3Config {
+            network: networks::ARBITRUM,
+            quoter_v2: "0x61ffe014ba17989e743c5f6cb21bf9697530b21e",
+            swap_router02: "0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45",
+            wrapped_native: "0x82af49447d8a07e3bd95bd0d56f35241523fbab1",
+        },
+        networks::OPTIMISM => UniswapV3Config {
+            network: networks::OPTIMISM,
+            quoter_v2: "0x61ffe014ba17989e743c5f6cb21bf9697530b21e",
+            swap_router02: "0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45",
+            wrapped_native: "0x4200000000000000000000000000000000000006",
+        },
+        networks::POLYGON => UniswapV3Config {
+            network: networks::POLYGON,
+            quoter_v2: "0x61ffe014ba17989e743c5f6cb21bf9697530b21e",
+            swap_router02: "0x68b3465833fb72a70ecdf485e0e4c7bd8665fc45",
+            wrapped_native: "0x0d500b1d8e8ef31e21c99d1db9a6444d3adf1270",
+        },
+        networks::BSC => UniswapV3Config {
+            network: networks::BSC,
+            quoter_v2: "0x78d78e420da98ad378d7799be8f4af69033eb077",
+            swap_router02: "0xb971ef87ede563556b2ed4b1c0b0019111dd85d2",
+            wrapped_native: "0xbb4cdb9cbd36b01bd1cbaebf2de08d9173bc095c",
+        },
+        networks::AVALANCHE => UniswapV3Config {
+            network: networks::AVALANCHE,
+            quoter_v2: "0xbe0f5544ec67e9b3b2d979aaa43f18fd87e6257f",
+            swap_router02: "0xbb00ff08d01d300023c629e8ffffcb65a5a578ce",
+            wrapped_native: "0xb31f66aa3c1e785363f0875a1b74e27b85fd66c7",
+        },
+        _ => return None,
+    };
+    Some(cfg)
+}
+
+/// Registry used to resolve `name` for a transfer on `network`. ENS lives on L1 (or Sepolia for
+/// the testnet); `*.base.eth` Basenames are resolved on Base.
+pub fn name_registry(network: &str, name: &str) -> Option<NameRegistry> {
+    if !rpc_config::is_evm_network(network) {
+        return None;
+    }
+    if name.ends_with(".base.eth") {
+        return Some(NameRegistry {
+            network: networks::BASE,
+            registry_address: BASENAMES_REGISTRY_ADDRESS,
+        });
+    }
+    let network = if rpc_config::normalize_network(network) == networks::SEPOLIA {
+        networks::SEPOLIA
+    } else {
+        networks::ETHEREUM
+    };
+    Some(NameRegistry {
+        network,
+        registry_address: ENS_REGISTRY_ADDRESS,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_price_oracle_only_on_op_stack_networks() {
+        assert!(op_stack_gas_price_oracle(networks::OPTIMISM).is_some());
+        assert!(op_stack_gas_price_oracle(networks::BASE).is_some());
+        for network in [
+            networks::ETHEREUM,
+            networks::ARBITRUM,
+            networks::POLYGON,
+            networks::BSC,
+        ] {
+            assert!(op_stack_gas_price_oracle(network).is_none(), "{network}");
+        }
+    }
+
+    #[test]
+    fn uniswap_v3_config_follows_network_names() {
+        let cfg = uniswap_v3(networks::BASE).unwrap();
+        assert_eq!(cfg.network, networks::BASE);
+        assert!(uniswap_v3(networks::OKX).is_none());
+        assert!(uniswap_v3(networks::SOLANA).is_none());
+        for network in [
+            networks::ETHEREUM,
+            networks::SEPOLIA,
+            networks::BASE,
+            networks::ARBITRUM,
+            networks::OPTIMISM,
+            networks::POLYGON,
+            networks::BSC,
+            networks::AVALANCHE,
+        ] {
+            let cfg = uniswap_v3(network).unwrap();
+            for addr in [cfg.quoter_v2, cfg.swap_router02, cfg.wrapped_native] {
+                assert_eq!(addr.len(), 42, "{network}: {addr}");
+                assert!(addr[2..].bytes().all(|b| b.is_ascii_hexdigit()));
+            }
+        }
+    }
+}
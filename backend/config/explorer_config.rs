@@ -64,6 +64,16 @@ pub fn configured_explorer(network: &str) -> Option<ExplorerConfig> {
             address_url_template: "https://mempool.space/address/{address}",
             token_url_template: None,
         }),
+        networks::BITCOIN_TESTNET => Some(ExplorerConfig {
+            network: networks::BITCOIN_TESTNET,
+            address_url_template: "https://mempool.space/testnet4/address/{address}",
+            token_url_template: None,
+        }),
+        networks::BITCOIN_SIGNET => Some(ExplorerConfig {
+            network: networks::BITCOIN_SIGNET,
+            address_url_template: "https://mempool.space/signet/address/{address}",
+            token_url_template: None,
+        }),
         networks::INTERNET_COMPUTER => Some(ExplorerConfig {
             network: networks::INTERNET_COMPUTER,
             address_url_template: "https://dashboard.internetcomputer.org/canister/{address}",
@@ -79,6 +89,11 @@ pub fn configured_explorer(network: &str) -> Option<ExplorerConfig> {
             address_url_template: "https://solscan.io/account/{address}?cluster=testnet",
             token_url_template: Some("https://solscan.io/token/{token}?cluster=testnet"),
         }),
+        networks::SOLANA_DEVNET => Some(ExplorerConfig {
+            network: networks::SOLANA_DEVNET,
+            address_url_template: "https://solscan.io/account/{address}?cluster=devnet",
+            token_url_template: Some("https://solscan.io/token/{token}?cluster=devnet"),
+        }),
         networks::TRON => Some(ExplorerConfig {
             network: networks::TRON,
             address_url_template: "https://tronscan.org/#/address/{address}",
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 pub mod app_config;
+pub mod contract_config;
 pub mod explorer_config;
 pub mod rpc_config;
 pub mod token_list;
@@ -1,8 +1,9 @@
 use crate::state;
-use crate::types::networks;
+use crate::types::{networks, CustomEvmNetworkResponse};
 
 pub const DEFAULT_SOLANA_RPC_URL: &str = "https://solana-rpc.publicnode.com";
 pub const DEFAULT_SOLANA_TESTNET_RPC_URL: &str = "https://solana-testnet-rpc.publicnode.com";
+pub const DEFAULT_SOLANA_DEVNET_RPC_URL: &str = "https://api.devnet.solana.com";
 pub const DEFAULT_ETHEREUM_RPC_URL: &str = "https://ethereum-rpc.publicnode.com";
 pub const DEFAULT_SEPOLIA_RPC_URL: &str = "https://ethereum-sepolia-rpc.publicnode.com";
 pub const DEFAULT_BASE_RPC_URL: &str = "https://base-rpc.publicnode.com";
@@ -19,6 +20,8 @@ pub const DEFAULT_APTOS_RPC_URL: &str = "https://fullnode.mainnet.aptoslabs.com/
 pub const DEFAULT_SUI_RPC_URL: &str = "https://fullnode.mainnet.sui.io:443";
 
 pub const DEFAULT_BITCOIN_RPC_URL: &str = "https://blockstream.info/api";
+pub const DEFAULT_BITCOIN_TESTNET_RPC_URL: &str = "https://mempool.space/testnet4/api";
+pub const DEFAULT_BITCOIN_SIGNET_RPC_URL: &str = "https://mempool.space/signet/api";
 
 pub const TEST_CUSTOM_RPC_URL: &str = "https://rpc.example";
 
@@ -64,6 +67,41 @@ const CHAIN_CONFIGS: &[ChainConfig] = &[
         chain_id: None,
         wallet_visible: true,
     },
+    ChainConfig {
+        id: networks::BITCOIN_TESTNET,
+        primary_symbol: "BTC",
+        address_family: "bitcoin",
+        shared_address_group: "btc-taproot-testnet-managed-v1",
+        supports_send: true,
+        supports_balance: true,
+        default_rpc_url: Some(DEFAULT_BITCOIN_TESTNET_RPC_URL),
+        chain_id: None,
+        wallet_visible: true,
+    },
+    ChainConfig {
+        id: networks::BITCOIN_SIGNET,
+        primary_symbol: "BTC",
+        address_family: "bitcoin",
+        shared_address_group: "btc-taproot-testnet-managed-v1",
+        supports_send: true,
+        supports_balance: true,
+        default_rpc_url: Some(DEFAULT_BITCOIN_SIGNET_RPC_URL),
+        chain_id: None,
+        wallet_visible: true,
+    },
+    ChainConfig {
+        id: networks::BITCOIN_REGTEST,
+        primary_symbol: "BTC",
+        address_family: "bitcoin",
+        shared_address_group: "btc-taproot-regtest-managed-v1",
+        supports_send: true,
+        supports_balance: true,
+        // HTTPS outcalls cannot reach a local esplora; use the Bitcoin canister data source or
+        // configure an https esplora endpoint.
+        default_rpc_url: None,
+        chain_id: None,
+        wallet_visible: true,
+    },
     ChainConfig {
         id: networks::INTERNET_COMPUTER,
         primary_symbol: "ICP",
@@ -196,6 +234,30 @@ const CHAIN_CONFIGS: &[ChainConfig] = &[
         chain_id: None,
         wallet_visible: true,
     },
+    ChainConfig {
+        id: networks::SOLANA_DEVNET,
+        primary_symbol: "SOL",
+        address_family: "solana",
+        shared_address_group: "solana-ed25519-base58-v1",
+        supports_send: true,
+        supports_balance: true,
+        default_rpc_url: Some(DEFAULT_SOLANA_DEVNET_RPC_URL),
+        chain_id: None,
+        wallet_visible: true,
+    },
+    ChainConfig {
+        id: networks::SOLANA_LOCALNET,
+        primary_symbol: "SOL",
+        address_family: "solana",
+        shared_address_group: "solana-ed25519-base58-v1",
+        supports_send: true,
+        supports_balance: true,
+        // HTTPS outcalls cannot reach a plain-http `solana-test-validator`; configure an https
+        // endpoint (e.g. a tunnel to port 8899).
+        default_rpc_url: None,
+        chain_id: None,
+        wallet_visible: true,
+    },
     ChainConfig {
         id: networks::TRON,
         primary_symbol: "TRX",
@@ -302,16 +364,39 @@ pub fn configured_rpc(network: &str) -> Option<RpcConfig> {
 
 // This mirrors the Motoko behavior: known chain IDs or custom EVM chain IDs are considered supported.
 pub fn is_supported(network: &str) -> bool {
-    wallet_network_info(network).is_some() || parse_custom_chain_id(network).is_some()
+    wallet_network_info(network).is_some()
+        || custom_evm_network(network).is_some()
+        || parse_custom_chain_id(network).is_some()
 }
 
 pub fn chain_id(network: &str) -> Option<u64> {
     match find_chain_by_input(network) {
         Some(cfg) => cfg.chain_id,
-        None => parse_custom_chain_id(network),
+        None => custom_evm_network(network)
+            .map(|n| n.chain_id)
+            .or_else(|| parse_custom_chain_id(network)),
+    }
+}
+
+pub fn custom_evm_network(network: &str) -> Option<CustomEvmNetworkResponse> {
+    let normalized = normalize_text(network);
+    if normalized.is_empty() {
+        return None;
+    }
+    state::custom_evm_network(&normalized)
+}
+
+pub fn is_evm_network(network: &str) -> bool {
+    match find_chain_by_input(network) {
+        Some(cfg) => cfg.address_family == "evm",
+        None => custom_evm_network(network).is_some(),
     }
 }
 
+pub fn is_builtin_network(network: &str) -> bool {
+    find_chain_by_input(network).is_some()
+}
+
 pub fn default_rpc_url(network: &str) -> Option<&'static str> {
     find_chain_by_input(network).and_then(|cfg| cfg.default_rpc_url)
 }
@@ -338,6 +423,10 @@ pub fn resolve_rpc_url(network: &str, rpc_url: Option<&str>) -> Result<String, S
         None => {
             if parse_custom_chain_id(network).is_some() {
                 Err(format!("rpcUrl is required for custom network: {network}"))
+            } else if is_builtin_network(network) {
+                Err(format!(
+                    "no RPC configured for {network}; set an https endpoint with set_configured_rpc"
+                ))
             } else {
                 Err(format!("unsupported network: {network}"))
             }
@@ -464,6 +553,22 @@ mod tests {
             wallet_network_info(networks::BITCOIN).unwrap().id,
             networks::BITCOIN
         );
+        assert_eq!(
+            wallet_network_info(networks::BITCOIN_SIGNET)
+                .unwrap()
+                .shared_address_group,
+            wallet_network_info(networks::BITCOIN_TESTNET)
+                .unwrap()
+                .shared_address_group
+        );
+        assert_ne!(
+            wallet_network_info(networks::BITCOIN)
+                .unwrap()
+                .shared_address_group,
+            wallet_network_info(networks::BITCOIN_TESTNET)
+                .unwrap()
+                .shared_address_group
+        );
         assert_eq!(
             wallet_network_info(networks::TON_MAINNET).unwrap().id,
             networks::TON_MAINNET
@@ -492,6 +597,36 @@ mod tests {
                 .unwrap()
                 .shared_address_group
         );
+        assert_eq!(
+            wallet_network_info(networks::SOLANA_DEVNET)
+                .unwrap()
+                .shared_address_group,
+            wallet_network_info(networks::SOLANA_LOCALNET)
+                .unwrap()
+                .shared_address_group
+        );
+    }
+
+    #[test]
+    fn bitcoin_regtest_requires_configured_rpc() {
+        assert_eq!(default_rpc_url(networks::BITCOIN_REGTEST), None);
+        let err = resolve_rpc_url(networks::BITCOIN_REGTEST, None).unwrap_err();
+        assert!(err.contains("set_configured_rpc"), "{err}");
+        assert_eq!(
+            resolve_rpc_url(networks::BITCOIN_REGTEST, Some(TEST_CUSTOM_RPC_URL)).unwrap(),
+            TEST_CUSTOM_RPC_URL
+        );
+    }
+
+    #[test]
+    fn solana_localnet_requires_configured_rpc() {
+        assert_eq!(default_rpc_url(networks::SOLANA_LOCALNET), None);
+        let err = resolve_rpc_url(networks::SOLANA_LOCALNET, None).unwrap_err();
+        assert!(err.contains("set_configured_rpc"), "{err}");
+        assert_eq!(
+            resolve_rpc_url(networks::SOLANA_LOCALNET, Some(TEST_CUSTOM_RPC_URL)).unwrap(),
+            TEST_CUSTOM_RPC_URL
+        );
     }
 
     #[test]
@@ -502,6 +637,26 @@ mod tests {
         assert_eq!(chain_id("foo:1"), None);
     }
 
+    #[test]
+    fn custom_evm_networks_resolve_from_state() {
+        state::upsert_custom_evm_network(CustomEvmNetworkResponse {
+            id: "linea".to_string(),
+            chain_id: 59144,
+            native_symbol: "ETH".to_string(),
+            explorer_template: None,
+        });
+        state::upsert_configured_rpc("linea", TEST_CUSTOM_RPC_URL);
+        assert_eq!(chain_id("Linea"), Some(59144));
+        assert!(is_evm_network("linea"));
+        assert!(is_evm_network(networks::BASE));
+        assert!(!is_evm_network(networks::SOLANA));
+        assert!(!is_builtin_network("linea"));
+        assert_eq!(
+            resolve_rpc_url("linea", None).as_deref(),
+            Ok(TEST_CUSTOM_RPC_URL)
+        );
+    }
+
     #[test]
     fn resolves_rpc_urls() {
         let custom_rpc_url = format!(" {TEST_CUSTOM_RPC_URL} ");
@@ -8,8 +8,17 @@ pub enum WalletError {
     Forbidden,
     Paused,
     InvalidInput(String),
-    Unimplemented { network: String, operation: String },
+    Unimplemented {
+        network: String,
+        operation: String,
+    },
     Internal(String),
+    ExecutionReverted {
+        network: String,
+        reason: String,
+        selector: Option<String>,
+        data: String,
+    },
 }
 
 impl WalletError {
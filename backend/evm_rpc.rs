@@ -13,13 +13,42 @@ use crate::config;
 use crate::error::{WalletError, WalletResult};
 use crate::sdk::evm_tx;
 use crate::types::{
-    BalanceRequest, BalanceResponse, BroadcastHttpRequest, ConfiguredTokenResponse,
-    TransferRequest, TransferResponse,
+    BalanceRequest, BalanceResponse, BroadcastHttpRequest, ConfiguredNftCollectionResponse,
+    ConfiguredTokenResponse, Erc20AllowanceRequest, Erc20AllowanceResponse, Erc20ApprovalsRequest,
+    Erc20ApprovalsResponse, EvmBatchTransferRequest, EvmBatchTransferResponse,
+    EvmFeeEstimateResponse, EvmSwapQuoteResponse, EvmSwapRequest, EvmSwapResponse,
+    NftOwnershipRequest, NftOwnershipResponse, TransferRequest, TransferResponse,
 };
 
 const EVM_NATIVE_DECIMALS: usize = 18;
 const EVM_NATIVE_GAS_LIMIT: u64 = 21_000;
 const EVM_ERC20_GAS_LIMIT_DEFAULT: u64 = 120_000;
+const EVM_NFT_GAS_LIMIT_DEFAULT: u64 = 200_000;
+const ERC721_INTERFACE_ID: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+const ERC1155_INTERFACE_ID: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+const ERC20_APPROVAL_SCAN_CHUNK_BLOCKS: u64 = 50_000;
+const ERC20_APPROVAL_SCAN_MAX_CHUNKS: usize = 20;
+const ERC20_APPROVAL_SCAN_MAX_PAIRS: usize = 50;
+const EVM_RPC_MAX_RESPONSE_BYTES: u64 = 32 * 1024;
+const EVM_RPC_LOGS_MAX_RESPONSE_BYTES: u64 = 512 * 1024;
+const EVM_BATCH_MAX_ITEMS: usize = 256;
+const EVM_MULTICALL_BASE_GAS: u64 = 60_000;
+const EVM_MULTICALL_PER_CALL_GAS: u64 = 40_000;
+const EVM_DISPERSE_BASE_GAS: u64 = 60_000;
+const EVM_DISPERSE_PER_TRANSFER_GAS: u64 = 45_000;
+const UNISWAP_V3_FEE_TIERS: [u32; 4] = [100, 500, 3000, 10_000];
+const UNISWAP_V3_HOP_FEE_TIERS: [u32; 2] = [500, 3000];
+// SwapRouter02 `ADDRESS_THIS` sentinel: keep output in the router so it can be unwrapped.
+const SWAP_ROUTER_ADDRESS_THIS: [u8; 20] = {
+    let mut a = [0u8; 20];
+    a[19] = 2;
+    a
+};
+const EVM_SWAP_GAS_OVERHEAD: u64 = 150_000;
+const EVM_SWAP_DEADLINE_SECS: u64 = 20 * 60;
+const EVM_SWAP_SLIPPAGE_BPS_DEFAULT: u32 = 50;
+const EVM_SWAP_SLIPPAGE_BPS_MAX: u32 = 5_000;
+const EVM_NATIVE_TOKEN_PLACEHOLDER: &str = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
 
 struct PreparedEvmBroadcast {
     tx_id: String,
@@ -27,6 +56,40 @@ struct PreparedEvmBroadcast {
     broadcast_request: BroadcastHttpRequest,
 }
 
+struct BatchItem {
+    to: [u8; 20],
+    amount: BigUint,
+    token: Option<String>,
+}
+
+struct SwapToken {
+    native: bool,
+    address: [u8; 20],
+    decimals: u8,
+}
+
+struct SwapQuote {
+    tokens: Vec<[u8; 20]>,
+    fees: Vec<u32>,
+    amount_out: BigUint,
+    gas_estimate: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NftStandard {
+    Erc721,
+    Erc1155,
+}
+
+impl NftStandard {
+    fn as_str(self) -> &'static str {
+        match self {
+            NftStandard::Erc721 => "erc721",
+            NftStandard::Erc1155 => "erc1155",
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct JsonRpcRequest {
     jsonrpc: &'static str,
@@ -45,6 +108,8 @@ struct JsonRpcResponse {
 struct JsonRpcError {
     code: i64,
     message: String,
+    #[serde(default)]
+    data: Option<Value>,
 }
 
 pub async fn get_native_eth_balance(
@@ -143,21 +208,19 @@ pub async fn transfer_native_eth(
         ));
     }
     let to = normalize_and_validate_hex_address(&req.to)?;
-    let value_wei = evm_tx::parse_decimal_units(req.amount.trim(), EVM_NATIVE_DECIMALS)?;
+    let to_bytes = hex_address_to_20_bytes(&to)?;
+    let gas_limit = BigUint::from(EVM_NATIVE_GAS_LIMIT);
+    let mut signer = EvmSigner::new(network, req.from.as_deref()).await?;
+    let value_wei = if req.amount.trim().eq_ignore_ascii_case("max") {
+        signer.max_native_value(&to_bytes, &gas_limit).await?
+    } else {
+        evm_tx::parse_decimal_units(req.amount.trim(), EVM_NATIVE_DECIMALS)?
+    };
     if value_wei == BigUint::from(0u8) {
         return Err(WalletError::invalid_input("amount must be > 0"));
     }
-    let to_bytes = hex_address_to_20_bytes(&to)?;
 
-    let prepared = prepare_eip1559_transaction(
-        network,
-        req.from.as_deref(),
-        &to_bytes,
-        &value_wei,
-        &[],
-        &BigUint::from(EVM_NATIVE_GAS_LIMIT),
-    )
-    .await?;
+    let prepared = signer.sign(&to_bytes, &value_wei, &[], &gas_limit).await?;
 
     Ok(TransferResponse {
         network: network.to_string(),
@@ -170,6 +233,7 @@ pub async fn transfer_native_eth(
             "signed EIP-1559 transaction prepared; frontend should broadcast via eth_sendRawTransaction: {}",
             prepared.tx_id
         ),
+        resolved_to: None,
     })
 }
 
@@ -212,9 +276,1037 @@ pub async fn transfer_erc20(network: &str, req: TransferRequest) -> WalletResult
             "signed ERC20 transfer prepared; frontend should broadcast via eth_sendRawTransaction: {}",
             prepared.tx_id
         ),
+        resolved_to: None,
+    })
+}
+
+/// Fee breakdown for a native (or ERC-20, when `token` is set) transfer without signing it.
+/// `amount = "max"` is accepted for native transfers.
+pub async fn estimate_fee(
+    network: &str,
+    req: TransferRequest,
+) -> WalletResult<EvmFeeEstimateResponse> {
+    let to = normalize_and_validate_hex_address(&req.to)?;
+    let to_bytes = hex_address_to_20_bytes(&to)?;
+    let signer = EvmSigner::new(network, req.from.as_deref()).await?;
+
+    let (target, value, data, gas_units) = match req.token.as_deref() {
+        Some(token) => {
+            let token_contract = normalize_and_validate_hex_address(token)?;
+            let decimals = fetch_erc20_decimals(network, &token_contract).await?;
+            let amount_units =
+                evm_tx::parse_decimal_units(req.amount.trim(), usize::from(decimals))?;
+            (
+                hex_address_to_20_bytes(&token_contract)?,
+                BigUint::from(0u8),
+                evm_tx::encode_erc20_transfer_call(&to_bytes, &amount_units)?,
+                EVM_ERC20_GAS_LIMIT_DEFAULT,
+            )
+        }
+        None => {
+            let value = if req.amount.trim().eq_ignore_ascii_case("max") {
+                signer
+                    .max_native_value(&to_bytes, &BigUint::from(EVM_NATIVE_GAS_LIMIT))
+                    .await?
+            } else {
+                evm_tx::parse_decimal_units(req.amount.trim(), EVM_NATIVE_DECIMALS)?
+            };
+            (to_bytes, value, Vec::new(), EVM_NATIVE_GAS_LIMIT)
+        }
+    };
+
+    let gas_limit = BigUint::from(gas_units);
+    let payload = signer.unsigned_payload(&target, &value, &data, &gas_limit);
+    let fees = signer.fee_breakdown(&payload, &gas_limit).await?;
+    let total_fee = fees.total();
+    let total_cost = &value + &total_fee;
+    let fmt = |v: &BigUint| evm_tx::format_units(v, EVM_NATIVE_DECIMALS);
+
+    Ok(EvmFeeEstimateResponse {
+        network: network.to_string(),
+        gas_limit: gas_units,
+        max_fee_per_gas: signer.max_fee_per_gas.to_string(),
+        max_priority_fee_per_gas: signer.max_priority_fee_per_gas.to_string(),
+        execution_fee: fmt(&fees.execution_fee),
+        l1_data_fee: fmt(&fees.l1_data_fee),
+        total_fee: fmt(&total_fee),
+        value: fmt(&value),
+        total_cost: fmt(&total_cost),
+        balance: fmt(&signer.balance),
+        sufficient: total_cost <= signer.balance,
+    })
+}
+
+pub async fn discover_nft_collection(
+    network: &str,
+    contract_address: &str,
+) -> WalletResult<ConfiguredNftCollectionResponse> {
+    let contract = normalize_and_validate_hex_address(contract_address)?;
+    let standard = detect_nft_standard(network, &contract).await?;
+    let symbol = fetch_erc20_text_property(network, &contract, [0x95, 0xd8, 0x9b, 0x41])
+        .await
+        .unwrap_or_else(|_| format!("NFT{}", short_address_suffix(&contract).to_uppercase()));
+    let name = fetch_erc20_text_property(network, &contract, [0x06, 0xfd, 0xde, 0x03])
+        .await
+        .unwrap_or_else(|_| format!("NFT {}", short_address_suffix(&contract)));
+
+    Ok(ConfiguredNftCollectionResponse {
+        network: network.to_string(),
+        standard: standard.as_str().to_string(),
+        symbol,
+        name,
+        contract_address: contract,
+    })
+}
+
+pub async fn get_nft_ownership(
+    network: &str,
+    req: NftOwnershipRequest,
+) -> WalletResult<NftOwnershipResponse> {
+    let contract = normalize_and_validate_hex_address(&req.contract_address)?;
+    let (token_id, token_id_text) = parse_nft_token_id(&req.token_id)?;
+    let account = match req
+        .account
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        Some(account) => normalize_and_validate_hex_address(account)?,
+        None => managed_evm_address().await?,
+    };
+    let standard = detect_nft_standard(network, &contract).await?;
+
+    let (owner, balance) = match standard {
+        NftStandard::Erc721 => {
+            let owner = fetch_erc721_owner(network, &contract, &token_id).await?;
+            let balance = if eq_hex_address(&owner, &account) {
+                "1"
+            } else {
+                "0"
+            };
+            (Some(owner), balance.to_string())
+        }
+        NftStandard::Erc1155 => {
+            let account_bytes = hex_address_to_20_bytes(&account)?;
+            let balance =
+                fetch_erc1155_balance(network, &contract, &account_bytes, &token_id).await?;
+            (None, balance.to_str_radix(10))
+        }
+    };
+
+    Ok(NftOwnershipResponse {
+        network: network.to_string(),
+        contract_address: contract,
+        token_id: token_id_text,
+        standard: standard.as_str().to_string(),
+        account,
+        owner,
+        balance,
+    })
+}
+
+pub async fn transfer_nft(network: &str, req: TransferRequest) -> WalletResult<TransferResponse> {
+    let contract = req
+        .token
+        .as_deref()
+        .ok_or_else(|| WalletError::invalid_input("token (NFT contract) is required"))?;
+    let contract = normalize_and_validate_hex_address(contract)?;
+    let contract_bytes = hex_address_to_20_bytes(&contract)?;
+    let token_id_text = metadata_value(&req.metadata, "token_id")
+        .ok_or_else(|| WalletError::invalid_input("metadata token_id is required"))?;
+    let (token_id, token_id_text) = parse_nft_token_id(token_id_text)?;
+
+    let to = normalize_and_validate_hex_address(&req.to)?;
+    let to_bytes = hex_address_to_20_bytes(&to)?;
+    let from = managed_evm_address().await?;
+    let from_bytes = hex_address_to_20_bytes(&from)?;
+
+    let standard = detect_nft_standard(network, &contract).await?;
+    let data = match standard {
+        NftStandard::Erc721 => {
+            let amount = req.amount.trim();
+            if !amount.is_empty() && amount != "1" {
+                return Err(WalletError::invalid_input(
+                    "ERC721 transfer amount must be 1",
+                ));
+            }
+            let owner = fetch_erc721_owner(network, &contract, &token_id).await?;
+            if !eq_hex_address(&owner, &from) {
+                return Err(WalletError::invalid_input(format!(
+                    "managed EVM address does not own token {token_id_text} (owner: {owner})"
+                )));
+            }
+            evm_tx::encode_erc721_safe_transfer_from_call(&from_bytes, &to_bytes, &token_id)?
+        }
+        NftStandard::Erc1155 => {
+            let amount = evm_tx::parse_decimal_units(req.amount.trim(), 0)?;
+            if amount == BigUint::from(0u8) {
+                return Err(WalletError::invalid_input("amount must be > 0"));
+            }
+            let balance = fetch_erc1155_balance(network, &contract, &from_bytes, &token_id).await?;
+            if balance < amount {
+                return Err(WalletError::invalid_input(format!(
+                    "insufficient ERC1155 balance for token {token_id_text}: have {balance}, need {amount}"
+                )));
+            }
+            evm_tx::encode_erc1155_safe_transfer_from_call(
+                &from_bytes,
+                &to_bytes,
+                &token_id,
+                &amount,
+            )?
+        }
+    };
+
+    let prepared = prepare_eip1559_transaction(
+        network,
+        req.from.as_deref(),
+        &contract_bytes,
+        &BigUint::from(0u8),
+        &data,
+        &BigUint::from(EVM_NFT_GAS_LIMIT_DEFAULT),
+    )
+    .await?;
+
+    Ok(TransferResponse {
+        network: network.to_string(),
+        accepted: false,
+        tx_id: Some(prepared.tx_id.clone()),
+        signed_tx: Some(prepared.raw_tx_hex),
+        signed_tx_encoding: Some("hex".to_string()),
+        broadcast_request: Some(prepared.broadcast_request),
+        message: format!(
+            "signed {} safeTransferFrom prepared; frontend should broadcast via eth_sendRawTransaction: {}",
+            standard.as_str().to_uppercase(),
+            prepared.tx_id
+        ),
+        resolved_to: None,
+    })
+}
+
+pub async fn approve_erc20(network: &str, req: TransferRequest) -> WalletResult<TransferResponse> {
+    let token = req
+        .token
+        .as_deref()
+        .ok_or_else(|| WalletError::invalid_input("token is required for ERC20 approve"))?;
+    let token = normalize_and_validate_hex_address(token)?;
+    let spender = normalize_and_validate_hex_address(&req.to)?;
+    let amount = match req.amount.trim().to_lowercase().as_str() {
+        "max" | "unlimited" => evm_tx::max_uint256(),
+        other => {
+            let decimals = fetch_erc20_decimals(network, &token).await?;
+            evm_tx::parse_decimal_units(other, usize::from(decimals))?
+        }
+    };
+    if amount == BigUint::from(0u8) {
+        return Err(WalletError::invalid_input(
+            "approve amount must be > 0; use erc20_revoke to clear an allowance",
+        ));
+    }
+
+    let prepared =
+        prepare_erc20_approve(network, req.from.as_deref(), &token, &spender, &amount).await?;
+    Ok(prepared_transfer_response(
+        network,
+        prepared,
+        "signed ERC20 approve prepared",
+    ))
+}
+
+pub async fn revoke_erc20(
+    network: &str,
+    req: Erc20AllowanceRequest,
+) -> WalletResult<TransferResponse> {
+    let token = normalize_and_validate_hex_address(&req.token)?;
+    let spender = normalize_and_validate_hex_address(&req.spender)?;
+    let prepared = prepare_erc20_approve(
+        network,
+        req.owner.as_deref(),
+        &token,
+        &spender,
+        &BigUint::from(0u8),
+    )
+    .await?;
+    Ok(prepared_transfer_response(
+        network,
+        prepared,
+        "signed ERC20 approve(spender, 0) revoke prepared",
+    ))
+}
+
+pub async fn get_erc20_allowance(
+    network: &str,
+    req: Erc20AllowanceRequest,
+) -> WalletResult<Erc20AllowanceResponse> {
+    let token = normalize_and_validate_hex_address(&req.token)?;
+    let spender = normalize_and_validate_hex_address(&req.spender)?;
+    let owner = match req
+        .owner
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        Some(owner) => normalize_and_validate_hex_address(owner)?,
+        None => managed_evm_address().await?,
+    };
+    let raw = fetch_erc20_allowance(network, &token, &owner, &spender).await?;
+    let decimals = fetch_erc20_decimals(network, &token).await.ok();
+    Ok(allowance_response(
+        network, token, owner, spender, &raw, decimals,
+    ))
+}
+
+pub async fn list_erc20_approvals(
+    network: &str,
+    req: Erc20ApprovalsRequest,
+) -> WalletResult<Erc20ApprovalsResponse> {
+    let owner = managed_evm_address().await?;
+    let owner_bytes = hex_address_to_20_bytes(&owner)?;
+    let latest = evm_tx::parse_hex_quantity(
+        &rpc_call_hex_string(network, "eth_blockNumber", json!([])).await?,
+    )?;
+    let latest = u64::try_from(latest)
+        .map_err(|_| WalletError::Internal("eth_blockNumber out of range".into()))?;
+    let to_block = req.to_block.map_or(latest, |block| block.min(latest));
+    let from_block = req.from_block.unwrap_or(0);
+    if from_block > to_block {
+        return Err(WalletError::invalid_input(format!(
+            "from_block {from_block} is after to_block {to_block}"
+        )));
+    }
+    let ranges = approval_scan_ranges(
+        from_block,
+        to_block,
+        ERC20_APPROVAL_SCAN_CHUNK_BLOCKS,
+        ERC20_APPROVAL_SCAN_MAX_CHUNKS,
+    );
+    let scanned_from = ranges.last().map_or(to_block, |(lo, _)| *lo);
+
+    let tokens = req
+        .tokens
+        .iter()
+        .map(|t| normalize_and_validate_hex_address(t))
+        .collect::<WalletResult<Vec<_>>>()?;
+    let mut logs = Vec::new();
+    for (lo, hi) in &ranges {
+        let mut filter = json!({
+            "fromBlock": format!("0x{lo:x}"),
+            "toBlock": format!("0x{hi:x}"),
+            "topics": [
+                evm_tx::ERC20_APPROVAL_EVENT_TOPIC,
+                format!("0x{}{}", "0".repeat(24), addressing::hex_encode(&owner_bytes))
+            ]
+        });
+        if !tokens.is_empty() {
+            filter["address"] = json!(tokens);
+        }
+        let chunk = rpc_call_with_limit(
+            network,
+            "eth_getLogs",
+            json!([filter]),
+            EVM_RPC_LOGS_MAX_RESPONSE_BYTES,
+        )
+        .await?;
+        match chunk {
+            Value::Array(entries) => logs.extend(entries),
+            _ => {
+                return Err(WalletError::Internal(
+                    "eth_getLogs result is not an array".into(),
+                ))
+            }
+        }
+    }
+
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    for log in &logs {
+        // ERC721 Approval shares the topic but indexes tokenId as a fourth topic.
+        let Some(topics) = log.get("topics").and_then(Value::as_array) else {
+            continue;
+        };
+        if topics.len() != 3 {
+            continue;
+        }
+        let (Some(token), Some(spender_topic)) = (
+            log.get("address").and_then(Value::as_str),
+            topics[2].as_str(),
+        ) else {
+            continue;
+        };
+        let spender_word = evm_tx::parse_hex_data(spender_topic)?;
+        let spender = format!(
+            "0x{}",
+            addressing::hex_encode(&evm_tx::decode_abi_address(&spender_word)?)
+        );
+        let token = normalize_and_validate_hex_address(token)?;
+        if !pairs.iter().any(|(t, s)| t == &token && s == &spender) {
+            pairs.push((token, spender));
+        }
+    }
+    if pairs.len() > ERC20_APPROVAL_SCAN_MAX_PAIRS {
+        return Err(WalletError::invalid_input(format!(
+            "found {} token/spender pairs; narrow the scan with from_block, to_block or tokens (max {})",
+            pairs.len(),
+            ERC20_APPROVAL_SCAN_MAX_PAIRS
+        )));
+    }
+
+    let mut out = Vec::new();
+    let mut decimals_cache: Vec<(String, Option<u8>)> = Vec::new();
+    for (token, spender) in pairs {
+        let raw = fetch_erc20_allowance(network, &token, &owner, &spender).await?;
+        if raw == BigUint::from(0u8) {
+            continue;
+        }
+        let decimals = match decimals_cache.iter().find(|(t, _)| t == &token) {
+            Some((_, d)) => *d,
+            None => {
+                let d = fetch_erc20_decimals(network, &token).await.ok();
+                decimals_cache.push((token.clone(), d));
+                d
+            }
+        };
+        out.push(allowance_response(
+            network,
+            token,
+            owner.clone(),
+            spender,
+            &raw,
+            decimals,
+        ));
+    }
+    Ok(Erc20ApprovalsResponse {
+        network: network.to_string(),
+        approvals: out,
+        from_block: scanned_from,
+        to_block,
+        complete: scanned_from == from_block,
+    })
+}
+
+// Newest-first block ranges covering `to_block` down towards `from_block`, at most `max_chunks`
+// of them; the caller resumes below the last range when it stops short of `from_block`.
+fn approval_scan_ranges(
+    from_block: u64,
+    to_block: u64,
+    chunk_blocks: u64,
+    max_chunks: usize,
+) -> Vec<(u64, u64)> {
+    let mut ranges = Vec::new();
+    let mut hi = to_block;
+    while ranges.len() < max_chunks {
+        let lo = hi.saturating_sub(chunk_blocks - 1).max(from_block);
+        ranges.push((lo, hi));
+        if lo == from_block {
+            break;
+        }
+        hi = lo - 1;
+    }
+    ranges
+}
+
+pub async fn batch_transfer(
+    network: &str,
+    req: EvmBatchTransferRequest,
+) -> WalletResult<EvmBatchTransferResponse> {
+    if req.items.is_empty() {
+        return Err(WalletError::invalid_input("items must not be empty"));
+    }
+    if req.items.len() > EVM_BATCH_MAX_ITEMS {
+        return Err(WalletError::invalid_input(format!(
+            "at most {EVM_BATCH_MAX_ITEMS} items per batch"
+        )));
+    }
+    let mode = req
+        .mode
+        .as_deref()
+        .map(|m| m.trim().to_lowercase())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| "sequential".to_string());
+    if mode != "sequential" && mode != "multicall" {
+        return Err(WalletError::invalid_input(
+            "mode must be 'sequential' or 'multicall'",
+        ));
+    }
+
+    let mut decimals_cache: Vec<(String, u8)> = Vec::new();
+    let mut items = Vec::with_capacity(req.items.len());
+    for (index, item) in req.items.iter().enumerate() {
+        let to = hex_address_to_20_bytes(&normalize_and_validate_hex_address(&item.to)?)?;
+        let token = match item.token.as_deref().map(str::trim) {
+            Some(t) if !t.is_empty() => Some(normalize_and_validate_hex_address(t)?),
+            _ => None,
+        };
+        let decimals = match token.as_deref() {
+            None => EVM_NATIVE_DECIMALS,
+            Some(token) => {
+                let cached = decimals_cache
+                    .iter()
+                    .find(|(t, _)| t == token)
+                    .map(|(_, d)| *d);
+                let d = match cached {
+                    Some(d) => d,
+                    None => {
+                        let d = fetch_erc20_decimals(network, token).await?;
+                        decimals_cache.push((token.to_string(), d));
+                        d
+                    }
+                };
+                usize::from(d)
+            }
+        };
+        let amount = evm_tx::parse_decimal_units(item.amount.trim(), decimals)?;
+        if amount == BigUint::from(0u8) {
+            return Err(WalletError::invalid_input(format!(
+                "items[{index}]: amount must be > 0"
+            )));
+        }
+        items.push(BatchItem { to, amount, token });
+    }
+
+    let mut signer = EvmSigner::new(network, req.from.as_deref()).await?;
+    let transfers = if mode == "multicall" {
+        sign_multicall_batch(network, &mut signer, &items).await?
+    } else {
+        sign_sequential_batch(network, &mut signer, &items).await?
+    };
+
+    Ok(EvmBatchTransferResponse {
+        network: network.to_string(),
+        message: format!(
+            "{} signed transaction(s) for {} payout(s); broadcast them in order",
+            transfers.len(),
+            items.len()
+        ),
+        mode,
+        transfers,
+    })
+}
+
+async fn sign_sequential_batch(
+    network: &str,
+    signer: &mut EvmSigner,
+    items: &[BatchItem],
+) -> WalletResult<Vec<TransferResponse>> {
+    let mut out = Vec::with_capacity(items.len());
+    for item in items {
+        let prepared = match item.token.as_deref() {
+            None => {
+                signer
+                    .sign(
+                        &item.to,
+                        &item.amount,
+                        &[],
+                        &BigUint::from(EVM_NATIVE_GAS_LIMIT),
+                    )
+                    .await?
+            }
+            Some(token) => {
+                let data = evm_tx::encode_erc20_transfer_call(&item.to, &item.amount)?;
+                signer
+                    .sign(
+                        &hex_address_to_20_bytes(token)?,
+                        &BigUint::from(0u8),
+                        &data,
+                        &BigUint::from(EVM_ERC20_GAS_LIMIT_DEFAULT),
+                    )
+                    .await?
+            }
+        };
+        let what = if item.token.is_some() {
+            "signed ERC20 transfer prepared"
+        } else {
+            "signed EIP-1559 transaction prepared"
+        };
+        out.push(prepared_transfer_response(network, prepared, what));
+    }
+    Ok(out)
+}
+
+async fn sign_multicall_batch(
+    network: &str,
+    signer: &mut EvmSigner,
+    items: &[BatchItem],
+) -> WalletResult<Vec<TransferResponse>> {
+    let mut out = Vec::new();
+
+    let native: Vec<&BatchItem> = items.iter().filter(|i| i.token.is_none()).collect();
+    if !native.is_empty() {
+        let multicall = config::contract_config::multicall3_address(network)
+            .ok_or_else(|| WalletError::invalid_input("Multicall3 is not configured"))?;
+        ensure_contract_deployed(network, multicall, "Multicall3").await?;
+        let calls: Vec<evm_tx::Call3Value> = native
+            .iter()
+            .map(|i| evm_tx::Call3Value {
+                target: i.to,
+                value: i.amount.clone(),
+                call_data: Vec::new(),
+            })
+            .collect();
+        let total: BigUint = native.iter().map(|i| &i.amount).sum();
+        let data = evm_tx::encode_multicall3_aggregate3_value_call(&calls)?;
+        let gas = EVM_MULTICALL_BASE_GAS + EVM_MULTICALL_PER_CALL_GAS * native.len() as u64;
+        let prepared = signer
+            .sign(
+                &hex_address_to_20_bytes(multicall)?,
+                &total,
+                &data,
+                &BigUint::from(gas),
+            )
+            .await?;
+        out.push(prepared_transfer_response(
+            network,
+            prepared,
+            &format!(
+                "signed Multicall3 aggregate3Value for {} payout(s)",
+                native.len()
+            ),
+        ));
+    }
+
+    let mut tokens: Vec<&str> = Vec::new();
+    for token in items.iter().filter_map(|i| i.token.as_deref()) {
+        if !tokens.contains(&token) {
+            tokens.push(token);
+        }
+    }
+    if tokens.is_empty() {
+        return Ok(out);
+    }
+
+    let disperse = config::contract_config::disperse_address(network)
+        .ok_or_else(|| WalletError::invalid_input("Disperse is not configured"))?;
+    ensure_contract_deployed(network, disperse, "Disperse").await?;
+    let disperse_bytes = hex_address_to_20_bytes(disperse)?;
+    for token in tokens {
+        let group: Vec<&BatchItem> = items
+            .iter()
+            .filter(|i| i.token.as_deref() == Some(token))
+            .collect();
+        let recipients: Vec<[u8; 20]> = group.iter().map(|i| i.to).collect();
+        let values: Vec<BigUint> = group.iter().map(|i| i.amount.clone()).collect();
+        let total: BigUint = values.iter().sum();
+        let token_bytes = hex_address_to_20_bytes(token)?;
+
+        let allowance =
+            fetch_erc20_allowance(network, token, &signer.from_address, disperse).await?;
+        let approved = allowance < total;
+        if approved {
+            let data = evm_tx::encode_erc20_approve_call(&disperse_bytes, &total)?;
+            let prepared = signer
+                .sign(
+                    &token_bytes,
+                    &BigUint::from(0u8),
+                    &data,
+                    &BigUint::from(EVM_ERC20_GAS_LIMIT_DEFAULT),
+                )
+                .await?;
+            out.push(prepared_transfer_response(
+                network,
+                prepared,
+                "signed ERC20 approve for Disperse prepared",
+            ));
+        }
+
+        let data = evm_tx::encode_disperse_token_call(&token_bytes, &recipients, &values)?;
+        let gas = EVM_DISPERSE_BASE_GAS + EVM_DISPERSE_PER_TRANSFER_GAS * group.len() as u64;
+        // Only this group's disperse depends on the approve just signed; later groups simulate.
+        let simulate = signer.simulate;
+        signer.simulate = simulate && !approved;
+        let prepared = signer
+            .sign(
+                &disperse_bytes,
+                &BigUint::from(0u8),
+                &data,
+                &BigUint::from(gas),
+            )
+            .await;
+        signer.simulate = simulate;
+        let prepared = prepared?;
+        out.push(prepared_transfer_response(
+            network,
+            prepared,
+            &format!("signed disperseToken for {} payout(s)", group.len()),
+        ));
+    }
+    Ok(out)
+}
+
+pub async fn swap_quote(network: &str, req: EvmSwapRequest) -> WalletResult<EvmSwapQuoteResponse> {
+    let cfg = uniswap_v3_config(network)?;
+    let token_in = parse_swap_token(network, &cfg, &req.token_in).await?;
+    let token_out = parse_swap_token(network, &cfg, &req.token_out).await?;
+    let amount_in = parse_swap_amount(&req.amount, &token_in)?;
+    let quote = quote_uniswap_v3(network, &cfg, &token_in, &token_out, &amount_in).await?;
+    Ok(swap_quote_response(
+        network, &req, &token_in, &token_out, &amount_in, &quote,
+    ))
+}
+
+pub async fn swap(network: &str, req: EvmSwapRequest) -> WalletResult<EvmSwapResponse> {
+    let cfg = uniswap_v3_config(network)?;
+    let max_slippage_bps = req
+        .max_slippage_bps
+        .unwrap_or(EVM_SWAP_SLIPPAGE_BPS_DEFAULT);
+    if max_slippage_bps > EVM_SWAP_SLIPPAGE_BPS_MAX {
+        return Err(WalletError::invalid_input(format!(
+            "max_slippage_bps must be <= {EVM_SWAP_SLIPPAGE_BPS_MAX}"
+        )));
+    }
+    let token_in = parse_swap_token(network, &cfg, &req.token_in).await?;
+    let token_out = parse_swap_token(network, &cfg, &req.token_out).await?;
+    let amount_in = parse_swap_amount(&req.amount, &token_in)?;
+    ensure_contract_deployed(network, cfg.swap_router02, "SwapRouter02").await?;
+    let quote = quote_uniswap_v3(network, &cfg, &token_in, &token_out, &amount_in).await?;
+    let min_amount_out =
+        &quote.amount_out * BigUint::from(10_000 - max_slippage_bps) / BigUint::from(10_000u32);
+
+    let router = hex_address_to_20_bytes(cfg.swap_router02)?;
+    let mut signer = EvmSigner::new(network, req.from.as_deref()).await?;
+    let owner = hex_address_to_20_bytes(&signer.from_address)?;
+    let mut transactions = Vec::new();
+
+    if !token_in.native {
+        let token_in_hex = format!("0x{}", addressing::hex_encode(&token_in.address));
+        let allowance = fetch_erc20_allowance(
+            network,
+            &token_in_hex,
+            &signer.from_address,
+            cfg.swap_router02,
+        )
+        .await?;
+        if allowance < amount_in {
+            let data = evm_tx::encode_erc20_approve_call(&router, &amount_in)?;
+            let prepared = signer
+                .sign(
+                    &token_in.address,
+                    &BigUint::from(0u8),
+                    &data,
+                    &BigUint::from(EVM_ERC20_GAS_LIMIT_DEFAULT),
+                )
+                .await?;
+            transactions.push(prepared_transfer_response(
+                network,
+                prepared,
+                "signed ERC20 approve for SwapRouter02 prepared",
+            ));
+            signer.simulate = false;
+        }
+    }
+
+    let recipient = if token_out.native {
+        SWAP_ROUTER_ADDRESS_THIS
+    } else {
+        owner
+    };
+    let swap_call = if quote.fees.len() == 1 {
+        evm_tx::encode_swap_router02_exact_input_single_call(
+            &quote.tokens[0],
+            &quote.tokens[1],
+            quote.fees[0],
+            &recipient,
+            &amount_in,
+            &min_amount_out,
+        )?
+    } else {
+        let path = evm_tx::encode_uniswap_v3_path(&quote.tokens, &quote.fees)?;
+        evm_tx::encode_swap_router02_exact_input_call(
+            &path,
+            &recipient,
+            &amount_in,
+            &min_amount_out,
+        )?
+    };
+    let mut calls = vec![swap_call];
+    if token_out.native {
+        calls.push(evm_tx::encode_swap_router02_unwrap_weth9_call(
+            &min_amount_out,
+            &owner,
+        )?);
+    }
+    let deadline = ic_cdk::api::time() / 1_000_000_000 + EVM_SWAP_DEADLINE_SECS;
+    let data = evm_tx::encode_swap_router02_multicall_call(deadline, &calls);
+    let value = if token_in.native {
+        amount_in.clone()
+    } else {
+        BigUint::from(0u8)
+    };
+    let prepared = signer
+        .sign(
+            &router,
+            &value,
+            &data,
+            &BigUint::from(quote.gas_estimate + EVM_SWAP_GAS_OVERHEAD),
+        )
+        .await?;
+    transactions.push(prepared_transfer_response(
+        network,
+        prepared,
+        "signed Uniswap V3 swap prepared",
+    ));
+
+    let quote = swap_quote_response(network, &req, &token_in, &token_out, &amount_in, &quote);
+    Ok(EvmSwapResponse {
+        message: format!(
+            "{} signed transaction(s); broadcast them in order. expected {} out, minimum {}",
+            transactions.len(),
+            quote.amount_out,
+            evm_tx::format_units(&min_amount_out, usize::from(token_out.decimals))
+        ),
+        min_amount_out: evm_tx::format_units(&min_amount_out, usize::from(token_out.decimals)),
+        max_slippage_bps,
+        quote,
+        transactions,
+    })
+}
+
+pub async fn resolve_ens_name(network: &str, name: &str) -> WalletResult<String> {
+    let registry = config::contract_config::name_registry(network, name).ok_or_else(|| {
+        WalletError::invalid_input(format!("ENS names are not supported on {network}"))
+    })?;
+    let node = evm_tx::ens_namehash(name);
+
+    let resolver_word = eth_call(
+        registry.network,
+        registry.registry_address,
+        &evm_tx::encode_ens_resolver_call(&node),
+    )
+    .await?;
+    let resolver = evm_tx::decode_abi_address(&resolver_word)?;
+    if resolver == [0u8; 20] {
+        return Err(WalletError::invalid_input(format!(
+            "{name} is not registered or has no resolver"
+        )));
+    }
+    let resolver = format!("0x{}", addressing::hex_encode(&resolver));
+
+    let addr_word = eth_call(
+        registry.network,
+        &resolver,
+        &evm_tx::encode_ens_addr_call(&node),
+    )
+    .await?;
+    let addr = evm_tx::decode_abi_address(&addr_word)?;
+    if addr == [0u8; 20] {
+        return Err(WalletError::invalid_input(format!(
+            "{name} has no address record"
+        )));
+    }
+    Ok(format!("0x{}", addressing::hex_encode(&addr)))
+}
+
+fn uniswap_v3_config(network: &str) -> WalletResult<config::contract_config::UniswapV3Config> {
+    config::contract_config::uniswap_v3(network).ok_or_else(|| WalletError::Unimplemented {
+        network: network.to_string(),
+        operation: "Uniswap V3 swap".to_string(),
+    })
+}
+
+async fn parse_swap_token(
+    network: &str,
+    cfg: &config::contract_config::UniswapV3Config,
+    value: &str,
+) -> WalletResult<SwapToken> {
+    let value = value.trim().to_lowercase();
+    if value == "native" || value == EVM_NATIVE_TOKEN_PLACEHOLDER {
+        return Ok(SwapToken {
+            native: true,
+            address: hex_address_to_20_bytes(cfg.wrapped_native)?,
+            decimals: EVM_NATIVE_DECIMALS as u8,
+        });
+    }
+    let address = normalize_and_validate_hex_address(&value)?;
+    let decimals = fetch_erc20_decimals(network, &address).await?;
+    Ok(SwapToken {
+        native: false,
+        address: hex_address_to_20_bytes(&address)?,
+        decimals,
     })
 }
 
+fn parse_swap_amount(amount: &str, token_in: &SwapToken) -> WalletResult<BigUint> {
+    let amount_in = evm_tx::parse_decimal_units(amount.trim(), usize::from(token_in.decimals))?;
+    if amount_in == BigUint::from(0u8) {
+        return Err(WalletError::invalid_input("amount must be > 0"));
+    }
+    Ok(amount_in)
+}
+
+async fn quote_uniswap_v3(
+    network: &str,
+    cfg: &config::contract_config::UniswapV3Config,
+    token_in: &SwapToken,
+    token_out: &SwapToken,
+    amount_in: &BigUint,
+) -> WalletResult<SwapQuote> {
+    if token_in.address == token_out.address {
+        return Err(WalletError::invalid_input(
+            "token_in and token_out must differ (wrapping native tokens is not a swap)",
+        ));
+    }
+    let mut best: Option<SwapQuote> = None;
+    let mut last_err = None;
+    for fee in UNISWAP_V3_FEE_TIERS {
+        let data = evm_tx::encode_quoter_v2_quote_exact_input_single_call(
+            &token_in.address,
+            &token_out.address,
+            amount_in,
+            fee,
+        )?;
+        match quote_call(network, cfg.quoter_v2, &data).await {
+            Ok((amount_out, gas_estimate)) => {
+                if best.as_ref().is_none_or(|b| amount_out > b.amount_out) {
+                    best = Some(SwapQuote {
+                        tokens: vec![token_in.address, token_out.address],
+                        fees: vec![fee],
+                        amount_out,
+                        gas_estimate,
+                    });
+                }
+            }
+            Err(err) => last_err = Some(err),
+        }
+    }
+
+    let wrapped = hex_address_to_20_bytes(cfg.wrapped_native)?;
+    if best.is_none() && token_in.address != wrapped && token_out.address != wrapped {
+        for fee_in in UNISWAP_V3_HOP_FEE_TIERS {
+            for fee_out in UNISWAP_V3_HOP_FEE_TIERS {
+                let tokens = vec![token_in.address, wrapped, token_out.address];
+                let fees = vec![fee_in, fee_out];
+                let path = evm_tx::encode_uniswap_v3_path(&tokens, &fees)?;
+                let data = evm_tx::encode_quoter_v2_quote_exact_input_call(&path, amount_in)?;
+                match quote_call(network, cfg.quoter_v2, &data).await {
+                    Ok((amount_out, gas_estimate)) => {
+                        if best.as_ref().is_none_or(|b| amount_out > b.amount_out) {
+                            best = Some(SwapQuote {
+                                tokens,
+                                fees,
+                                amount_out,
+                                gas_estimate,
+                            });
+                        }
+                    }
+                    Err(err) => last_err = Some(err),
+                }
+            }
+        }
+    }
+
+    best.filter(|q| q.amount_out > BigUint::from(0u8))
+        .ok_or_else(|| {
+            let detail = last_err
+                .map(|e| format!(" (last error: {e:?})"))
+                .unwrap_or_default();
+            WalletError::invalid_input(format!("no Uniswap V3 route found{detail}"))
+        })
+}
+
+// QuoterV2 returns `amountOut` in the first word and `gasEstimate` in the last one for both
+// quoteExactInputSingle and quoteExactInput.
+async fn quote_call(network: &str, quoter: &str, data: &[u8]) -> WalletResult<(BigUint, u64)> {
+    let bytes = eth_call(network, quoter, data).await?;
+    if bytes.len() < 128 {
+        return Err(WalletError::Internal("QuoterV2 returned short data".into()));
+    }
+    let amount_out = BigUint::from_bytes_be(&bytes[..32]);
+    let gas_word = &bytes[96..128];
+    let gas_estimate = u64::try_from(BigUint::from_bytes_be(gas_word)).unwrap_or(u64::MAX);
+    Ok((amount_out, gas_estimate.min(5_000_000)))
+}
+
+fn swap_quote_response(
+    network: &str,
+    req: &EvmSwapRequest,
+    token_in: &SwapToken,
+    token_out: &SwapToken,
+    amount_in: &BigUint,
+    quote: &SwapQuote,
+) -> EvmSwapQuoteResponse {
+    EvmSwapQuoteResponse {
+        network: network.to_string(),
+        token_in: req.token_in.trim().to_lowercase(),
+        token_out: req.token_out.trim().to_lowercase(),
+        amount_in: evm_tx::format_units(amount_in, usize::from(token_in.decimals)),
+        amount_out: evm_tx::format_units(&quote.amount_out, usize::from(token_out.decimals)),
+        raw_amount_out: quote.amount_out.to_str_radix(10),
+        decimals_out: token_out.decimals,
+        route: quote
+            .tokens
+            .iter()
+            .map(|t| format!("0x{}", addressing::hex_encode(t)))
+            .collect(),
+        fee_tiers: quote.fees.clone(),
+        gas_estimate: quote.gas_estimate,
+    }
+}
+
+async fn prepare_erc20_approve(
+    network: &str,
+    from_override: Option<&str>,
+    token: &str,
+    spender: &str,
+    amount: &BigUint,
+) -> WalletResult<PreparedEvmBroadcast> {
+    let token_bytes = hex_address_to_20_bytes(token)?;
+    let spender_bytes = hex_address_to_20_bytes(spender)?;
+    let data = evm_tx::encode_erc20_approve_call(&spender_bytes, amount)?;
+    prepare_eip1559_transaction(
+        network,
+        from_override,
+        &token_bytes,
+        &BigUint::from(0u8),
+        &data,
+        &BigUint::from(EVM_ERC20_GAS_LIMIT_DEFAULT),
+    )
+    .await
+}
+
+fn prepared_transfer_response(
+    network: &str,
+    prepared: PreparedEvmBroadcast,
+    what: &str,
+) -> TransferResponse {
+    TransferResponse {
+        network: network.to_string(),
+        accepted: false,
+        tx_id: Some(prepared.tx_id.clone()),
+        signed_tx: Some(prepared.raw_tx_hex),
+        signed_tx_encoding: Some("hex".to_string()),
+        broadcast_request: Some(prepared.broadcast_request),
+        message: format!(
+            "{what}; frontend should broadcast via eth_sendRawTransaction: {}",
+            prepared.tx_id
+        ),
+        resolved_to: None,
+    }
+}
+
+fn allowance_response(
+    network: &str,
+    token: String,
+    owner: String,
+    spender: String,
+    raw: &BigUint,
+    decimals: Option<u8>,
+) -> Erc20AllowanceResponse {
+    let unlimited = raw == &evm_tx::max_uint256();
+    let amount = if unlimited {
+        "unlimited".to_string()
+    } else {
+        evm_tx::format_units(raw, usize::from(decimals.unwrap_or(0)))
+    };
+    Erc20AllowanceResponse {
+        network: network.to_string(),
+        token,
+        owner,
+        spender,
+        amount,
+        raw_amount: raw.to_str_radix(10),
+        decimals,
+        unlimited,
+    }
+}
+
 async fn prepare_eip1559_transaction(
     network: &str,
     from_override: Option<&str>,
@@ -223,105 +1315,326 @@ async fn prepare_eip1559_transaction(
     data: &[u8],
     gas_limit: &BigUint,
 ) -> WalletResult<PreparedEvmBroadcast> {
-    let (public_key_bytes, _key_name) = addressing::fetch_ecdsa_secp256k1_public_key().await?;
-    let from_address = evm_address_from_sec1_public_key(&public_key_bytes)?;
-    if let Some(from) = from_override {
-        let normalized_from = normalize_and_validate_hex_address(from)?;
-        if !eq_hex_address(&normalized_from, &from_address) {
-            return Err(WalletError::invalid_input(
-                "from does not match canister-managed EVM address",
-            ));
+    EvmSigner::new(network, from_override)
+        .await?
+        .sign(to_bytes, value, data, gas_limit)
+        .await
+}
+
+/// Holds the per-call signing inputs so several transactions can be signed with
+/// consecutive nonces after a single round of nonce/fee/balance lookups.
+struct EvmSigner {
+    network: String,
+    public_key_bytes: Vec<u8>,
+    from_address: String,
+    chain_id: u64,
+    nonce: BigUint,
+    max_priority_fee_per_gas: BigUint,
+    max_fee_per_gas: BigUint,
+    base_fee_per_gas: BigUint,
+    rpc_url: String,
+    balance: BigUint,
+    // Expected native spend (value + fees at the current base fee) of transactions already
+    // signed in this session; each new one must still cover its own worst case on top.
+    committed: BigUint,
+    // Cleared by callers once a later call depends on state from a not-yet-broadcast one
+    // (e.g. a swap after its approve), since `latest` cannot reflect it.
+    simulate: bool,
+}
+
+struct EvmFeeBreakdown {
+    execution_fee: BigUint,
+    expected_execution_fee: BigUint,
+    l1_data_fee: BigUint,
+}
+
+impl EvmFeeBreakdown {
+    fn new(
+        gas_limit: &BigUint,
+        max_fee_per_gas: &BigUint,
+        base_fee_per_gas: &BigUint,
+        max_priority_fee_per_gas: &BigUint,
+        l1_data_fee: BigUint,
+    ) -> Self {
+        let expected_price =
+            (base_fee_per_gas + max_priority_fee_per_gas).min(max_fee_per_gas.clone());
+        Self {
+            execution_fee: gas_limit * max_fee_per_gas,
+            expected_execution_fee: gas_limit * expected_price,
+            l1_data_fee,
         }
     }
 
-    let chain_id = config::rpc_config::chain_id(network).ok_or_else(|| {
-        WalletError::Internal(format!("missing chain_id config for network: {network}"))
-    })?;
+    /// What nodes require the sender to hold when the transaction is included.
+    fn total(&self) -> BigUint {
+        &self.execution_fee + &self.l1_data_fee
+    }
 
-    let nonce = evm_tx::parse_hex_quantity(
-        &rpc_call_hex_string(
-            network,
-            "eth_getTransactionCount",
-            json!([from_address.clone(), "pending"]),
-        )
-        .await?,
-    )?;
+    fn expected_total(&self) -> BigUint {
+        &self.expected_execution_fee + &self.l1_data_fee
+    }
+}
 
-    let (max_priority_fee_per_gas, max_fee_per_gas) = fetch_eip1559_fees(network).await?;
-
-    let signing_payload = evm_tx::rlp_encode_eip1559_unsigned(
-        chain_id,
-        &nonce,
-        &max_priority_fee_per_gas,
-        &max_fee_per_gas,
-        gas_limit,
-        to_bytes,
-        value,
-        data,
-    );
-    let signing_hash = evm_tx::keccak256(&signing_payload);
-    let signature_bytes = sign_prehash_with_management(&signing_hash).await?;
+fn max_sendable_value(available: BigUint, fees: &BigUint) -> WalletResult<BigUint> {
+    if available <= *fees {
+        return Err(WalletError::invalid_input(format!(
+            "balance {} does not cover fees {}",
+            evm_tx::format_units(&available, EVM_NATIVE_DECIMALS),
+            evm_tx::format_units(fees, EVM_NATIVE_DECIMALS)
+        )));
+    }
+    Ok(available - fees)
+}
 
-    let signature = Signature::try_from(signature_bytes.as_slice()).map_err(|err| {
-        WalletError::Internal(format!("invalid secp256k1 signature from tECDSA: {err}"))
-    })?;
-    let expected_vk = VerifyingKey::from_sec1_bytes(&public_key_bytes)
-        .map_err(|err| WalletError::Internal(format!("invalid secp256k1 public key: {err}")))?;
-    let recovery = detect_recovery_id(&signing_hash, &signature, &expected_vk)?;
-    if recovery.is_x_reduced() {
+fn parse_l1_data_fee(bytes: &[u8]) -> WalletResult<BigUint> {
+    if bytes.len() < 32 {
         return Err(WalletError::Internal(
-            "unsupported ECDSA recovery id (x_reduced=true) for Ethereum encoding".into(),
+            "GasPriceOracle getL1Fee returned short data".into(),
         ));
     }
+    Ok(BigUint::from_bytes_be(&bytes[..32]))
+}
 
-    let y_parity = if recovery.is_y_odd() { 1u8 } else { 0u8 };
-
-    let r = BigUint::from_bytes_be(&signature_bytes[..32]);
-    let s = BigUint::from_bytes_be(&signature_bytes[32..]);
-    let signed_raw = evm_tx::rlp_encode_eip1559_signed(
-        chain_id,
-        &nonce,
-        &max_priority_fee_per_gas,
-        &max_fee_per_gas,
-        gas_limit,
-        to_bytes,
-        value,
-        data,
-        y_parity,
-        &r,
-        &s,
-    );
-    let raw_tx_hex = format!("0x{}", addressing::hex_encode(&signed_raw));
-    let tx_hash = format!(
-        "0x{}",
-        addressing::hex_encode(&evm_tx::keccak256(&signed_raw))
-    );
-    let rpc_url = config::rpc_config::resolve_rpc_url(network, None)
-        .map_err(|err| WalletError::Internal(format!("rpc url resolution failed: {err}")))?;
-    let broadcast_body = serde_json::to_string(&JsonRpcRequest {
-        jsonrpc: "2.0",
-        method: "eth_sendRawTransaction".to_string(),
-        params: json!([raw_tx_hex.clone()]),
-        id: 1,
-    })
-    .map_err(|err| WalletError::Internal(format!("serialize rpc request failed: {err}")))?;
-
-    Ok(PreparedEvmBroadcast {
-        tx_id: tx_hash,
-        raw_tx_hex,
-        broadcast_request: BroadcastHttpRequest {
-            url: rpc_url,
-            method: "POST".to_string(),
-            headers: vec![
-                ("content-type".to_string(), "application/json".to_string()),
-                ("accept".to_string(), "application/json".to_string()),
-            ],
-            body: Some(broadcast_body),
-        },
-    })
+impl EvmSigner {
+    async fn new(network: &str, from_override: Option<&str>) -> WalletResult<Self> {
+        let (public_key_bytes, _key_name) = addressing::fetch_ecdsa_secp256k1_public_key().await?;
+        let from_address = evm_address_from_sec1_public_key(&public_key_bytes)?;
+        if let Some(from) = from_override {
+            let normalized_from = normalize_and_validate_hex_address(from)?;
+            if !eq_hex_address(&normalized_from, &from_address) {
+                return Err(WalletError::invalid_input(
+                    "from does not match canister-managed EVM address",
+                ));
+            }
+        }
+
+        let chain_id = config::rpc_config::chain_id(network).ok_or_else(|| {
+            WalletError::Internal(format!("missing chain_id config for network: {network}"))
+        })?;
+
+        let nonce = evm_tx::parse_hex_quantity(
+            &rpc_call_hex_string(
+                network,
+                "eth_getTransactionCount",
+                json!([from_address.clone(), "pending"]),
+            )
+            .await?,
+        )?;
+
+        let (max_priority_fee_per_gas, max_fee_per_gas, base_fee_per_gas) =
+            fetch_eip1559_fees(network).await?;
+        let rpc_url = config::rpc_config::resolve_rpc_url(network, None)
+            .map_err(|err| WalletError::Internal(format!("rpc url resolution failed: {err}")))?;
+        let balance = evm_tx::parse_hex_quantity(
+            &rpc_call_hex_string(
+                network,
+                "eth_getBalance",
+                json!([from_address.clone(), "pending"]),
+            )
+            .await?,
+        )?;
+
+        Ok(Self {
+            network: network.to_string(),
+            public_key_bytes,
+            from_address,
+            chain_id,
+            nonce,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            base_fee_per_gas,
+            rpc_url,
+            balance,
+            committed: BigUint::from(0u8),
+            simulate: true,
+        })
+    }
+
+    fn unsigned_payload(
+        &self,
+        to_bytes: &[u8; 20],
+        value: &BigUint,
+        data: &[u8],
+        gas_limit: &BigUint,
+    ) -> Vec<u8> {
+        evm_tx::rlp_encode_eip1559_unsigned(
+            self.chain_id,
+            &self.nonce,
+            &self.max_priority_fee_per_gas,
+            &self.max_fee_per_gas,
+            gas_limit,
+            to_bytes,
+            value,
+            data,
+        )
+    }
+
+    /// Worst-case fees: `gas_limit * max_fee_per_gas`, plus the L1 data fee on OP-stack networks
+    /// (the only ones with a GasPriceOracle; elsewhere no extra call is made).
+    async fn fee_breakdown(
+        &self,
+        unsigned_payload: &[u8],
+        gas_limit: &BigUint,
+    ) -> WalletResult<EvmFeeBreakdown> {
+        let l1_data_fee = match config::contract_config::op_stack_gas_price_oracle(&self.network) {
+            Some(oracle) => {
+                let data = evm_tx::encode_gas_price_oracle_get_l1_fee_call(unsigned_payload);
+                parse_l1_data_fee(&eth_call(&self.network, oracle, &data).await?)?
+            }
+            None => BigUint::from(0u8),
+        };
+        Ok(EvmFeeBreakdown::new(
+            gas_limit,
+            &self.max_fee_per_gas,
+            &self.base_fee_per_gas,
+            &self.max_priority_fee_per_gas,
+            l1_data_fee,
+        ))
+    }
+
+    fn available_balance(&self) -> BigUint {
+        if self.balance > self.committed {
+            &self.balance - &self.committed
+        } else {
+            BigUint::from(0u8)
+        }
+    }
+
+    /// Largest native value that can be sent to `to_bytes` after fees.
+    async fn max_native_value(
+        &self,
+        to_bytes: &[u8; 20],
+        gas_limit: &BigUint,
+    ) -> WalletResult<BigUint> {
+        let available = self.available_balance();
+        // Sizing the L1 fee with the full balance as value keeps the estimate an upper bound.
+        let payload = self.unsigned_payload(to_bytes, &available, &[], gas_limit);
+        let fees = self.fee_breakdown(&payload, gas_limit).await?.total();
+        max_sendable_value(available, &fees)
+    }
+
+    /// Runs the exact call through `eth_call` at `latest` and refuses to sign if it reverts.
+    async fn simulate_call(
+        &self,
+        to_bytes: &[u8; 20],
+        value: &BigUint,
+        data: &[u8],
+        gas_limit: &BigUint,
+    ) -> WalletResult<()> {
+        let params = json!([
+            {
+                "from": self.from_address,
+                "to": format!("0x{}", addressing::hex_encode(to_bytes)),
+                "value": format!("0x{}", value.to_str_radix(16)),
+                "gas": format!("0x{}", gas_limit.to_str_radix(16)),
+                "data": format!("0x{}", addressing::hex_encode(data))
+            },
+            "latest"
+        ]);
+        let rpc_body = rpc_request(
+            &self.network,
+            "eth_call",
+            params,
+            EVM_RPC_MAX_RESPONSE_BYTES,
+        )
+        .await?;
+        match rpc_body.error {
+            None => Ok(()),
+            Some(err) => Err(simulation_error(&self.network, err)),
+        }
+    }
+
+    async fn sign(
+        &mut self,
+        to_bytes: &[u8; 20],
+        value: &BigUint,
+        data: &[u8],
+        gas_limit: &BigUint,
+    ) -> WalletResult<PreparedEvmBroadcast> {
+        let signing_payload = self.unsigned_payload(to_bytes, value, data, gas_limit);
+        let fees = self.fee_breakdown(&signing_payload, gas_limit).await?;
+        let required = value + fees.total();
+        let available = self.available_balance();
+        if required > available {
+            return Err(WalletError::invalid_input(format!(
+                "insufficient native balance: need {} (value {} + fees {}, of which L1 data fee {}), available {}",
+                evm_tx::format_units(&required, EVM_NATIVE_DECIMALS),
+                evm_tx::format_units(value, EVM_NATIVE_DECIMALS),
+                evm_tx::format_units(&fees.total(), EVM_NATIVE_DECIMALS),
+                evm_tx::format_units(&fees.l1_data_fee, EVM_NATIVE_DECIMALS),
+                evm_tx::format_units(&available, EVM_NATIVE_DECIMALS)
+            )));
+        }
+        if self.simulate {
+            self.simulate_call(to_bytes, value, data, gas_limit).await?;
+        }
+
+        let signing_hash = evm_tx::keccak256(&signing_payload);
+        let signature_bytes = sign_prehash_with_management(&signing_hash).await?;
+
+        let signature = Signature::try_from(signature_bytes.as_slice()).map_err(|err| {
+            WalletError::Internal(format!("invalid secp256k1 signature from tECDSA: {err}"))
+        })?;
+        let expected_vk = VerifyingKey::from_sec1_bytes(&self.public_key_bytes)
+            .map_err(|err| WalletError::Internal(format!("invalid secp256k1 public key: {err}")))?;
+        let recovery = detect_recovery_id(&signing_hash, &signature, &expected_vk)?;
+        if recovery.is_x_reduced() {
+            return Err(WalletError::Internal(
+                "unsupported ECDSA recovery id (x_reduced=true) for Ethereum encoding".into(),
+            ));
+        }
+
+        let y_parity = if recovery.is_y_odd() { 1u8 } else { 0u8 };
+
+        let r = BigUint::from_bytes_be(&signature_bytes[..32]);
+        let s = BigUint::from_bytes_be(&signature_bytes[32..]);
+        let signed_raw = evm_tx::rlp_encode_eip1559_signed(
+            self.chain_id,
+            &self.nonce,
+            &self.max_priority_fee_per_gas,
+            &self.max_fee_per_gas,
+            gas_limit,
+            to_bytes,
+            value,
+            data,
+            y_parity,
+            &r,
+            &s,
+        );
+        self.nonce += 1u8;
+        self.committed += value + fees.expected_total();
+
+        let raw_tx_hex = format!("0x{}", addressing::hex_encode(&signed_raw));
+        let tx_hash = format!(
+            "0x{}",
+            addressing::hex_encode(&evm_tx::keccak256(&signed_raw))
+        );
+        let broadcast_body = serde_json::to_string(&JsonRpcRequest {
+            jsonrpc: "2.0",
+            method: "eth_sendRawTransaction".to_string(),
+            params: json!([raw_tx_hex.clone()]),
+            id: 1,
+        })
+        .map_err(|err| WalletError::Internal(format!("serialize rpc request failed: {err}")))?;
+
+        Ok(PreparedEvmBroadcast {
+            tx_id: tx_hash,
+            raw_tx_hex,
+            broadcast_request: BroadcastHttpRequest {
+                url: self.rpc_url.clone(),
+                method: "POST".to_string(),
+                headers: vec![
+                    ("content-type".to_string(), "application/json".to_string()),
+                    ("accept".to_string(), "application/json".to_string()),
+                ],
+                body: Some(broadcast_body),
+            },
+        })
+    }
 }
 
-async fn fetch_eip1559_fees(network: &str) -> WalletResult<(BigUint, BigUint)> {
+/// Returns `(max_priority_fee_per_gas, max_fee_per_gas, base_fee_per_gas)`.
+async fn fetch_eip1559_fees(network: &str) -> WalletResult<(BigUint, BigUint, BigUint)> {
     let priority_fee =
         match rpc_call_hex_string(network, "eth_maxPriorityFeePerGas", json!([])).await {
             Ok(v) => evm_tx::parse_hex_quantity(&v)?,
@@ -343,7 +1656,7 @@ async fn fetch_eip1559_fees(network: &str) -> WalletResult<(BigUint, BigUint)> {
     } else {
         max_fee
     };
-    Ok((priority_fee, max_fee))
+    Ok((priority_fee, max_fee, base_fee))
 }
 
 async fn fetch_latest_base_fee_per_gas(network: &str) -> WalletResult<BigUint> {
@@ -407,6 +1720,69 @@ async fn rpc_call_hex_string(network: &str, method: &str, params: Value) -> Wall
 }
 
 async fn rpc_call(network: &str, method: &str, params: Value) -> WalletResult<Value> {
+    rpc_call_with_limit(network, method, params, EVM_RPC_MAX_RESPONSE_BYTES).await
+}
+
+async fn rpc_call_with_limit(
+    network: &str,
+    method: &str,
+    params: Value,
+    max_response_bytes: u64,
+) -> WalletResult<Value> {
+    let rpc_body = rpc_request(network, method, params, max_response_bytes).await?;
+
+    if let Some(err) = rpc_body.error {
+        return Err(WalletError::Internal(format!(
+            "rpc error {}: {}",
+            err.code, err.message
+        )));
+    }
+
+    rpc_body
+        .result
+        .ok_or_else(|| WalletError::Internal("rpc response missing result".to_string()))
+}
+
+fn simulation_error(network: &str, err: JsonRpcError) -> WalletError {
+    // Nodes report revert data either directly in `error.data` or nested one level deeper.
+    let revert_hex = match &err.data {
+        Some(Value::String(hex)) => Some(hex.clone()),
+        Some(Value::Object(obj)) => obj.get("data").and_then(Value::as_str).map(str::to_string),
+        _ => None,
+    };
+    let revert_data = revert_hex
+        .as_deref()
+        .and_then(|hex| evm_tx::parse_hex_data(hex).ok());
+    if revert_data.is_none() && !err.message.to_ascii_lowercase().contains("revert") {
+        return WalletError::Internal(format!(
+            "simulation failed: rpc error {}: {}",
+            err.code, err.message
+        ));
+    }
+
+    let data = revert_data.unwrap_or_default();
+    let decoded = evm_tx::decode_revert_data(&data);
+    let reason = if decoded.selector.is_none() && err.message.len() > "execution reverted".len() {
+        err.message
+    } else {
+        decoded.reason
+    };
+    WalletError::ExecutionReverted {
+        network: network.to_string(),
+        reason,
+        selector: decoded
+            .selector
+            .map(|sel| format!("0x{}", addressing::hex_encode(&sel))),
+        data: format!("0x{}", addressing::hex_encode(&data)),
+    }
+}
+
+async fn rpc_request(
+    network: &str,
+    method: &str,
+    params: Value,
+    max_response_bytes: u64,
+) -> WalletResult<JsonRpcResponse> {
     let rpc_url = config::rpc_config::resolve_rpc_url(network, None)
         .map_err(|err| WalletError::Internal(format!("rpc url resolution failed: {err}")))?;
 
@@ -420,7 +1796,7 @@ async fn rpc_call(network: &str, method: &str, params: Value) -> WalletResult<Va
         WalletError::Internal(format!("serialize rpc request failed ({method}): {err}"))
     })?;
 
-    let http_res = crate::outcall::post_json(rpc_url, body, 32 * 1024, "evm rpc").await?;
+    let http_res = crate::outcall::post_json(rpc_url, body, max_response_bytes, "evm rpc").await?;
 
     if http_res.status != Nat::from(200u16) {
         let body_text = String::from_utf8_lossy(&http_res.body);
@@ -431,19 +1807,163 @@ async fn rpc_call(network: &str, method: &str, params: Value) -> WalletResult<Va
         )));
     }
 
-    let rpc_body: JsonRpcResponse = serde_json::from_slice(&http_res.body)
-        .map_err(|err| WalletError::Internal(format!("parse rpc response failed: {err}")))?;
+    serde_json::from_slice(&http_res.body)
+        .map_err(|err| WalletError::Internal(format!("parse rpc response failed: {err}")))
+}
+
+async fn eth_call(network: &str, to: &str, data: &[u8]) -> WalletResult<Vec<u8>> {
+    let result_hex = rpc_call_hex_string(
+        network,
+        "eth_call",
+        json!([
+            {
+                "to": to,
+                "data": format!("0x{}", addressing::hex_encode(data))
+            },
+            "latest"
+        ]),
+    )
+    .await?;
+    evm_tx::parse_hex_data(&result_hex)
+}
+
+async fn ensure_contract_deployed(network: &str, address: &str, what: &str) -> WalletResult<()> {
+    let code = rpc_call_hex_string(network, "eth_getCode", json!([address, "latest"])).await?;
+    if evm_tx::parse_hex_data(&code)?.is_empty() {
+        return Err(WalletError::invalid_input(format!(
+            "{what} is not deployed at {address} on {network}"
+        )));
+    }
+    Ok(())
+}
+
+async fn fetch_erc20_allowance(
+    network: &str,
+    token: &str,
+    owner: &str,
+    spender: &str,
+) -> WalletResult<BigUint> {
+    let data = evm_tx::encode_erc20_allowance_call(
+        &hex_address_to_20_bytes(owner)?,
+        &hex_address_to_20_bytes(spender)?,
+    );
+    let bytes = eth_call(network, token, &data).await?;
+    if bytes.len() < 32 {
+        return Err(WalletError::Internal(
+            "ERC20 allowance() returned short data".into(),
+        ));
+    }
+    Ok(BigUint::from_bytes_be(&bytes[..32]))
+}
+
+async fn managed_evm_address() -> WalletResult<String> {
+    let (public_key_bytes, _key_name) = addressing::fetch_ecdsa_secp256k1_public_key().await?;
+    evm_address_from_sec1_public_key(&public_key_bytes)
+}
+
+async fn detect_nft_standard(network: &str, contract: &str) -> WalletResult<NftStandard> {
+    if supports_interface(network, contract, ERC721_INTERFACE_ID).await? {
+        return Ok(NftStandard::Erc721);
+    }
+    if supports_interface(network, contract, ERC1155_INTERFACE_ID).await? {
+        return Ok(NftStandard::Erc1155);
+    }
+    Err(WalletError::invalid_input(
+        "contract does not report ERC721 or ERC1155 support via ERC165",
+    ))
+}
+
+async fn supports_interface(
+    network: &str,
+    contract: &str,
+    interface_id: [u8; 4],
+) -> WalletResult<bool> {
+    let data = evm_tx::encode_erc165_supports_interface_call(interface_id);
+    let rpc_body = rpc_request(
+        network,
+        "eth_call",
+        json!([
+            {
+                "to": contract,
+                "data": format!("0x{}", addressing::hex_encode(&data))
+            },
+            "latest"
+        ]),
+        EVM_RPC_MAX_RESPONSE_BYTES,
+    )
+    .await?;
+    interface_support_from_rpc(rpc_body)
+}
 
+// Only a revert or empty return means "not supported"; transport and node errors propagate so a
+// flaky RPC cannot misclassify an NFT contract.
+fn interface_support_from_rpc(rpc_body: JsonRpcResponse) -> WalletResult<bool> {
     if let Some(err) = rpc_body.error {
+        if err.code == 3 || err.message.to_ascii_lowercase().contains("revert") {
+            return Ok(false);
+        }
         return Err(WalletError::Internal(format!(
             "rpc error {}: {}",
             err.code, err.message
         )));
     }
-
-    rpc_body
+    let result_hex = rpc_body
         .result
-        .ok_or_else(|| WalletError::Internal("rpc response missing result".to_string()))
+        .as_ref()
+        .and_then(Value::as_str)
+        .ok_or_else(|| WalletError::Internal("rpc eth_call result is not string".into()))?;
+    let bytes = evm_tx::parse_hex_data(result_hex)?;
+    Ok(bytes.len() >= 32 && bytes[31] == 1 && bytes[..31].iter().all(|b| *b == 0))
+}
+
+async fn fetch_erc721_owner(
+    network: &str,
+    contract: &str,
+    token_id: &BigUint,
+) -> WalletResult<String> {
+    let data = evm_tx::encode_erc721_owner_of_call(token_id)?;
+    let bytes = eth_call(network, contract, &data).await?;
+    let owner = evm_tx::decode_abi_address(&bytes)?;
+    Ok(format!("0x{}", addressing::hex_encode(&owner)))
+}
+
+async fn fetch_erc1155_balance(
+    network: &str,
+    contract: &str,
+    account: &[u8; 20],
+    token_id: &BigUint,
+) -> WalletResult<BigUint> {
+    let data = evm_tx::encode_erc1155_balance_of_call(account, token_id)?;
+    let bytes = eth_call(network, contract, &data).await?;
+    Ok(BigUint::from_bytes_be(&bytes))
+}
+
+fn parse_nft_token_id(value: &str) -> WalletResult<(BigUint, String)> {
+    let v = value.trim();
+    let parsed = match v.strip_prefix("0x").or_else(|| v.strip_prefix("0X")) {
+        Some(hex) if !hex.is_empty() => BigUint::parse_bytes(hex.as_bytes(), 16),
+        Some(_) => None,
+        None if !v.is_empty() && v.bytes().all(|b| b.is_ascii_digit()) => {
+            BigUint::parse_bytes(v.as_bytes(), 10)
+        }
+        None => None,
+    };
+    let token_id = parsed.ok_or_else(|| {
+        WalletError::invalid_input("token_id must be a decimal or 0x-prefixed hex integer")
+    })?;
+    if token_id.bits() > 256 {
+        return Err(WalletError::invalid_input("token_id exceeds uint256"));
+    }
+    let text = token_id.to_str_radix(10);
+    Ok((token_id, text))
+}
+
+fn metadata_value<'a>(metadata: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    metadata
+        .iter()
+        .find(|(k, _)| k.trim().eq_ignore_ascii_case(key))
+        .map(|(_, v)| v.trim())
+        .filter(|v| !v.is_empty())
 }
 
 fn normalize_and_validate_hex_address(value: &str) -> WalletResult<String> {
@@ -644,4 +2164,87 @@ mod tests {
     fn rlp_encodes_zero_as_empty_string() {
         assert_eq!(evm_tx::rlp_encode_u64_for_test(0), vec![0x80]);
     }
+
+    #[test]
+    fn parses_nft_token_ids() {
+        assert_eq!(super::parse_nft_token_id("42").unwrap().1, "42");
+        assert_eq!(super::parse_nft_token_id("0x2a").unwrap().1, "42");
+        assert!(super::parse_nft_token_id("0x").is_err());
+        assert!(super::parse_nft_token_id("-1").is_err());
+        assert!(super::parse_nft_token_id(&format!("0x1{}", "0".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn fee_breakdown_separates_worst_case_from_expected_cost() {
+        let gwei = |n: u64| BigUint::from(n) * BigUint::from(1_000_000_000u64);
+        let gas = BigUint::from(21_000u32);
+        // max fee = 2 * base + tip, as built by fetch_eip1559_fees.
+        let fees = super::EvmFeeBreakdown::new(&gas, &gwei(21), &gwei(10), &gwei(1), gwei(0));
+        assert_eq!(fees.total(), &gas * gwei(21));
+        assert_eq!(fees.expected_total(), &gas * gwei(11));
+        // The OP-stack L1 data fee is added to both.
+        let l1 = BigUint::from(12_345u32);
+        let op = super::EvmFeeBreakdown::new(&gas, &gwei(21), &gwei(10), &gwei(1), l1.clone());
+        assert_eq!(op.total(), &gas * gwei(21) + &l1);
+        assert_eq!(op.expected_total(), &gas * gwei(11) + &l1);
+        // Fallback pricing (no base fee) never expects more than the cap.
+        let capped = super::EvmFeeBreakdown::new(&gas, &gwei(3), &gwei(5), &gwei(3), gwei(0));
+        assert_eq!(capped.expected_total(), capped.total());
+    }
+
+    #[test]
+    fn max_send_leaves_exactly_the_fees() {
+        let max = super::max_sendable_value(BigUint::from(1_000u32), &BigUint::from(300u32));
+        assert_eq!(max.unwrap(), BigUint::from(700u32));
+        assert!(super::max_sendable_value(BigUint::from(300u32), &BigUint::from(300u32)).is_err());
+        assert!(super::max_sendable_value(BigUint::from(0u32), &BigUint::from(1u32)).is_err());
+    }
+
+    #[test]
+    fn parses_l1_data_fee_word() {
+        let mut word = vec![0u8; 32];
+        word[30] = 0x01;
+        word[31] = 0x02;
+        assert_eq!(
+            super::parse_l1_data_fee(&word).unwrap(),
+            BigUint::from(0x0102u32)
+        );
+        assert!(super::parse_l1_data_fee(&word[..31]).is_err());
+    }
+
+    #[test]
+    fn approval_scan_pages_backwards_in_chunks() {
+        assert_eq!(
+            super::approval_scan_ranges(0, 250, 100, 10),
+            vec![(151, 250), (51, 150), (0, 50)]
+        );
+        assert_eq!(
+            super::approval_scan_ranges(120, 250, 100, 10),
+            vec![(151, 250), (120, 150)]
+        );
+        // Stops at the chunk budget; the caller reports complete=false and resumes below 51.
+        assert_eq!(
+            super::approval_scan_ranges(0, 250, 100, 2),
+            vec![(151, 250), (51, 150)]
+        );
+        assert_eq!(super::approval_scan_ranges(7, 7, 100, 2), vec![(7, 7)]);
+    }
+
+    #[test]
+    fn erc165_support_only_defaults_to_false_on_revert_or_empty_result() {
+        let rpc = |body: &str| serde_json::from_str::<super::JsonRpcResponse>(body).unwrap();
+        let yes = format!(r#"{{"result":"0x{}01"}}"#, "0".repeat(62));
+        let no = format!(r#"{{"result":"0x{}"}}"#, "0".repeat(64));
+        assert!(super::interface_support_from_rpc(rpc(&yes)).unwrap());
+        assert!(!super::interface_support_from_rpc(rpc(&no)).unwrap());
+        assert!(!super::interface_support_from_rpc(rpc(r#"{"result":"0x"}"#)).unwrap());
+        assert!(!super::interface_support_from_rpc(rpc(
+            r#"{"error":{"code":3,"message":"execution reverted"}}"#
+        ))
+        .unwrap());
+        assert!(super::interface_support_from_rpc(rpc(
+            r#"{"error":{"code":-32005,"message":"rate limited"}}"#
+        ))
+        .is_err());
+    }
 }
@@ -3,15 +3,19 @@ use ic_cdk::call::Call;
 use num_bigint::BigUint;
 use serde::Deserialize;
 
+use crate::addressing;
+use crate::chains::bitcoin;
 use crate::config;
 use crate::error::{WalletError, WalletResult};
 use crate::types::{
-    self, AddressResponse, BalanceRequest, BalanceResponse, ConfiguredTokenResponse,
-    TransferRequest, TransferResponse,
+    self, AddressResponse, BalanceRequest, BalanceResponse, CkbtcUpdateBalanceResponse,
+    CkbtcUtxoStatus, ConfiguredTokenResponse, TransferRequest, TransferResponse,
 };
 
 const NETWORK_NAME: &str = types::networks::INTERNET_COMPUTER;
 const ICP_DECIMALS: u8 = 8;
+const CKBTC_DECIMALS: u8 = 8;
+const CKBTC_APPROVAL_TTL_NS: u64 = 10 * 60 * 1_000_000_000;
 
 pub async fn request_address() -> WalletResult<AddressResponse> {
     let principal = current_canister_principal();
@@ -55,6 +59,114 @@ enum IcrcTransferError {
     GenericError { error_code: Nat, message: String },
 }
 
+#[derive(CandidType, Deserialize, Clone, Debug)]
+struct IcrcApproveArg {
+    from_subaccount: Option<Vec<u8>>,
+    spender: IcrcAccount,
+    amount: Nat,
+    expected_allowance: Option<Nat>,
+    expires_at: Option<u64>,
+    fee: Option<Nat>,
+    memo: Option<Vec<u8>>,
+    created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+enum IcrcApproveError {
+    BadFee { expected_fee: Nat },
+    InsufficientFunds { balance: Nat },
+    AllowanceChanged { current_allowance: Nat },
+    Expired { ledger_time: u64 },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    Duplicate { duplicate_of: Nat },
+    TemporarilyUnavailable,
+    GenericError { error_code: Nat, message: String },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+struct CkbtcMinterAccountArg {
+    owner: Option<Principal>,
+    subaccount: Option<Vec<u8>>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+struct CkbtcOutpoint {
+    txid: Vec<u8>,
+    vout: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+struct CkbtcUtxo {
+    outpoint: CkbtcOutpoint,
+    value: u64,
+    height: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+struct CkbtcPendingUtxo {
+    outpoint: CkbtcOutpoint,
+    value: u64,
+    confirmations: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+enum CkbtcMinterUtxoStatus {
+    ValueTooSmall(CkbtcUtxo),
+    Tainted(CkbtcUtxo),
+    Checked(CkbtcUtxo),
+    Minted {
+        block_index: u64,
+        minted_amount: u64,
+        utxo: CkbtcUtxo,
+    },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+enum CkbtcUpdateBalanceError {
+    GenericError {
+        error_code: u64,
+        error_message: String,
+    },
+    TemporarilyUnavailable(String),
+    AlreadyProcessing,
+    NoNewUtxos {
+        required_confirmations: u32,
+        pending_utxos: Option<Vec<CkbtcPendingUtxo>>,
+        current_confirmations: Option<u32>,
+    },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+struct CkbtcRetrieveBtcWithApprovalArg {
+    address: String,
+    amount: u64,
+    from_subaccount: Option<Vec<u8>>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+struct CkbtcRetrieveBtcOk {
+    block_index: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+enum CkbtcRetrieveBtcWithApprovalError {
+    MalformedAddress(String),
+    GenericError {
+        error_code: u64,
+        error_message: String,
+    },
+    TemporarilyUnavailable(String),
+    InsufficientAllowance {
+        allowance: u64,
+    },
+    AlreadyProcessing,
+    AmountTooLow(u64),
+    InsufficientFunds {
+        balance: u64,
+    },
+}
+
 pub async fn get_balance_icp(req: BalanceRequest) -> WalletResult<BalanceResponse> {
     validate_account_text(&req.account)?;
     if non_empty_opt_str(req.token.as_deref()).is_some() {
@@ -136,6 +248,7 @@ pub async fn transfer_icp(req: TransferRequest) -> WalletResult<TransferResponse
         signed_tx_encoding: None,
         broadcast_request: None,
         message: "icrc1_transfer on ICP ledger accepted".to_string(),
+        resolved_to: None,
     })
 }
 
@@ -171,6 +284,7 @@ pub async fn transfer_icrc(req: TransferRequest) -> WalletResult<TransferRespons
         signed_tx_encoding: None,
         broadcast_request: None,
         message: "icrc1_transfer on token ledger accepted".to_string(),
+        resolved_to: None,
     })
 }
 
@@ -188,6 +302,180 @@ pub async fn discover_icrc_token(ledger_text: &str) -> WalletResult<ConfiguredTo
     })
 }
 
+/// BTC deposit address the ckBTC minter watches for this canister's ckBTC account.
+pub async fn ckbtc_get_btc_address() -> WalletResult<AddressResponse> {
+    let minter = config::app_config::ckbtc_minter_principal();
+    let res = Call::unbounded_wait(minter, "get_btc_address")
+        .with_arg(ckbtc_minter_account())
+        .await
+        .map_err(|err| WalletError::Internal(format!("get_btc_address call failed: {err:?}")))?;
+    let (address,): (String,) = res
+        .candid_tuple()
+        .map_err(|err| WalletError::Internal(format!("get_btc_address decode failed: {err:?}")))?;
+    Ok(AddressResponse {
+        network: types::networks::BITCOIN.to_string(),
+        address,
+        public_key_hex: String::new(),
+        key_name: "ckbtc_minter".to_string(),
+        message: Some(
+            "send BTC here, then call ckbtc_update_balance to mint ckBTC to the canister principal"
+                .to_string(),
+        ),
+    })
+}
+
+/// Asks the minter to mint ckBTC for confirmed deposits to `ckbtc_get_btc_address`.
+pub async fn ckbtc_update_balance() -> WalletResult<CkbtcUpdateBalanceResponse> {
+    let minter = config::app_config::ckbtc_minter_principal();
+    let res = Call::unbounded_wait(minter, "update_balance")
+        .with_arg(ckbtc_minter_account())
+        .await
+        .map_err(|err| WalletError::Internal(format!("update_balance call failed: {err:?}")))?;
+    let (result,): (Result<Vec<CkbtcMinterUtxoStatus>, CkbtcUpdateBalanceError>,) = res
+        .candid_tuple()
+        .map_err(|err| WalletError::Internal(format!("update_balance decode failed: {err:?}")))?;
+    let statuses = result.map_err(|err| match err {
+        CkbtcUpdateBalanceError::NoNewUtxos {
+            required_confirmations,
+            pending_utxos,
+            current_confirmations,
+        } => WalletError::invalid_input(format!(
+            "no new BTC deposits to mint: {} pending UTXO(s), {}/{required_confirmations} confirmations",
+            pending_utxos.map_or(0, |p| p.len()),
+            current_confirmations.unwrap_or(0)
+        )),
+        other => WalletError::Internal(format!("update_balance rejected: {other:?}")),
+    })?;
+
+    let utxos = statuses
+        .into_iter()
+        .map(ckbtc_utxo_status_record)
+        .collect::<Vec<_>>();
+    let minted_sats = utxos.iter().filter_map(|u| u.minted_sats).sum::<u64>();
+    Ok(CkbtcUpdateBalanceResponse {
+        minted_sats,
+        message: format!(
+            "minted {} ckBTC from {} UTXO(s)",
+            format_nat_units(&Nat::from(minted_sats), CKBTC_DECIMALS),
+            utxos.len()
+        ),
+        utxos,
+    })
+}
+
+/// Redeems ckBTC for native BTC: approves the minter on the ckBTC ledger (ICRC-2) for amount
+/// plus the ledger fee, then calls `retrieve_btc_with_approval`. An empty `address` withdraws to
+/// the canister-managed Taproot address.
+pub async fn ckbtc_retrieve_btc_with_approval(
+    address: &str,
+    amount: &str,
+) -> WalletResult<TransferResponse> {
+    let address = match non_empty_opt_str(Some(address)) {
+        Some(address) => address.to_string(),
+        None => bitcoin::request_address().await?.address,
+    };
+    let amount = parse_decimal_nat_units(amount.trim(), CKBTC_DECIMALS)?;
+    let amount_sats =
+        u64::try_from(&amount.0).map_err(|_| WalletError::invalid_input("amount is too large"))?;
+    if amount_sats == 0 {
+        return Err(WalletError::invalid_input("amount must be > 0"));
+    }
+
+    let ledger = config::app_config::ckbtc_ledger_principal();
+    let minter = config::app_config::ckbtc_minter_principal();
+    let fee = fetch_icrc_fee(ledger).await?;
+    let approve_block = icrc2_approve(
+        ledger,
+        IcrcApproveArg {
+            from_subaccount: None,
+            spender: IcrcAccount {
+                owner: minter,
+                subaccount: None,
+            },
+            amount: amount.clone() + fee,
+            expected_allowance: None,
+            expires_at: Some(ic_cdk::api::time() + CKBTC_APPROVAL_TTL_NS),
+            fee: None,
+            memo: None,
+            created_at_time: None,
+        },
+    )
+    .await?;
+
+    let res = Call::unbounded_wait(minter, "retrieve_btc_with_approval")
+        .with_arg(CkbtcRetrieveBtcWithApprovalArg {
+            address: address.clone(),
+            amount: amount_sats,
+            from_subaccount: None,
+        })
+        .await
+        .map_err(|err| {
+            WalletError::Internal(format!("retrieve_btc_with_approval call failed: {err:?}"))
+        })?;
+    let (result,): (Result<CkbtcRetrieveBtcOk, CkbtcRetrieveBtcWithApprovalError>,) =
+        res.candid_tuple().map_err(|err| {
+            WalletError::Internal(format!("retrieve_btc_with_approval decode failed: {err:?}"))
+        })?;
+    let ok = result.map_err(|err| match err {
+        CkbtcRetrieveBtcWithApprovalError::MalformedAddress(msg) => {
+            WalletError::invalid_input(format!("malformed BTC address: {msg}"))
+        }
+        CkbtcRetrieveBtcWithApprovalError::AmountTooLow(min) => {
+            WalletError::invalid_input(format!(
+                "amount is below the minter minimum of {} BTC",
+                format_nat_units(&Nat::from(min), CKBTC_DECIMALS)
+            ))
+        }
+        other => WalletError::Internal(format!("retrieve_btc_with_approval rejected: {other:?}")),
+    })?;
+
+    Ok(TransferResponse {
+        network: NETWORK_NAME.to_string(),
+        accepted: true,
+        tx_id: Some(ok.block_index.to_string()),
+        signed_tx: None,
+        signed_tx_encoding: None,
+        broadcast_request: None,
+        message: format!(
+            "ckBTC burned at block {} (approval block {approve_block}); the minter will send {} BTC to {address}",
+            ok.block_index,
+            format_nat_units(&amount, CKBTC_DECIMALS)
+        ),
+        resolved_to: Some(address),
+    })
+}
+
+fn ckbtc_minter_account() -> CkbtcMinterAccountArg {
+    CkbtcMinterAccountArg {
+        owner: Some(current_canister_principal()),
+        subaccount: None,
+    }
+}
+
+fn ckbtc_utxo_status_record(status: CkbtcMinterUtxoStatus) -> CkbtcUtxoStatus {
+    let (label, utxo, minted) = match status {
+        CkbtcMinterUtxoStatus::ValueTooSmall(utxo) => ("value_too_small", utxo, None),
+        CkbtcMinterUtxoStatus::Tainted(utxo) => ("tainted", utxo, None),
+        CkbtcMinterUtxoStatus::Checked(utxo) => ("checked", utxo, None),
+        CkbtcMinterUtxoStatus::Minted {
+            block_index,
+            minted_amount,
+            utxo,
+        } => ("minted", utxo, Some((minted_amount, block_index))),
+    };
+    // The minter reports txids in internal byte order, like the Bitcoin canister.
+    let mut txid = utxo.outpoint.txid;
+    txid.reverse();
+    CkbtcUtxoStatus {
+        status: label.to_string(),
+        txid: addressing::hex_encode(&txid),
+        vout: utxo.outpoint.vout,
+        value_sats: utxo.value,
+        minted_sats: minted.map(|(amount, _)| amount),
+        mint_block_index: minted.map(|(_, block)| block),
+    }
+}
+
 fn icp_ledger_principal() -> Principal {
     if config::app_config::default_icp_ledger_use_mainnet() {
         config::app_config::icp_ledger_mainnet_principal()
@@ -301,6 +589,27 @@ async fn fetch_icrc_name(ledger: Principal) -> WalletResult<String> {
     Ok(value)
 }
 
+async fn fetch_icrc_fee(ledger: Principal) -> WalletResult<Nat> {
+    let res = Call::bounded_wait(ledger, "icrc1_fee")
+        .await
+        .map_err(|err| WalletError::Internal(format!("icrc1_fee failed: {err:?}")))?;
+    let (value,): (Nat,) = res
+        .candid_tuple()
+        .map_err(|err| WalletError::Internal(format!("icrc1_fee decode failed: {err:?}")))?;
+    Ok(value)
+}
+
+async fn icrc2_approve(ledger: Principal, arg: IcrcApproveArg) -> WalletResult<Nat> {
+    let res = Call::unbounded_wait(ledger, "icrc2_approve")
+        .with_arg(arg)
+        .await
+        .map_err(|err| WalletError::Internal(format!("icrc2_approve call failed: {err:?}")))?;
+    let (result,): (Result<Nat, IcrcApproveError>,) = res
+        .candid_tuple()
+        .map_err(|err| WalletError::Internal(format!("icrc2_approve decode failed: {err:?}")))?;
+    result.map_err(|err| WalletError::Internal(format!("icrc2_approve rejected: {err:?}")))
+}
+
 async fn icrc1_transfer(ledger: Principal, arg: IcrcTransferArg) -> WalletResult<Nat> {
     let res = Call::unbounded_wait(ledger, "icrc1_transfer")
         .with_arg(arg)
@@ -443,4 +752,29 @@ mod tests {
             "100000010000"
         );
     }
+
+    #[test]
+    fn maps_ckbtc_minter_utxo_statuses() {
+        let mut txid = vec![0u8; 32];
+        txid[0] = 0xaa;
+        let utxo = CkbtcUtxo {
+            outpoint: CkbtcOutpoint { txid, vout: 3 },
+            value: 50_000,
+            height: 800_000,
+        };
+        let minted = ckbtc_utxo_status_record(CkbtcMinterUtxoStatus::Minted {
+            block_index: 42,
+            minted_amount: 49_990,
+            utxo: utxo.clone(),
+        });
+        assert_eq!(minted.status, "minted");
+        assert!(minted.txid.ends_with("aa"));
+        assert_eq!(minted.vout, 3);
+        assert_eq!(minted.minted_sats, Some(49_990));
+        assert_eq!(minted.mint_block_index, Some(42));
+
+        let small = ckbtc_utxo_status_record(CkbtcMinterUtxoStatus::ValueTooSmall(utxo));
+        assert_eq!(small.status, "value_too_small");
+        assert_eq!(small.minted_sats, None);
+    }
 }
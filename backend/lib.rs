@@ -5,6 +5,7 @@ mod chains;
 mod config;
 mod error;
 mod evm_rpc;
+mod name_service;
 mod outcall;
 mod sdk;
 mod state;
@@ -18,10 +19,17 @@ use candid::Principal;
 use error::WalletResult;
 #[allow(unused_imports)]
 use types::{
-    AddConfiguredTokenRequest, AddressResponse, BalanceRequest, BalanceResponse,
-    BroadcastHttpRequest, ConfiguredExplorerResponse, ConfiguredRpcResponse,
-    ConfiguredTokenResponse, NetworkModuleStatus, RemoveConfiguredRpcRequest,
-    RemoveConfiguredTokenRequest, ServiceInfoResponse, SetConfiguredRpcRequest, TransferRequest,
+    AddConfiguredNftCollectionRequest, AddConfiguredTokenRequest, AddEvmNetworkRequest,
+    AddressResponse, BalanceRequest, BalanceResponse, BitcoinDataSource, BitcoinPsbtResponse,
+    BitcoinSentTxRecord, BroadcastHttpRequest, CkbtcUpdateBalanceResponse,
+    ConfiguredExplorerResponse, ConfiguredNftCollectionResponse, ConfiguredRpcResponse,
+    ConfiguredTokenResponse, CustomEvmNetworkResponse, Erc20AllowanceRequest,
+    Erc20AllowanceResponse, Erc20ApprovalsRequest, Erc20ApprovalsResponse, EvmBatchTransferRequest,
+    EvmBatchTransferResponse, EvmFeeEstimateResponse, EvmSwapQuoteResponse, EvmSwapRequest,
+    EvmSwapResponse, NameResolutionRecord, NetworkModuleStatus, NftOwnershipRequest,
+    NftOwnershipResponse, RemoveConfiguredNftCollectionRequest, RemoveConfiguredRpcRequest,
+    RemoveConfiguredTokenRequest, ServiceInfoResponse, SetConfiguredRpcRequest,
+    SolanaCloseTokenAccountsResponse, SolanaNonceAccountResponse, TransferItem, TransferRequest,
     TransferResponse, WalletNetworkInfoResponse,
 };
 
@@ -0,0 +1,173 @@
+use crate::chains::{near_mainnet, solana, ton_mainnet};
+use crate::config;
+use crate::error::{WalletError, WalletResult};
+use crate::evm_rpc;
+use crate::state;
+use crate::types::{
+    networks, EvmBatchTransferResponse, NameResolutionRecord, TransferRequest, TransferResponse,
+};
+
+/// Resolves `to` when it is a name-service name for `network` (`.eth` on EVM networks, `.sol`,
+/// `.ton`, `.near`). Returns `None` when `to` should be treated as a plain address.
+pub async fn resolve_recipient(
+    network: &str,
+    to: &str,
+) -> WalletResult<Option<NameResolutionRecord>> {
+    let network = config::rpc_config::normalize_network(network);
+    let name = to.trim().to_lowercase();
+    let address = if config::rpc_config::is_evm_network(&network) {
+        if !name.ends_with(".eth") {
+            return Ok(None);
+        }
+        validate_ens_name(&name)?;
+        evm_rpc::resolve_ens_name(&network, &name).await?
+    } else {
+        match network.as_str() {
+            networks::SOLANA
+            | networks::SOLANA_TESTNET
+            | networks::SOLANA_DEVNET
+            | networks::SOLANA_LOCALNET
+                if name.ends_with(".sol") =>
+            {
+                solana::resolve_sns_name(&network, &name).await?
+            }
+            networks::TON_MAINNET if name.ends_with(".ton") => {
+                ton_mainnet::resolve_dns_name(&name).await?
+            }
+            networks::NEAR_MAINNET if name.ends_with(".near") => {
+                near_mainnet::resolve_account_name(&name).await?
+            }
+            _ => return Ok(None),
+        }
+    };
+
+    let record = NameResolutionRecord {
+        network,
+        name,
+        address,
+        resolved_at_ns: ic_cdk::api::time(),
+    };
+    state::record_name_resolution(record.clone());
+    Ok(Some(record))
+}
+
+/// Rewrites `req.to` to the resolved address; also returns the address to echo in the response.
+pub async fn resolve_transfer_recipient(
+    network: &str,
+    mut req: TransferRequest,
+) -> WalletResult<(TransferRequest, Option<String>)> {
+    match resolve_recipient(network, &req.to).await? {
+        Some(record) => {
+            req.to = record.address.clone();
+            Ok((req, Some(record.address)))
+        }
+        None => Ok((req, None)),
+    }
+}
+
+pub fn with_resolved_recipient(
+    result: WalletResult<TransferResponse>,
+    resolved_to: Option<String>,
+) -> WalletResult<TransferResponse> {
+    result.map(|mut resp| {
+        resp.resolved_to = resolved_to;
+        resp
+    })
+}
+
+/// Echoes batch recipients resolved from names (`(name, address)` per item, in item order).
+/// Sequential batches carry one transaction per item; a multicall transaction covers several
+/// items, so its resolutions are listed in the message instead.
+pub fn with_resolved_batch_recipients(
+    result: WalletResult<EvmBatchTransferResponse>,
+    resolved: Vec<Option<(String, String)>>,
+) -> WalletResult<EvmBatchTransferResponse> {
+    result.map(|mut resp| {
+        if resp.mode == "sequential" {
+            for (transfer, resolved) in resp.transfers.iter_mut().zip(resolved) {
+                transfer.resolved_to = resolved.map(|(_, address)| address);
+            }
+        } else {
+            let pairs: Vec<String> = resolved
+                .into_iter()
+                .flatten()
+                .map(|(name, address)| format!("{name} -> {address}"))
+                .collect();
+            if !pairs.is_empty() {
+                resp.message = format!("{}; resolved {}", resp.message, pairs.join(", "));
+            }
+        }
+        resp
+    })
+}
+
+// Full ENSIP-15 normalization is out of scope; accept lowercase ASCII labels only.
+fn validate_ens_name(name: &str) -> WalletResult<()> {
+    let valid = name.split('.').all(|label| {
+        !label.is_empty()
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_')
+    });
+    if !valid {
+        return Err(WalletError::invalid_input(format!(
+            "unsupported ENS name: {name}; only ASCII labels are supported"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch_response(mode: &str, transfers: usize) -> EvmBatchTransferResponse {
+        EvmBatchTransferResponse {
+            network: networks::ETHEREUM.to_string(),
+            mode: mode.to_string(),
+            transfers: (0..transfers)
+                .map(|_| TransferResponse {
+                    network: networks::ETHEREUM.to_string(),
+                    accepted: false,
+                    tx_id: None,
+                    signed_tx: None,
+                    signed_tx_encoding: None,
+                    broadcast_request: None,
+                    message: String::new(),
+                    resolved_to: None,
+                })
+                .collect(),
+            message: "signed".to_string(),
+        }
+    }
+
+    fn resolved() -> Vec<Option<(String, String)>> {
+        vec![
+            Some(("alice.eth".to_string(), "0xaaaa".to_string())),
+            None,
+            Some(("bob.eth".to_string(), "0xbbbb".to_string())),
+        ]
+    }
+
+    #[test]
+    fn echoes_resolved_batch_recipients() {
+        let sequential =
+            with_resolved_batch_recipients(Ok(batch_response("sequential", 3)), resolved())
+                .unwrap();
+        let echoed: Vec<Option<&str>> = sequential
+            .transfers
+            .iter()
+            .map(|t| t.resolved_to.as_deref())
+            .collect();
+        assert_eq!(echoed, [Some("0xaaaa"), None, Some("0xbbbb")]);
+        assert_eq!(sequential.message, "signed");
+
+        let multicall =
+            with_resolved_batch_recipients(Ok(batch_response("multicall", 1)), resolved()).unwrap();
+        assert_eq!(multicall.transfers[0].resolved_to, None);
+        assert_eq!(
+            multicall.message,
+            "signed; resolved alice.eth -> 0xaaaa, bob.eth -> 0xbbbb"
+        );
+    }
+}
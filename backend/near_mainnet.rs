@@ -259,6 +259,7 @@ pub async fn transfer(req: TransferRequest) -> WalletResult<TransferResponse> {
         message: tx_id
             .map(|h| format!("signed NEAR transaction prepared; frontend should broadcast via broadcast_tx_commit: {h}"))
             .unwrap_or_else(|| "signed NEAR transaction prepared; frontend should broadcast via broadcast_tx_commit".to_string()),
+        resolved_to: None,
     })
 }
 
@@ -306,6 +307,30 @@ struct NearManagedIdentity {
     public_key32: [u8; 32],
 }
 
+pub async fn resolve_account_name(name: &str) -> WalletResult<String> {
+    if !is_valid_named_account(name) {
+        return Err(WalletError::invalid_input(format!(
+            "invalid NEAR account name: {name}"
+        )));
+    }
+    match near_rpc_call(
+        "query",
+        json!({
+            "request_type": "view_account",
+            "finality": "final",
+            "account_id": name
+        }),
+    )
+    .await
+    {
+        Ok(_) => Ok(name.to_string()),
+        Err(err) if is_near_unknown_account_error(&err) => Err(WalletError::invalid_input(
+            format!("NEAR account {name} does not exist"),
+        )),
+        Err(err) => Err(err),
+    }
+}
+
 async fn fetch_managed_near_identity() -> WalletResult<NearManagedIdentity> {
     let (pubkey, _key_name) =
         addressing::fetch_schnorr_public_key(SchnorrAlgorithm::Ed25519).await?;
@@ -598,6 +623,22 @@ fn biguint_to_u128(v: &BigUint) -> WalletResult<u128> {
     Ok(u128::from_le_bytes(arr))
 }
 
+// NEAR account ID rules: 2..=64 chars, parts of [a-z0-9] joined by single '-', '_' or '.'.
+fn is_valid_named_account(name: &str) -> bool {
+    if !(2..=64).contains(&name.len()) {
+        return false;
+    }
+    let mut prev_separator = true;
+    for b in name.bytes() {
+        match b {
+            b'a'..=b'z' | b'0'..=b'9' => prev_separator = false,
+            b'-' | b'_' | b'.' if !prev_separator => prev_separator = true,
+            _ => return false,
+        }
+    }
+    !prev_separator
+}
+
 fn validate_account(account: &str) -> WalletResult<()> {
     if account.trim().is_empty() {
         return Err(WalletError::invalid_input("account is required"));
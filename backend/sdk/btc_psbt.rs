@@ -0,0 +1,348 @@
+use crate::error::{WalletError, WalletResult};
+use crate::sdk::ton_tx::{base64_decode_url_or_std, base64_encode_std};
+
+const PSBT_MAGIC: &[u8; 5] = b"psbt\xff";
+
+pub const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+pub const PSBT_IN_WITNESS_UTXO: u8 = 0x01;
+pub const PSBT_IN_PARTIAL_SIG: u8 = 0x02;
+pub const PSBT_IN_SIGHASH_TYPE: u8 = 0x03;
+pub const PSBT_IN_TAP_KEY_SIG: u8 = 0x13;
+pub const PSBT_IN_TAP_INTERNAL_KEY: u8 = 0x17;
+pub const PSBT_OUT_TAP_INTERNAL_KEY: u8 = 0x05;
+
+/// Key-value pairs of one PSBT map; keys keep their type byte. Unknown entries are preserved
+/// so a PSBT round-trips through the canister untouched apart from what we add.
+pub type PsbtMap = Vec<(Vec<u8>, Vec<u8>)>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Psbt {
+    pub global: PsbtMap,
+    pub inputs: Vec<PsbtMap>,
+    pub outputs: Vec<PsbtMap>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsignedTxInput {
+    /// Previous txid in serialized (little-endian) byte order.
+    pub txid_le: [u8; 32],
+    pub vout: u32,
+    pub sequence: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsignedTxOutput {
+    pub value: u64,
+    pub script_pubkey: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsignedTx {
+    pub version: u32,
+    pub inputs: Vec<UnsignedTxInput>,
+    pub outputs: Vec<UnsignedTxOutput>,
+    pub lock_time: u32,
+}
+
+impl Psbt {
+    pub fn new(unsigned_tx: Vec<u8>, input_count: usize, output_count: usize) -> Self {
+        Self {
+            global: vec![(vec![PSBT_GLOBAL_UNSIGNED_TX], unsigned_tx)],
+            inputs: vec![Vec::new(); input_count],
+            outputs: vec![Vec::new(); output_count],
+        }
+    }
+
+    pub fn unsigned_tx(&self) -> WalletResult<UnsignedTx> {
+        let raw = map_get(&self.global, PSBT_GLOBAL_UNSIGNED_TX).ok_or_else(|| {
+            WalletError::invalid_input("PSBT is missing the unsigned transaction")
+        })?;
+        parse_unsigned_tx(raw)
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = PSBT_MAGIC.to_vec();
+        serialize_map_into(&self.global, &mut out);
+        for map in self.inputs.iter().chain(self.outputs.iter()) {
+            serialize_map_into(map, &mut out);
+        }
+        out
+    }
+
+    pub fn parse(bytes: &[u8]) -> WalletResult<Self> {
+        let rest = bytes
+            .strip_prefix(PSBT_MAGIC.as_slice())
+            .ok_or_else(|| WalletError::invalid_input("not a PSBT (bad magic)"))?;
+        let mut reader = Reader::new(rest);
+        let global = reader.map()?;
+        let tx_raw = map_get(&global, PSBT_GLOBAL_UNSIGNED_TX).ok_or_else(|| {
+            WalletError::invalid_input("PSBT is missing the unsigned transaction")
+        })?;
+        let tx = parse_unsigned_tx(tx_raw)?;
+        let inputs = (0..tx.inputs.len())
+            .map(|_| reader.map())
+            .collect::<WalletResult<Vec<_>>>()?;
+        let outputs = (0..tx.outputs.len())
+            .map(|_| reader.map())
+            .collect::<WalletResult<Vec<_>>>()?;
+        if !reader.is_empty() {
+            return Err(WalletError::invalid_input("PSBT has trailing bytes"));
+        }
+        Ok(Self {
+            global,
+            inputs,
+            outputs,
+        })
+    }
+
+    pub fn to_base64(&self) -> String {
+        base64_encode_std(&self.serialize())
+    }
+
+    pub fn from_base64(text: &str) -> WalletResult<Self> {
+        Self::parse(&base64_decode_url_or_std(text)?)
+    }
+}
+
+/// Value of a key that carries no key data (`key == [key_type]`).
+pub fn map_get(map: &PsbtMap, key_type: u8) -> Option<&[u8]> {
+    map.iter()
+        .find(|(key, _)| key.as_slice() == [key_type])
+        .map(|(_, value)| value.as_slice())
+}
+
+pub fn map_set(map: &mut PsbtMap, key_type: u8, value: Vec<u8>) {
+    match map.iter_mut().find(|(key, _)| key.as_slice() == [key_type]) {
+        Some(entry) => entry.1 = value,
+        None => map.push((vec![key_type], value)),
+    }
+}
+
+pub fn encode_witness_utxo(value: u64, script_pubkey: &[u8]) -> Vec<u8> {
+    let mut out = value.to_le_bytes().to_vec();
+    write_compact_size_into(script_pubkey.len() as u64, &mut out);
+    out.extend_from_slice(script_pubkey);
+    out
+}
+
+pub fn decode_witness_utxo(raw: &[u8]) -> WalletResult<UnsignedTxOutput> {
+    let mut reader = Reader::new(raw);
+    let output = reader.tx_output()?;
+    if !reader.is_empty() {
+        return Err(WalletError::invalid_input(
+            "PSBT witness_utxo has trailing bytes",
+        ));
+    }
+    Ok(output)
+}
+
+fn parse_unsigned_tx(raw: &[u8]) -> WalletResult<UnsignedTx> {
+    let mut reader = Reader::new(raw);
+    let version = reader.u32_le()?;
+    let input_count = reader.compact_size()?;
+    if input_count == 0 {
+        // A zero here is the segwit marker; BIP-174 requires the non-witness serialization.
+        return Err(WalletError::invalid_input(
+            "PSBT unsigned transaction must not contain witnesses or be empty",
+        ));
+    }
+    let mut inputs = Vec::new();
+    for _ in 0..input_count {
+        let txid_le: [u8; 32] = reader
+            .take(32)?
+            .try_into()
+            .map_err(|_| WalletError::invalid_input("PSBT input txid is malformed"))?;
+        let vout = reader.u32_le()?;
+        let script_sig_len = reader.compact_size()?;
+        if script_sig_len != 0 {
+            return Err(WalletError::invalid_input(
+                "PSBT unsigned transaction must have empty scriptSigs",
+            ));
+        }
+        let sequence = reader.u32_le()?;
+        inputs.push(UnsignedTxInput {
+            txid_le,
+            vout,
+            sequence,
+        });
+    }
+    let output_count = reader.compact_size()?;
+    let mut outputs = Vec::new();
+    for _ in 0..output_count {
+        outputs.push(reader.tx_output()?);
+    }
+    let lock_time = reader.u32_le()?;
+    if !reader.is_empty() {
+        return Err(WalletError::invalid_input(
+            "PSBT unsigned transaction has trailing bytes",
+        ));
+    }
+    Ok(UnsignedTx {
+        version,
+        inputs,
+        outputs,
+        lock_time,
+    })
+}
+
+fn serialize_map_into(map: &PsbtMap, out: &mut Vec<u8>) {
+    for (key, value) in map {
+        write_compact_size_into(key.len() as u64, out);
+        out.extend_from_slice(key);
+        write_compact_size_into(value.len() as u64, out);
+        out.extend_from_slice(value);
+    }
+    out.push(0x00);
+}
+
+fn write_compact_size_into(n: u64, out: &mut Vec<u8>) {
+    match n {
+        0..=0xfc => out.push(n as u8),
+        0xfd..=0xffff => {
+            out.push(0xfd);
+            out.extend_from_slice(&(n as u16).to_le_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(0xfe);
+            out.extend_from_slice(&(n as u32).to_le_bytes());
+        }
+        _ => {
+            out.push(0xff);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn take(&mut self, n: usize) -> WalletResult<&'a [u8]> {
+        if self.data.len() < n {
+            return Err(WalletError::invalid_input("PSBT is truncated"));
+        }
+        let (head, tail) = self.data.split_at(n);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn u32_le(&mut self) -> WalletResult<u32> {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn u64_le(&mut self) -> WalletResult<u64> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn compact_size(&mut self) -> WalletResult<usize> {
+        let n = match self.take(1)?[0] {
+            0xfd => u64::from(u16::from_le_bytes([self.take(1)?[0], self.take(1)?[0]])),
+            0xfe => u64::from(self.u32_le()?),
+            0xff => self.u64_le()?,
+            b => u64::from(b),
+        };
+        usize::try_from(n)
+            .ok()
+            .filter(|n| *n <= self.data.len())
+            .ok_or_else(|| WalletError::invalid_input("PSBT length prefix is out of range"))
+    }
+
+    fn bytes(&mut self) -> WalletResult<&'a [u8]> {
+        let len = self.compact_size()?;
+        self.take(len)
+    }
+
+    fn tx_output(&mut self) -> WalletResult<UnsignedTxOutput> {
+        let value = self.u64_le()?;
+        let script_pubkey = self.bytes()?.to_vec();
+        Ok(UnsignedTxOutput {
+            value,
+            script_pubkey,
+        })
+    }
+
+    fn map(&mut self) -> WalletResult<PsbtMap> {
+        let mut map = PsbtMap::new();
+        loop {
+            let key = self.bytes()?;
+            if key.is_empty() {
+                return Ok(map);
+            }
+            if map.iter().any(|(existing, _)| existing.as_slice() == key) {
+                return Err(WalletError::invalid_input("PSBT map has a duplicate key"));
+            }
+            let value = self.bytes()?;
+            map.push((key.to_vec(), value.to_vec()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unsigned_tx_bytes() -> Vec<u8> {
+        let mut tx = 2u32.to_le_bytes().to_vec();
+        tx.push(1);
+        tx.extend_from_slice(&[0xab; 32]);
+        tx.extend_from_slice(&1u32.to_le_bytes());
+        tx.push(0);
+        tx.extend_from_slice(&0xffff_fffdu32.to_le_bytes());
+        tx.push(1);
+        tx.extend_from_slice(&encode_witness_utxo(40_000, &[0x51, 0x20]));
+        tx.extend_from_slice(&0u32.to_le_bytes());
+        tx
+    }
+
+    #[test]
+    fn psbt_roundtrips_and_preserves_unknown_entries() {
+        let mut psbt = Psbt::new(unsigned_tx_bytes(), 1, 1);
+        map_set(
+            &mut psbt.inputs[0],
+            PSBT_IN_WITNESS_UTXO,
+            encode_witness_utxo(50_000, &[0x51, 0x20, 7]),
+        );
+        map_set(&mut psbt.inputs[0], PSBT_IN_TAP_INTERNAL_KEY, vec![9u8; 32]);
+        psbt.outputs[0].push((vec![0xfc, 1, 2], vec![3]));
+
+        let encoded = psbt.to_base64();
+        assert!(encoded.starts_with("cHNidP8"));
+        let parsed = Psbt::from_base64(&encoded).unwrap();
+        assert_eq!(parsed, psbt);
+
+        let tx = parsed.unsigned_tx().unwrap();
+        assert_eq!(tx.version, 2);
+        assert_eq!(tx.inputs[0].txid_le, [0xab; 32]);
+        assert_eq!(tx.inputs[0].vout, 1);
+        assert_eq!(tx.inputs[0].sequence, 0xffff_fffd);
+        assert_eq!(tx.outputs[0].value, 40_000);
+
+        let utxo =
+            decode_witness_utxo(map_get(&parsed.inputs[0], PSBT_IN_WITNESS_UTXO).unwrap()).unwrap();
+        assert_eq!(utxo.value, 50_000);
+        assert_eq!(utxo.script_pubkey, vec![0x51, 0x20, 7]);
+    }
+
+    #[test]
+    fn rejects_malformed_psbt() {
+        assert!(Psbt::parse(b"psbt").is_err());
+        let mut bytes = Psbt::new(unsigned_tx_bytes(), 1, 1).serialize();
+        bytes.pop();
+        assert!(Psbt::parse(&bytes).is_err());
+        bytes.extend_from_slice(&[0, 0]);
+        assert!(Psbt::parse(&bytes).is_err());
+    }
+}
@@ -26,6 +26,378 @@ pub fn encode_erc20_balance_of_call(account: &[u8; 20]) -> Vec<u8> {
     out
 }
 
+pub fn encode_erc20_approve_call(spender: &[u8; 20], amount: &BigUint) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32 * 2);
+    out.extend_from_slice(&[0x09, 0x5e, 0xa7, 0xb3]); // approve(address,uint256)
+    push_abi_address(spender, &mut out);
+    push_abi_uint(amount, "token amount", &mut out)?;
+    Ok(out)
+}
+
+pub fn encode_erc20_allowance_call(owner: &[u8; 20], spender: &[u8; 20]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + 32 * 2);
+    out.extend_from_slice(&[0xdd, 0x62, 0xed, 0x3e]); // allowance(address,address)
+    push_abi_address(owner, &mut out);
+    push_abi_address(spender, &mut out);
+    out
+}
+
+/// keccak256("Approval(address,address,uint256)")
+pub const ERC20_APPROVAL_EVENT_TOPIC: &str =
+    "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925";
+
+pub fn max_uint256() -> BigUint {
+    (BigUint::from(1u8) << 256usize) - BigUint::from(1u8)
+}
+
+pub fn encode_erc165_supports_interface_call(interface_id: [u8; 4]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + 32);
+    out.extend_from_slice(&[0x01, 0xff, 0xc9, 0xa7]); // supportsInterface(bytes4)
+    out.extend_from_slice(&interface_id);
+    out.extend_from_slice(&[0u8; 28]);
+    out
+}
+
+pub fn encode_erc721_safe_transfer_from_call(
+    from: &[u8; 20],
+    to: &[u8; 20],
+    token_id: &BigUint,
+) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32 * 3);
+    out.extend_from_slice(&[0x42, 0x84, 0x2e, 0x0e]); // safeTransferFrom(address,address,uint256)
+    push_abi_address(from, &mut out);
+    push_abi_address(to, &mut out);
+    push_abi_uint(token_id, "token id", &mut out)?;
+    Ok(out)
+}
+
+pub fn encode_erc721_owner_of_call(token_id: &BigUint) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32);
+    out.extend_from_slice(&[0x63, 0x52, 0x21, 0x1e]); // ownerOf(uint256)
+    push_abi_uint(token_id, "token id", &mut out)?;
+    Ok(out)
+}
+
+pub fn encode_erc1155_safe_transfer_from_call(
+    from: &[u8; 20],
+    to: &[u8; 20],
+    token_id: &BigUint,
+    amount: &BigUint,
+) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32 * 6);
+    out.extend_from_slice(&[0xf2, 0x42, 0x43, 0x2a]); // safeTransferFrom(address,address,uint256,uint256,bytes)
+    push_abi_address(from, &mut out);
+    push_abi_address(to, &mut out);
+    push_abi_uint(token_id, "token id", &mut out)?;
+    push_abi_uint(amount, "token amount", &mut out)?;
+    push_abi_uint(&BigUint::from(5u8 * 32), "bytes offset", &mut out)?;
+    push_abi_uint(&BigUint::from(0u8), "bytes length", &mut out)?; // empty `data`
+    Ok(out)
+}
+
+pub fn encode_erc1155_balance_of_call(
+    account: &[u8; 20],
+    token_id: &BigUint,
+) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32 * 2);
+    out.extend_from_slice(&[0x00, 0xfd, 0xd5, 0x8e]); // balanceOf(address,uint256)
+    push_abi_address(account, &mut out);
+    push_abi_uint(token_id, "token id", &mut out)?;
+    Ok(out)
+}
+
+/// One `Call3Value` entry for Multicall3; calls are encoded with `allowFailure = false`.
+pub struct Call3Value {
+    pub target: [u8; 20],
+    pub value: BigUint,
+    pub call_data: Vec<u8>,
+}
+
+pub fn encode_multicall3_aggregate3_value_call(calls: &[Call3Value]) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32 * (2 + calls.len() * 6));
+    out.extend_from_slice(&[0x17, 0x4d, 0xea, 0x71]); // aggregate3Value((address,bool,uint256,bytes)[])
+    push_abi_usize(32, &mut out);
+    push_abi_usize(calls.len(), &mut out);
+
+    let mut offset = 32 * calls.len();
+    for call in calls {
+        push_abi_usize(offset, &mut out);
+        offset += 32 * 5 + padded_len(call.call_data.len());
+    }
+    for call in calls {
+        push_abi_address(&call.target, &mut out);
+        push_abi_usize(0, &mut out); // allowFailure = false
+        push_abi_uint(&call.value, "call value", &mut out)?;
+        push_abi_usize(32 * 4, &mut out);
+        push_abi_bytes(&call.call_data, &mut out);
+    }
+    Ok(out)
+}
+
+pub fn encode_disperse_token_call(
+    token: &[u8; 20],
+    recipients: &[[u8; 20]],
+    values: &[BigUint],
+) -> WalletResult<Vec<u8>> {
+    if recipients.len() != values.len() {
+        return Err(WalletError::invalid_input(
+            "recipients and values must have the same length",
+        ));
+    }
+    let n = recipients.len();
+    let mut out = Vec::with_capacity(4 + 32 * (5 + n * 2));
+    out.extend_from_slice(&[0xc7, 0x3a, 0x2d, 0x60]); // disperseToken(address,address[],uint256[])
+    push_abi_address(token, &mut out);
+    push_abi_usize(32 * 3, &mut out);
+    push_abi_usize(32 * (4 + n), &mut out);
+    push_abi_usize(n, &mut out);
+    for recipient in recipients {
+        push_abi_address(recipient, &mut out);
+    }
+    push_abi_usize(n, &mut out);
+    for value in values {
+        push_abi_uint(value, "token amount", &mut out)?;
+    }
+    Ok(out)
+}
+
+/// Uniswap V3 packed path: `token (20) | fee (3) | token (20) | ...`.
+pub fn encode_uniswap_v3_path(tokens: &[[u8; 20]], fees: &[u32]) -> WalletResult<Vec<u8>> {
+    if tokens.len() < 2 || fees.len() + 1 != tokens.len() {
+        return Err(WalletError::invalid_input(
+            "swap path needs n tokens and n-1 fee tiers",
+        ));
+    }
+    let mut out = Vec::with_capacity(20 + 23 * fees.len());
+    out.extend_from_slice(&tokens[0]);
+    for (fee, token) in fees.iter().zip(&tokens[1..]) {
+        if *fee >= 1 << 24 {
+            return Err(WalletError::invalid_input("fee tier must fit in uint24"));
+        }
+        out.extend_from_slice(&fee.to_be_bytes()[1..]);
+        out.extend_from_slice(token);
+    }
+    Ok(out)
+}
+
+pub fn encode_quoter_v2_quote_exact_input_single_call(
+    token_in: &[u8; 20],
+    token_out: &[u8; 20],
+    amount_in: &BigUint,
+    fee: u32,
+) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32 * 5);
+    out.extend_from_slice(&[0xc6, 0xa5, 0x02, 0x6a]); // quoteExactInputSingle((address,address,uint256,uint24,uint160))
+    push_abi_address(token_in, &mut out);
+    push_abi_address(token_out, &mut out);
+    push_abi_uint(amount_in, "amount in", &mut out)?;
+    push_abi_usize(fee as usize, &mut out);
+    push_abi_usize(0, &mut out); // sqrtPriceLimitX96
+    Ok(out)
+}
+
+pub fn encode_quoter_v2_quote_exact_input_call(
+    path: &[u8],
+    amount_in: &BigUint,
+) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32 * 3 + padded_len(path.len()));
+    out.extend_from_slice(&[0xcd, 0xca, 0x17, 0x53]); // quoteExactInput(bytes,uint256)
+    push_abi_usize(32 * 2, &mut out);
+    push_abi_uint(amount_in, "amount in", &mut out)?;
+    push_abi_bytes(path, &mut out);
+    Ok(out)
+}
+
+pub fn encode_swap_router02_exact_input_single_call(
+    token_in: &[u8; 20],
+    token_out: &[u8; 20],
+    fee: u32,
+    recipient: &[u8; 20],
+    amount_in: &BigUint,
+    amount_out_minimum: &BigUint,
+) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32 * 7);
+    out.extend_from_slice(&[0x04, 0xe4, 0x5a, 0xaf]); // exactInputSingle((address,address,uint24,address,uint256,uint256,uint160))
+    push_abi_address(token_in, &mut out);
+    push_abi_address(token_out, &mut out);
+    push_abi_usize(fee as usize, &mut out);
+    push_abi_address(recipient, &mut out);
+    push_abi_uint(amount_in, "amount in", &mut out)?;
+    push_abi_uint(amount_out_minimum, "minimum amount out", &mut out)?;
+    push_abi_usize(0, &mut out); // sqrtPriceLimitX96
+    Ok(out)
+}
+
+pub fn encode_swap_router02_exact_input_call(
+    path: &[u8],
+    recipient: &[u8; 20],
+    amount_in: &BigUint,
+    amount_out_minimum: &BigUint,
+) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32 * 6 + padded_len(path.len()));
+    out.extend_from_slice(&[0xb8, 0x58, 0x18, 0x3f]); // exactInput((bytes,address,uint256,uint256))
+    push_abi_usize(32, &mut out);
+    push_abi_usize(32 * 4, &mut out);
+    push_abi_address(recipient, &mut out);
+    push_abi_uint(amount_in, "amount in", &mut out)?;
+    push_abi_uint(amount_out_minimum, "minimum amount out", &mut out)?;
+    push_abi_bytes(path, &mut out);
+    Ok(out)
+}
+
+pub fn encode_swap_router02_unwrap_weth9_call(
+    amount_minimum: &BigUint,
+    recipient: &[u8; 20],
+) -> WalletResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(4 + 32 * 2);
+    out.extend_from_slice(&[0x49, 0x40, 0x4b, 0x7c]); // unwrapWETH9(uint256,address)
+    push_abi_uint(amount_minimum, "minimum amount", &mut out)?;
+    push_abi_address(recipient, &mut out);
+    Ok(out)
+}
+
+pub fn encode_swap_router02_multicall_call(deadline: u64, calls: &[Vec<u8>]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&[0x5a, 0xe4, 0x01, 0xdc]); // multicall(uint256,bytes[])
+    push_abi_usize(deadline as usize, &mut out);
+    push_abi_usize(32 * 2, &mut out);
+    push_abi_usize(calls.len(), &mut out);
+    let mut offset = 32 * calls.len();
+    for call in calls {
+        push_abi_usize(offset, &mut out);
+        offset += 32 + padded_len(call.len());
+    }
+    for call in calls {
+        push_abi_bytes(call, &mut out);
+    }
+    out
+}
+
+pub fn encode_gas_price_oracle_get_l1_fee_call(unsigned_tx: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + 32 * 2 + padded_len(unsigned_tx.len()));
+    out.extend_from_slice(&[0x49, 0x94, 0x8e, 0x0e]); // getL1Fee(bytes)
+    push_abi_usize(32, &mut out);
+    push_abi_bytes(unsigned_tx, &mut out);
+    out
+}
+
+/// ENS namehash over already-normalized (lowercase ASCII) labels.
+pub fn ens_namehash(name: &str) -> [u8; 32] {
+    let mut node = [0u8; 32];
+    if name.is_empty() {
+        return node;
+    }
+    for label in name.rsplit('.') {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(&node);
+        buf[32..].copy_from_slice(&keccak256(label.as_bytes()));
+        node = keccak256(&buf);
+    }
+    node
+}
+
+pub fn encode_ens_resolver_call(node: &[u8; 32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + 32);
+    out.extend_from_slice(&[0x01, 0x78, 0xb8, 0xbf]); // resolver(bytes32)
+    out.extend_from_slice(node);
+    out
+}
+
+pub fn encode_ens_addr_call(node: &[u8; 32]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4 + 32);
+    out.extend_from_slice(&[0x3b, 0x3b, 0x57, 0xde]); // addr(bytes32)
+    out.extend_from_slice(node);
+    out
+}
+
+pub fn decode_abi_address(word: &[u8]) -> WalletResult<[u8; 20]> {
+    if word.len() < 32 {
+        return Err(WalletError::Internal(
+            "ABI address word must be 32 bytes".to_string(),
+        ));
+    }
+    if word[..12].iter().any(|b| *b != 0) {
+        return Err(WalletError::Internal(
+            "ABI address word has non-zero padding".to_string(),
+        ));
+    }
+    let mut out = [0u8; 20];
+    out.copy_from_slice(&word[12..32]);
+    Ok(out)
+}
+
+/// Revert data decoded from a failed `eth_call`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedRevert {
+    pub selector: Option<[u8; 4]>,
+    pub reason: String,
+}
+
+/// Decodes `Error(string)`, `Panic(uint256)` and falls back to the raw selector for custom errors.
+pub fn decode_revert_data(data: &[u8]) -> DecodedRevert {
+    if data.len() < 4 {
+        return DecodedRevert {
+            selector: None,
+            reason: "execution reverted without reason".to_string(),
+        };
+    }
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&data[..4]);
+    let payload = &data[4..];
+    let reason = match selector {
+        // Error(string)
+        [0x08, 0xc3, 0x79, 0xa0] => match decode_abi_string(payload) {
+            Some(message) => format!("Error(string): {message}"),
+            None => "Error(string) with malformed payload".to_string(),
+        },
+        // Panic(uint256)
+        [0x4e, 0x48, 0x7b, 0x71] if payload.len() >= 32 => {
+            let code = BigUint::from_bytes_be(&payload[..32]);
+            format!(
+                "Panic(0x{}): {}",
+                code.to_str_radix(16),
+                panic_code_description(&code)
+            )
+        }
+        _ => format!(
+            "custom error 0x{}",
+            selector
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>()
+        ),
+    };
+    DecodedRevert {
+        selector: Some(selector),
+        reason,
+    }
+}
+
+fn decode_abi_string(payload: &[u8]) -> Option<String> {
+    let offset = usize::try_from(BigUint::from_bytes_be(payload.get(..32)?)).ok()?;
+    let len_word = payload.get(offset..offset.checked_add(32)?)?;
+    let len = usize::try_from(BigUint::from_bytes_be(len_word)).ok()?;
+    let start = offset + 32;
+    let bytes = payload.get(start..start.checked_add(len)?)?;
+    Some(String::from_utf8_lossy(bytes).into_owned())
+}
+
+fn panic_code_description(code: &BigUint) -> &'static str {
+    match u64::try_from(code).unwrap_or(u64::MAX) {
+        0x00 => "generic compiler panic",
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array encoding",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to zero-initialized function",
+        _ => "unknown panic code",
+    }
+}
+
 pub fn parse_hex_quantity(hex: &str) -> WalletResult<BigUint> {
     let trimmed = hex.trim();
     let digits = trimmed
@@ -215,6 +587,36 @@ pub fn rlp_encode_u64_for_test(v: u64) -> Vec<u8> {
     rlp_encode_u64(v)
 }
 
+fn push_abi_address(address: &[u8; 20], out: &mut Vec<u8>) {
+    out.extend_from_slice(&[0u8; 12]);
+    out.extend_from_slice(address);
+}
+
+fn push_abi_uint(value: &BigUint, what: &str, out: &mut Vec<u8>) -> WalletResult<()> {
+    let bytes = value.to_bytes_be();
+    if bytes.len() > 32 {
+        return Err(WalletError::invalid_input(format!("{what} is too large")));
+    }
+    out.extend_from_slice(&vec![0u8; 32 - bytes.len()]);
+    out.extend_from_slice(&bytes);
+    Ok(())
+}
+
+fn push_abi_usize(value: usize, out: &mut Vec<u8>) {
+    out.extend_from_slice(&[0u8; 24]);
+    out.extend_from_slice(&(value as u64).to_be_bytes());
+}
+
+fn push_abi_bytes(data: &[u8], out: &mut Vec<u8>) {
+    push_abi_usize(data.len(), out);
+    out.extend_from_slice(data);
+    out.extend_from_slice(&vec![0u8; padded_len(data.len()) - data.len()]);
+}
+
+fn padded_len(len: usize) -> usize {
+    len.div_ceil(32) * 32
+}
+
 fn trim_decimal_zeros(mut s: String) -> String {
     if let Some(dot) = s.find('.') {
         while s.ends_with('0') {
@@ -331,4 +733,194 @@ mod tests {
         assert_eq!(unsigned.first().copied(), Some(0x02));
         assert_eq!(signed.first().copied(), Some(0x02));
     }
+
+    #[test]
+    fn nft_call_selectors_match_signatures() {
+        let id = BigUint::from(7u8);
+        let addr = [0x11u8; 20];
+        let cases = [
+            (
+                encode_erc165_supports_interface_call([0x80, 0xac, 0x58, 0xcd]),
+                "supportsInterface(bytes4)",
+            ),
+            (
+                encode_erc721_safe_transfer_from_call(&addr, &addr, &id).unwrap(),
+                "safeTransferFrom(address,address,uint256)",
+            ),
+            (
+                encode_erc721_owner_of_call(&id).unwrap(),
+                "ownerOf(uint256)",
+            ),
+            (
+                encode_erc1155_safe_transfer_from_call(&addr, &addr, &id, &id).unwrap(),
+                "safeTransferFrom(address,address,uint256,uint256,bytes)",
+            ),
+            (
+                encode_erc1155_balance_of_call(&addr, &id).unwrap(),
+                "balanceOf(address,uint256)",
+            ),
+            (
+                encode_erc20_approve_call(&addr, &max_uint256()).unwrap(),
+                "approve(address,uint256)",
+            ),
+            (
+                encode_erc20_allowance_call(&addr, &addr),
+                "allowance(address,address)",
+            ),
+            (
+                encode_quoter_v2_quote_exact_input_single_call(&addr, &addr, &id, 500).unwrap(),
+                "quoteExactInputSingle((address,address,uint256,uint24,uint160))",
+            ),
+            (
+                encode_quoter_v2_quote_exact_input_call(&[0u8; 43], &id).unwrap(),
+                "quoteExactInput(bytes,uint256)",
+            ),
+            (
+                encode_swap_router02_exact_input_single_call(&addr, &addr, 500, &addr, &id, &id)
+                    .unwrap(),
+                "exactInputSingle((address,address,uint24,address,uint256,uint256,uint160))",
+            ),
+            (
+                encode_swap_router02_exact_input_call(&[0u8; 43], &addr, &id, &id).unwrap(),
+                "exactInput((bytes,address,uint256,uint256))",
+            ),
+            (
+                encode_swap_router02_unwrap_weth9_call(&id, &addr).unwrap(),
+                "unwrapWETH9(uint256,address)",
+            ),
+            (
+                encode_swap_router02_multicall_call(1, &[vec![0u8; 4]]),
+                "multicall(uint256,bytes[])",
+            ),
+            (
+                encode_gas_price_oracle_get_l1_fee_call(&[0x02, 0xc0]),
+                "getL1Fee(bytes)",
+            ),
+            (encode_ens_resolver_call(&[0u8; 32]), "resolver(bytes32)"),
+            (encode_ens_addr_call(&[0u8; 32]), "addr(bytes32)"),
+            (
+                encode_multicall3_aggregate3_value_call(&[]).unwrap(),
+                "aggregate3Value((address,bool,uint256,bytes)[])",
+            ),
+            (
+                encode_disperse_token_call(&addr, &[addr], std::slice::from_ref(&id)).unwrap(),
+                "disperseToken(address,address[],uint256[])",
+            ),
+        ];
+        for (data, signature) in cases {
+            assert_eq!(
+                &data[..4],
+                &keccak256(signature.as_bytes())[..4],
+                "{signature}"
+            );
+            assert_eq!((data.len() - 4) % 32, 0, "{signature}");
+        }
+    }
+
+    #[test]
+    fn approval_topic_matches_event_signature() {
+        let topic = keccak256(b"Approval(address,address,uint256)");
+        assert_eq!(
+            ERC20_APPROVAL_EVENT_TOPIC,
+            format!("0x{}", crate::addressing::hex_encode(&topic))
+        );
+        assert_eq!(max_uint256().bits(), 256);
+    }
+
+    #[test]
+    fn erc1155_transfer_encodes_empty_bytes_tail() {
+        let data = encode_erc1155_safe_transfer_from_call(
+            &[1u8; 20],
+            &[2u8; 20],
+            &BigUint::from(9u8),
+            &BigUint::from(3u8),
+        )
+        .unwrap();
+        assert_eq!(data.len(), 4 + 32 * 6);
+        assert_eq!(data[4 + 32 * 4 + 31], 0xa0);
+        assert!(data[4 + 32 * 5..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn aggregate3_value_encodes_dynamic_tuple_array() {
+        let calls = [
+            Call3Value {
+                target: [1u8; 20],
+                value: BigUint::from(5u8),
+                call_data: Vec::new(),
+            },
+            Call3Value {
+                target: [2u8; 20],
+                value: BigUint::from(6u8),
+                call_data: vec![0xaa; 33],
+            },
+        ];
+        let data = encode_multicall3_aggregate3_value_call(&calls).unwrap();
+        let word = |i: usize| &data[4 + 32 * i..4 + 32 * (i + 1)];
+        assert_eq!(word(0)[31], 0x20);
+        assert_eq!(word(1)[31], 2);
+        assert_eq!(word(2)[31], 0x40);
+        assert_eq!(word(3)[31], 0x40 + 0xa0);
+        assert_eq!(word(4)[31], 1);
+        assert_eq!(word(6)[31], 5);
+        assert_eq!(word(7)[31], 0x80);
+        assert_eq!(word(8)[31], 0);
+        assert_eq!(word(13)[31], 33);
+        assert_eq!(data.len(), 4 + 32 * (4 + 5 + 5 + 2));
+    }
+
+    #[test]
+    fn ens_namehash_matches_eip137_vectors() {
+        assert_eq!(ens_namehash(""), [0u8; 32]);
+        assert_eq!(
+            crate::addressing::hex_encode(&ens_namehash("eth")),
+            "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+        );
+        assert_eq!(
+            crate::addressing::hex_encode(&ens_namehash("foo.eth")),
+            "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+        );
+    }
+
+    #[test]
+    fn uniswap_v3_path_packs_fee_tiers() {
+        let path =
+            encode_uniswap_v3_path(&[[1u8; 20], [2u8; 20], [3u8; 20]], &[500, 3000]).unwrap();
+        assert_eq!(path.len(), 20 + 23 * 2);
+        assert_eq!(&path[20..23], &[0x00, 0x01, 0xf4]);
+        assert_eq!(&path[43..46], &[0x00, 0x0b, 0xb8]);
+        assert!(encode_uniswap_v3_path(&[[1u8; 20]], &[]).is_err());
+    }
+
+    #[test]
+    fn decodes_error_string_revert() {
+        let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+        let mut word = [0u8; 32];
+        word[31] = 0x20;
+        data.extend_from_slice(&word);
+        word[31] = 26;
+        data.extend_from_slice(&word);
+        let mut text = b"ERC20: transfer amount exc".to_vec();
+        text.resize(32, 0);
+        data.extend_from_slice(&text);
+        let decoded = decode_revert_data(&data);
+        assert_eq!(decoded.selector, Some([0x08, 0xc3, 0x79, 0xa0]));
+        assert_eq!(decoded.reason, "Error(string): ERC20: transfer amount exc");
+    }
+
+    #[test]
+    fn decodes_panic_and_custom_reverts() {
+        let mut data = vec![0x4e, 0x48, 0x7b, 0x71];
+        let mut word = [0u8; 32];
+        word[31] = 0x11;
+        data.extend_from_slice(&word);
+        assert_eq!(
+            decode_revert_data(&data).reason,
+            "Panic(0x11): arithmetic overflow or underflow"
+        );
+
+        let custom = decode_revert_data(&[0xe4, 0x50, 0xd3, 0x8c, 0x00]);
+        assert_eq!(custom.reason, "custom error 0xe450d38c");
+        assert_eq!(decode_revert_data(&[]).selector, None);
+    }
 }
@@ -1,3 +1,4 @@
+pub mod btc_psbt;
 pub mod evm_tx;
 pub mod sol_tx;
 pub mod ton_tx;
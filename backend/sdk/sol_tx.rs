@@ -1,12 +1,41 @@
-use ed25519_dalek::VerifyingKey;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use num_bigint::BigUint;
 use sha2::{Digest, Sha256};
 
 use crate::error::{WalletError, WalletResult};
 
 const SOLANA_SYSTEM_PROGRAM_ID: [u8; 32] = [0u8; 32];
-const SPL_TOKEN_PROGRAM_ID_BASE58: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const SPL_TOKEN_PROGRAM_ID_BASE58: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID_BASE58: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const SPL_MEMO_PROGRAM_ID_BASE58: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+const SPL_TOKEN_CLOSE_ACCOUNT: u8 = 9;
+const SPL_TOKEN_TRANSFER_CHECKED: u8 = 12;
+const TOKEN_2022_TRANSFER_FEE_EXTENSION: u8 = 26;
+const TOKEN_2022_TRANSFER_CHECKED_WITH_FEE: u8 = 1;
 const SPL_ASSOCIATED_TOKEN_PROGRAM_ID_BASE58: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+const COMPUTE_BUDGET_PROGRAM_ID_BASE58: &str = "ComputeBudget111111111111111111111111111111";
+const COMPUTE_BUDGET_SET_UNIT_LIMIT: u8 = 2;
+const COMPUTE_BUDGET_SET_UNIT_PRICE: u8 = 3;
+const SYSVAR_RECENT_BLOCKHASHES_BASE58: &str = "SysvarRecentB1ockHashes11111111111111111111";
+const SYSVAR_RENT_BASE58: &str = "SysvarRent111111111111111111111111111111111";
+const V0_MESSAGE_PREFIX: u8 = 0x80;
+const SYSTEM_CREATE_ACCOUNT_WITH_SEED: u32 = 3;
+const SYSTEM_ADVANCE_NONCE_ACCOUNT: u32 = 4;
+const SYSTEM_INITIALIZE_NONCE_ACCOUNT: u32 = 6;
+const NONCE_ACCOUNT_SEED: &str = "durable-nonce";
+pub const NONCE_ACCOUNT_SPACE: u64 = 80;
+const SNS_NAME_PROGRAM_ID_BASE58: &str = "namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX";
+const SNS_SOL_TLD_BASE58: &str = "58PwtjSDuFHuUkYjH9BYnnQKHfwo9reZhC2zMJv9JPkx";
+const SNS_HASH_PREFIX: &[u8] = b"SPL Name Service";
+const SNS_RECORDS_CENTRAL_STATE_BASE58: &str = "2pMnqHvei2N5oDcVGCRdZx48gqti199wAYYbaBmfNRe1";
+const SNS_NAME_TOKENIZER_PROGRAM_ID_BASE58: &str = "nftD3vbNkNqfj2Sd3HZwbpw4BxxKWr4AjGb9X38JeZk";
+// Name registry header: parent_name (32) | owner (32) | class (32)
+pub const SNS_NAME_HEADER_LEN: usize = 96;
+const SNS_RECORD_V2_HEADER_LEN: usize = 8;
+const SNS_VALIDATION_NONE: u16 = 0;
+const SNS_VALIDATION_SOLANA: u16 = 1;
+const SNS_VALIDATION_ETHEREUM: u16 = 2;
+const SNS_VALIDATION_UNVERIFIED_SOLANA: u16 = 3;
 const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
 const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
 
@@ -17,39 +46,599 @@ pub fn decode_solana_pubkey(value: &str) -> WalletResult<[u8; 32]> {
     })
 }
 
-pub fn encode_system_transfer_message(
-    from_pubkey: &[u8; 32],
-    to_pubkey: &[u8; 32],
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountMeta {
+    pub pubkey: [u8; 32],
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub program_id: [u8; 32],
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// What keeps a message valid: a recent blockhash (about 150 slots) or a durable nonce,
+/// which stays valid until the nonce account is advanced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TxLifetime {
+    Blockhash([u8; 32]),
+    DurableNonce {
+        nonce_account: [u8; 32],
+        authority: [u8; 32],
+        nonce: [u8; 32],
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressLookupTable {
+    pub key: [u8; 32],
+    pub addresses: Vec<[u8; 32]>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub account_indexes: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MessageAddressTableLookup {
+    pub account_key: [u8; 32],
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct V0Message {
+    pub num_required_signatures: u8,
+    pub num_readonly_signed: u8,
+    pub num_readonly_unsigned: u8,
+    pub static_keys: Vec<[u8; 32]>,
+    pub recent_blockhash: [u8; 32],
+    pub instructions: Vec<CompiledInstruction>,
+    pub address_table_lookups: Vec<MessageAddressTableLookup>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComputeBudget {
+    pub unit_limit: u32,
+    pub unit_price_micro_lamports: u64,
+}
+
+impl AccountMeta {
+    pub fn signer(pubkey: [u8; 32], is_writable: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer: true,
+            is_writable,
+        }
+    }
+
+    pub fn writable(pubkey: [u8; 32]) -> Self {
+        Self {
+            pubkey,
+            is_signer: false,
+            is_writable: true,
+        }
+    }
+
+    pub fn readonly(pubkey: [u8; 32]) -> Self {
+        Self {
+            pubkey,
+            is_signer: false,
+            is_writable: false,
+        }
+    }
+}
+
+impl ComputeBudget {
+    /// The ComputeBudget instructions to place ahead of the payload; a zero price is omitted.
+    pub fn instructions(&self) -> WalletResult<Vec<Instruction>> {
+        let program_id = decode_solana_pubkey(COMPUTE_BUDGET_PROGRAM_ID_BASE58)?;
+        let mut limit_data = vec![COMPUTE_BUDGET_SET_UNIT_LIMIT];
+        limit_data.extend_from_slice(&self.unit_limit.to_le_bytes());
+        let mut out = vec![Instruction {
+            program_id,
+            accounts: Vec::new(),
+            data: limit_data,
+        }];
+        if self.unit_price_micro_lamports > 0 {
+            let mut price_data = vec![COMPUTE_BUDGET_SET_UNIT_PRICE];
+            price_data.extend_from_slice(&self.unit_price_micro_lamports.to_le_bytes());
+            out.push(Instruction {
+                program_id,
+                accounts: Vec::new(),
+                data: price_data,
+            });
+        }
+        Ok(out)
+    }
+
+    /// Lamports paid on top of the base signature fee if the whole unit limit is requested.
+    pub fn max_priority_fee_lamports(&self) -> u64 {
+        (u128::from(self.unit_limit) * u128::from(self.unit_price_micro_lamports))
+            .div_ceil(1_000_000)
+            .try_into()
+            .unwrap_or(u64::MAX)
+    }
+}
+
+/// Compiles instructions into a legacy message with the fee payer as the only required signer.
+pub fn compile_legacy_message(
+    payer: &[u8; 32],
+    instructions: &[Instruction],
     recent_blockhash: &[u8; 32],
-    lamports: u64,
-) -> Vec<u8> {
-    let mut out = Vec::with_capacity(256);
+) -> WalletResult<Vec<u8>> {
+    let keys = collect_account_keys(payer, instructions)?;
+    let static_keys = keys.iter().map(|k| k.pubkey).collect::<Vec<_>>();
+    let readonly_unsigned = keys.iter().filter(|k| !k.is_writable).count();
 
+    let mut out = Vec::with_capacity(256);
     out.push(1);
     out.push(0);
-    out.push(1);
+    out.push(readonly_unsigned as u8);
 
-    encode_shortvec_len(3, &mut out);
-    out.extend_from_slice(from_pubkey);
-    out.extend_from_slice(to_pubkey);
-    out.extend_from_slice(&SOLANA_SYSTEM_PROGRAM_ID);
+    encode_shortvec_len(keys.len(), &mut out);
+    for key in &static_keys {
+        out.extend_from_slice(key);
+    }
 
     out.extend_from_slice(recent_blockhash);
+    encode_compiled_instructions(&compile_instructions(instructions, &static_keys), &mut out);
 
-    encode_shortvec_len(1, &mut out);
+    Ok(out)
+}
 
-    out.push(2);
-    encode_shortvec_len(2, &mut out);
-    out.push(0);
-    out.push(1);
+/// Compiles a v0 message, loading non-signer, non-program accounts from `lookup_tables`
+/// when they appear there; everything else stays in the static key list.
+pub fn compile_v0_message(
+    payer: &[u8; 32],
+    instructions: &[Instruction],
+    recent_blockhash: &[u8; 32],
+    lookup_tables: &[AddressLookupTable],
+) -> WalletResult<V0Message> {
+    let mut pinned = instructions
+        .iter()
+        .map(|ix| ix.program_id)
+        .collect::<Vec<_>>();
+    // The runtime only finds a durable nonce account among the static keys.
+    if let Some(nonce_account) = instructions
+        .first()
+        .filter(|ix| is_advance_nonce_instruction(ix))
+        .and_then(|ix| ix.accounts.first())
+    {
+        pinned.push(nonce_account.pubkey);
+    }
+    let mut static_keys = Vec::new();
+    let mut lookups = lookup_tables
+        .iter()
+        .map(|table| MessageAddressTableLookup {
+            account_key: table.key,
+            writable_indexes: Vec::new(),
+            readonly_indexes: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    for key in collect_account_keys(payer, instructions)? {
+        let table_slot = if key.is_signer || pinned.contains(&key.pubkey) {
+            None
+        } else {
+            lookup_tables.iter().enumerate().find_map(|(t, table)| {
+                table
+                    .addresses
+                    .iter()
+                    .position(|a| *a == key.pubkey)
+                    .and_then(|i| u8::try_from(i).ok())
+                    .map(|i| (t, i))
+            })
+        };
+        match table_slot {
+            Some((t, i)) if key.is_writable => lookups[t].writable_indexes.push(i),
+            Some((t, i)) => lookups[t].readonly_indexes.push(i),
+            None => static_keys.push(key),
+        }
+    }
+    lookups.retain(|l| !l.writable_indexes.is_empty() || !l.readonly_indexes.is_empty());
 
+    // Loaded accounts are indexed after the static keys: all writable lookups, then read-only.
+    let mut account_keys = static_keys.iter().map(|k| k.pubkey).collect::<Vec<_>>();
+    for writable in [true, false] {
+        for lookup in &lookups {
+            let table = lookup_tables
+                .iter()
+                .find(|t| t.key == lookup.account_key)
+                .ok_or_else(|| WalletError::Internal("lookup table disappeared".into()))?;
+            let indexes = if writable {
+                &lookup.writable_indexes
+            } else {
+                &lookup.readonly_indexes
+            };
+            account_keys.extend(indexes.iter().map(|i| table.addresses[usize::from(*i)]));
+        }
+    }
+    if account_keys.len() > usize::from(u8::MAX) + 1 {
+        return Err(WalletError::invalid_input(
+            "solana message references too many accounts",
+        ));
+    }
+
+    Ok(V0Message {
+        num_required_signatures: 1,
+        num_readonly_signed: 0,
+        num_readonly_unsigned: static_keys.iter().filter(|k| !k.is_writable).count() as u8,
+        static_keys: static_keys.iter().map(|k| k.pubkey).collect(),
+        recent_blockhash: *recent_blockhash,
+        instructions: compile_instructions(instructions, &account_keys),
+        address_table_lookups: lookups,
+    })
+}
+
+/// Payer first, then writable and read-only accounts, with signer/writable flags merged.
+fn collect_account_keys(
+    payer: &[u8; 32],
+    instructions: &[Instruction],
+) -> WalletResult<Vec<AccountMeta>> {
+    let mut keys: Vec<AccountMeta> = vec![AccountMeta::signer(*payer, true)];
+    let mut merge = |meta: AccountMeta| match keys.iter_mut().find(|k| k.pubkey == meta.pubkey) {
+        Some(existing) => {
+            existing.is_signer |= meta.is_signer;
+            existing.is_writable |= meta.is_writable;
+        }
+        None => keys.push(meta),
+    };
+    for ix in instructions {
+        for meta in &ix.accounts {
+            merge(meta.clone());
+        }
+        merge(AccountMeta::readonly(ix.program_id));
+    }
+    if keys[1..].iter().any(|k| k.is_signer) {
+        return Err(WalletError::invalid_input(
+            "solana message requires signers other than the fee payer",
+        ));
+    }
+    // Account order is fixed by the header: payer, writable accounts, then read-only accounts.
+    keys[1..].sort_by_key(|k| !k.is_writable);
+    if keys.len() > usize::from(u8::MAX) {
+        return Err(WalletError::invalid_input(
+            "solana message references too many accounts",
+        ));
+    }
+    Ok(keys)
+}
+
+fn compile_instructions(
+    instructions: &[Instruction],
+    account_keys: &[[u8; 32]],
+) -> Vec<CompiledInstruction> {
+    let index_of =
+        |pubkey: &[u8; 32]| account_keys.iter().position(|k| k == pubkey).unwrap_or(0) as u8;
+    instructions
+        .iter()
+        .map(|ix| CompiledInstruction {
+            program_id_index: index_of(&ix.program_id),
+            account_indexes: ix.accounts.iter().map(|m| index_of(&m.pubkey)).collect(),
+            data: ix.data.clone(),
+        })
+        .collect()
+}
+
+fn encode_compiled_instructions(instructions: &[CompiledInstruction], out: &mut Vec<u8>) {
+    encode_shortvec_len(instructions.len(), out);
+    for ix in instructions {
+        out.push(ix.program_id_index);
+        encode_shortvec_len(ix.account_indexes.len(), out);
+        out.extend_from_slice(&ix.account_indexes);
+        encode_shortvec_len(ix.data.len(), out);
+        out.extend_from_slice(&ix.data);
+    }
+}
+
+impl V0Message {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(512);
+        out.push(V0_MESSAGE_PREFIX);
+        out.push(self.num_required_signatures);
+        out.push(self.num_readonly_signed);
+        out.push(self.num_readonly_unsigned);
+        encode_shortvec_len(self.static_keys.len(), &mut out);
+        for key in &self.static_keys {
+            out.extend_from_slice(key);
+        }
+        out.extend_from_slice(&self.recent_blockhash);
+        encode_compiled_instructions(&self.instructions, &mut out);
+        encode_shortvec_len(self.address_table_lookups.len(), &mut out);
+        for lookup in &self.address_table_lookups {
+            out.extend_from_slice(&lookup.account_key);
+            encode_shortvec_len(lookup.writable_indexes.len(), &mut out);
+            out.extend_from_slice(&lookup.writable_indexes);
+            encode_shortvec_len(lookup.readonly_indexes.len(), &mut out);
+            out.extend_from_slice(&lookup.readonly_indexes);
+        }
+        out
+    }
+
+    /// Strict parse: the version prefix must be v0 and no bytes may trail the message.
+    pub fn parse(bytes: &[u8]) -> WalletResult<Self> {
+        let mut reader = ByteReader { bytes, pos: 0 };
+        if reader.u8()? != V0_MESSAGE_PREFIX {
+            return Err(WalletError::invalid_input(
+                "solana message is not a v0 versioned message",
+            ));
+        }
+        let num_required_signatures = reader.u8()?;
+        let num_readonly_signed = reader.u8()?;
+        let num_readonly_unsigned = reader.u8()?;
+        let static_keys = (0..reader.shortvec()?)
+            .map(|_| reader.key())
+            .collect::<WalletResult<Vec<_>>>()?;
+        let recent_blockhash = reader.key()?;
+        let instructions = (0..reader.shortvec()?)
+            .map(|_| {
+                let program_id_index = reader.u8()?;
+                let len = reader.shortvec()?;
+                let account_indexes = reader.take(len)?.to_vec();
+                let len = reader.shortvec()?;
+                Ok(CompiledInstruction {
+                    program_id_index,
+                    account_indexes,
+                    data: reader.take(len)?.to_vec(),
+                })
+            })
+            .collect::<WalletResult<Vec<_>>>()?;
+        let address_table_lookups = (0..reader.shortvec()?)
+            .map(|_| {
+                let account_key = reader.key()?;
+                let len = reader.shortvec()?;
+                let writable_indexes = reader.take(len)?.to_vec();
+                let len = reader.shortvec()?;
+                Ok(MessageAddressTableLookup {
+                    account_key,
+                    writable_indexes,
+                    readonly_indexes: reader.take(len)?.to_vec(),
+                })
+            })
+            .collect::<WalletResult<Vec<_>>>()?;
+        if reader.pos != bytes.len() {
+            return Err(WalletError::invalid_input(
+                "solana v0 message has trailing bytes",
+            ));
+        }
+
+        let message = Self {
+            num_required_signatures,
+            num_readonly_signed,
+            num_readonly_unsigned,
+            static_keys,
+            recent_blockhash,
+            instructions,
+            address_table_lookups,
+        };
+        let total_keys = message.static_keys.len()
+            + message
+                .address_table_lookups
+                .iter()
+                .map(|l| l.writable_indexes.len() + l.readonly_indexes.len())
+                .sum::<usize>();
+        if usize::from(message.num_required_signatures) > message.static_keys.len()
+            || message.instructions.iter().any(|ix| {
+                usize::from(ix.program_id_index) >= message.static_keys.len()
+                    || ix
+                        .account_indexes
+                        .iter()
+                        .any(|i| usize::from(*i) >= total_keys)
+            })
+        {
+            return Err(WalletError::invalid_input(
+                "solana v0 message references accounts out of range",
+            ));
+        }
+        Ok(message)
+    }
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl ByteReader<'_> {
+    fn take(&mut self, len: usize) -> WalletResult<&[u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| WalletError::invalid_input("solana message is truncated"))?;
+        let out = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> WalletResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn key(&mut self) -> WalletResult<[u8; 32]> {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(self.take(32)?);
+        Ok(out)
+    }
+
+    fn shortvec(&mut self) -> WalletResult<usize> {
+        let mut value = 0usize;
+        for shift in [0, 7, 14] {
+            let byte = self.u8()?;
+            value |= usize::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(WalletError::invalid_input(
+            "solana shortvec length is too long",
+        ))
+    }
+}
+
+/// Orders a payload behind `AdvanceNonceAccount` (which must come first) and the compute budget.
+/// Non-empty `lookup_tables` produce a v0 message instead of a legacy one.
+fn compile_with_lifetime(
+    payer: &[u8; 32],
+    lifetime: &TxLifetime,
+    compute_budget: &ComputeBudget,
+    lookup_tables: &[AddressLookupTable],
+    payload: Vec<Instruction>,
+) -> WalletResult<Vec<u8>> {
+    let mut instructions = Vec::with_capacity(payload.len() + 3);
+    let blockhash = match lifetime {
+        TxLifetime::Blockhash(blockhash) => *blockhash,
+        TxLifetime::DurableNonce {
+            nonce_account,
+            authority,
+            nonce,
+        } => {
+            instructions.push(advance_nonce_instruction(nonce_account, authority)?);
+            *nonce
+        }
+    };
+    instructions.extend(compute_budget.instructions()?);
+    instructions.extend(payload);
+    if lookup_tables.is_empty() {
+        compile_legacy_message(payer, &instructions, &blockhash)
+    } else {
+        compile_v0_message(payer, &instructions, &blockhash, lookup_tables).map(|m| m.encode())
+    }
+}
+
+pub fn system_transfer_instruction(
+    from_pubkey: &[u8; 32],
+    to_pubkey: &[u8; 32],
+    lamports: u64,
+) -> Instruction {
     let mut data = Vec::with_capacity(12);
     data.extend_from_slice(&2u32.to_le_bytes());
     data.extend_from_slice(&lamports.to_le_bytes());
-    encode_shortvec_len(data.len(), &mut out);
-    out.extend_from_slice(&data);
+    Instruction {
+        program_id: SOLANA_SYSTEM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::signer(*from_pubkey, true),
+            AccountMeta::writable(*to_pubkey),
+        ],
+        data,
+    }
+}
 
-    out
+pub fn encode_system_transfer_message(
+    from_pubkey: &[u8; 32],
+    to_pubkey: &[u8; 32],
+    lifetime: &TxLifetime,
+    lamports: u64,
+    memo: Option<&[u8]>,
+    compute_budget: &ComputeBudget,
+    lookup_tables: &[AddressLookupTable],
+) -> WalletResult<Vec<u8>> {
+    let mut payload = Vec::with_capacity(2);
+    if let Some(memo) = memo {
+        payload.push(memo_instruction(memo)?);
+    }
+    payload.push(system_transfer_instruction(
+        from_pubkey,
+        to_pubkey,
+        lamports,
+    ));
+    compile_with_lifetime(
+        from_pubkey,
+        lifetime,
+        compute_budget,
+        lookup_tables,
+        payload,
+    )
+}
+
+/// Seed-derived nonce account of `authority`; creating it needs no extra keypair signature.
+pub fn derive_nonce_account(authority: &[u8; 32]) -> [u8; 32] {
+    create_with_seed(authority, NONCE_ACCOUNT_SEED, &SOLANA_SYSTEM_PROGRAM_ID)
+}
+
+pub fn create_with_seed(base: &[u8; 32], seed: &str, owner: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(base);
+    hasher.update(seed.as_bytes());
+    hasher.update(owner);
+    hasher.finalize().into()
+}
+
+/// `CreateAccountWithSeed` + `InitializeNonceAccount`, with `authority` paying and owning the nonce.
+pub fn encode_create_nonce_account_message(
+    authority: &[u8; 32],
+    rent_exempt_lamports: u64,
+    recent_blockhash: &[u8; 32],
+    compute_budget: &ComputeBudget,
+) -> WalletResult<Vec<u8>> {
+    let nonce_account = derive_nonce_account(authority);
+
+    let mut create_data = Vec::with_capacity(4 + 32 + 8 + NONCE_ACCOUNT_SEED.len() + 48);
+    create_data.extend_from_slice(&SYSTEM_CREATE_ACCOUNT_WITH_SEED.to_le_bytes());
+    create_data.extend_from_slice(authority);
+    create_data.extend_from_slice(&(NONCE_ACCOUNT_SEED.len() as u64).to_le_bytes());
+    create_data.extend_from_slice(NONCE_ACCOUNT_SEED.as_bytes());
+    create_data.extend_from_slice(&rent_exempt_lamports.to_le_bytes());
+    create_data.extend_from_slice(&NONCE_ACCOUNT_SPACE.to_le_bytes());
+    create_data.extend_from_slice(&SOLANA_SYSTEM_PROGRAM_ID);
+
+    let mut init_data = Vec::with_capacity(36);
+    init_data.extend_from_slice(&SYSTEM_INITIALIZE_NONCE_ACCOUNT.to_le_bytes());
+    init_data.extend_from_slice(authority);
+
+    let payload = vec![
+        Instruction {
+            program_id: SOLANA_SYSTEM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::signer(*authority, true),
+                AccountMeta::writable(nonce_account),
+                AccountMeta::signer(*authority, false),
+            ],
+            data: create_data,
+        },
+        Instruction {
+            program_id: SOLANA_SYSTEM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::writable(nonce_account),
+                AccountMeta::readonly(decode_solana_pubkey(SYSVAR_RECENT_BLOCKHASHES_BASE58)?),
+                AccountMeta::readonly(decode_solana_pubkey(SYSVAR_RENT_BASE58)?),
+            ],
+            data: init_data,
+        },
+    ];
+    compile_with_lifetime(
+        authority,
+        &TxLifetime::Blockhash(*recent_blockhash),
+        compute_budget,
+        &[],
+        payload,
+    )
+}
+
+fn is_advance_nonce_instruction(ix: &Instruction) -> bool {
+    ix.program_id == SOLANA_SYSTEM_PROGRAM_ID
+        && ix.data == SYSTEM_ADVANCE_NONCE_ACCOUNT.to_le_bytes()
+}
+
+fn advance_nonce_instruction(
+    nonce_account: &[u8; 32],
+    authority: &[u8; 32],
+) -> WalletResult<Instruction> {
+    Ok(Instruction {
+        program_id: SOLANA_SYSTEM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::writable(*nonce_account),
+            AccountMeta::readonly(decode_solana_pubkey(SYSVAR_RECENT_BLOCKHASHES_BASE58)?),
+            AccountMeta::signer(*authority, false),
+        ],
+        data: SYSTEM_ADVANCE_NONCE_ACCOUNT.to_le_bytes().to_vec(),
+    })
 }
 
 pub fn encode_signed_transaction(signature: &[u8], message: &[u8]) -> Vec<u8> {
@@ -60,85 +649,136 @@ pub fn encode_signed_transaction(signature: &[u8], message: &[u8]) -> Vec<u8> {
     out
 }
 
-#[allow(clippy::too_many_arguments)]
+pub struct SplTransfer {
+    pub owner: [u8; 32],
+    pub source_token_account: [u8; 32],
+    pub dest_token_account: [u8; 32],
+    pub destination_owner: [u8; 32],
+    pub mint: [u8; 32],
+    /// Tokenkeg or Token-2022, whichever owns the mint.
+    pub token_program_id: [u8; 32],
+    pub amount_raw: u64,
+    pub decimals: u8,
+    /// Fee withheld by a Token-2022 transfer-fee mint; switches to `TransferCheckedWithFee`.
+    pub transfer_fee: Option<u64>,
+    pub create_destination_ata: bool,
+    /// Placed immediately before the transfer, as the memo-required extension expects.
+    pub memo: Option<Vec<u8>>,
+}
+
 pub fn encode_spl_transfer_checked_message(
-    owner_pubkey: &[u8; 32],
-    source_token_account: &[u8; 32],
-    dest_token_account: &[u8; 32],
-    destination_owner: &[u8; 32],
-    mint: &[u8; 32],
-    recent_blockhash: &[u8; 32],
-    amount_raw: u64,
-    decimals: u8,
-    create_destination_ata: bool,
+    transfer: &SplTransfer,
+    lifetime: &TxLifetime,
+    compute_budget: &ComputeBudget,
+    lookup_tables: &[AddressLookupTable],
 ) -> WalletResult<Vec<u8>> {
-    let token_program_id = decode_solana_pubkey(SPL_TOKEN_PROGRAM_ID_BASE58)?;
-    let associated_token_program_id = if create_destination_ata {
-        Some(decode_solana_pubkey(
-            SPL_ASSOCIATED_TOKEN_PROGRAM_ID_BASE58,
-        )?)
-    } else {
-        None
-    };
-    let mut out = Vec::with_capacity(320);
-
-    out.push(1);
-    out.push(0);
-    out.push(if create_destination_ata { 5 } else { 2 });
+    let mut instructions = Vec::with_capacity(3);
 
-    encode_shortvec_len(if create_destination_ata { 8 } else { 5 }, &mut out);
-    out.extend_from_slice(owner_pubkey);
-    out.extend_from_slice(source_token_account);
-    out.extend_from_slice(dest_token_account);
-    out.extend_from_slice(mint);
-    if create_destination_ata {
-        out.extend_from_slice(destination_owner);
-        out.extend_from_slice(&SOLANA_SYSTEM_PROGRAM_ID);
+    if transfer.create_destination_ata {
+        // CreateIdempotent, so a racing creation of the same ATA does not fail the transfer.
+        instructions.push(Instruction {
+            program_id: decode_solana_pubkey(SPL_ASSOCIATED_TOKEN_PROGRAM_ID_BASE58)?,
+            accounts: vec![
+                AccountMeta::signer(transfer.owner, true),
+                AccountMeta::writable(transfer.dest_token_account),
+                AccountMeta::readonly(transfer.destination_owner),
+                AccountMeta::readonly(transfer.mint),
+                AccountMeta::readonly(SOLANA_SYSTEM_PROGRAM_ID),
+                AccountMeta::readonly(transfer.token_program_id),
+            ],
+            data: vec![1],
+        });
     }
-    out.extend_from_slice(&token_program_id);
-    if let Some(ata_program_id) = associated_token_program_id {
-        out.extend_from_slice(&ata_program_id);
-    }
-
-    out.extend_from_slice(recent_blockhash);
 
-    encode_shortvec_len(if create_destination_ata { 2 } else { 1 }, &mut out);
+    if let Some(memo) = transfer.memo.as_deref() {
+        instructions.push(memo_instruction(memo)?);
+    }
 
-    if create_destination_ata {
-        out.push(7);
-        encode_shortvec_len(6, &mut out);
-        out.push(0);
-        out.push(2);
-        out.push(4);
-        out.push(3);
-        out.push(5);
-        out.push(6);
-        encode_shortvec_len(1, &mut out);
-        out.push(1);
+    let mut data = Vec::with_capacity(19);
+    match transfer.transfer_fee {
+        Some(fee) => {
+            data.push(TOKEN_2022_TRANSFER_FEE_EXTENSION);
+            data.push(TOKEN_2022_TRANSFER_CHECKED_WITH_FEE);
+            data.extend_from_slice(&transfer.amount_raw.to_le_bytes());
+            data.push(transfer.decimals);
+            data.extend_from_slice(&fee.to_le_bytes());
+        }
+        None => {
+            data.push(SPL_TOKEN_TRANSFER_CHECKED);
+            data.extend_from_slice(&transfer.amount_raw.to_le_bytes());
+            data.push(transfer.decimals);
+        }
     }
+    instructions.push(Instruction {
+        program_id: transfer.token_program_id,
+        accounts: vec![
+            AccountMeta::writable(transfer.source_token_account),
+            AccountMeta::readonly(transfer.mint),
+            AccountMeta::writable(transfer.dest_token_account),
+            AccountMeta::signer(transfer.owner, false),
+        ],
+        data,
+    });
 
-    out.push(if create_destination_ata { 6 } else { 4 });
-    encode_shortvec_len(4, &mut out);
-    out.push(1);
-    out.push(3);
-    out.push(2);
-    out.push(0);
+    compile_with_lifetime(
+        &transfer.owner,
+        lifetime,
+        compute_budget,
+        lookup_tables,
+        instructions,
+    )
+}
 
-    let mut data = Vec::with_capacity(10);
-    data.push(12);
-    data.extend_from_slice(&amount_raw.to_le_bytes());
-    data.push(decimals);
-    encode_shortvec_len(data.len(), &mut out);
-    out.extend_from_slice(&data);
+/// One `CloseAccount` per `(token account, token program)`, returning the rent to `owner`.
+pub fn encode_close_token_accounts_message(
+    owner: &[u8; 32],
+    accounts: &[([u8; 32], [u8; 32])],
+    recent_blockhash: &[u8; 32],
+    compute_budget: &ComputeBudget,
+) -> WalletResult<Vec<u8>> {
+    let payload = accounts
+        .iter()
+        .map(|(account, token_program_id)| Instruction {
+            program_id: *token_program_id,
+            accounts: vec![
+                AccountMeta::writable(*account),
+                AccountMeta::writable(*owner),
+                AccountMeta::signer(*owner, false),
+            ],
+            data: vec![SPL_TOKEN_CLOSE_ACCOUNT],
+        })
+        .collect();
+    compile_with_lifetime(
+        owner,
+        &TxLifetime::Blockhash(*recent_blockhash),
+        compute_budget,
+        &[],
+        payload,
+    )
+}
 
-    Ok(out)
+pub fn memo_instruction(memo: &[u8]) -> WalletResult<Instruction> {
+    Ok(Instruction {
+        program_id: decode_solana_pubkey(SPL_MEMO_PROGRAM_ID_BASE58)?,
+        accounts: Vec::new(),
+        data: memo.to_vec(),
+    })
+}
+
+/// Token-2022 transfer fee: `ceil(amount * bps / 10_000)`, capped at `maximum_fee`.
+pub fn calculate_transfer_fee(amount: u64, basis_points: u16, maximum_fee: u64) -> u64 {
+    if amount == 0 || basis_points == 0 {
+        return 0;
+    }
+    let fee = (u128::from(amount) * u128::from(basis_points)).div_ceil(10_000);
+    u64::try_from(fee).unwrap_or(u64::MAX).min(maximum_fee)
 }
 
 pub fn derive_associated_token_address(
     owner: &[u8; 32],
     mint: &[u8; 32],
+    token_program_id: &[u8; 32],
 ) -> WalletResult<[u8; 32]> {
-    let token_program_id = decode_solana_pubkey(SPL_TOKEN_PROGRAM_ID_BASE58)?;
     let ata_program_id = decode_solana_pubkey(SPL_ASSOCIATED_TOKEN_PROGRAM_ID_BASE58)?;
     find_program_address(
         &[
@@ -150,6 +790,94 @@ pub fn derive_associated_token_address(
     )
 }
 
+/// Name account for `<label>.sol` in the SPL Name Service (no name class).
+pub fn derive_sns_domain_account(label: &str) -> WalletResult<[u8; 32]> {
+    let sol_tld = decode_solana_pubkey(SNS_SOL_TLD_BASE58)?;
+    derive_sns_name_account(label.as_bytes(), &[0u8; 32], &sol_tld)
+}
+
+/// Legacy (V1) `SOL` record of a domain: `destination (32) | ed25519 signature (64)`.
+pub fn derive_sns_sol_record_v1(domain: &[u8; 32]) -> WalletResult<[u8; 32]> {
+    derive_sns_name_account(b"\x01SOL", &[0u8; 32], domain)
+}
+
+/// SNS Records V2 `SOL` record of a domain, owned by the records central state class.
+pub fn derive_sns_sol_record_v2(domain: &[u8; 32]) -> WalletResult<[u8; 32]> {
+    let central_state = decode_solana_pubkey(SNS_RECORDS_CENTRAL_STATE_BASE58)?;
+    derive_sns_name_account(b"\x02SOL", &central_state, domain)
+}
+
+/// Escrow that owns a domain while it is wrapped as an NFT by the name tokenizer.
+pub fn derive_sns_tokenizer_escrow(domain: &[u8; 32]) -> WalletResult<[u8; 32]> {
+    let tokenizer = decode_solana_pubkey(SNS_NAME_TOKENIZER_PROGRAM_ID_BASE58)?;
+    find_program_address(&[b"nft_record".as_slice(), domain.as_slice()], &tokenizer)
+}
+
+fn derive_sns_name_account(
+    name: &[u8],
+    class: &[u8; 32],
+    parent: &[u8; 32],
+) -> WalletResult<[u8; 32]> {
+    let name_program_id = decode_solana_pubkey(SNS_NAME_PROGRAM_ID_BASE58)?;
+    let mut hasher = Sha256::new();
+    hasher.update(SNS_HASH_PREFIX);
+    hasher.update(name);
+    let hashed_name = hasher.finalize();
+    find_program_address(
+        &[hashed_name.as_slice(), class.as_slice(), parent.as_slice()],
+        &name_program_id,
+    )
+}
+
+/// Destination of a V2 `SOL` record, only if the current domain owner signed it (staleness) and
+/// the destination key itself did (right of association).
+pub fn parse_sns_sol_record_v2(data: &[u8], domain_owner: &[u8; 32]) -> Option<[u8; 32]> {
+    let header = data.get(SNS_NAME_HEADER_LEN..SNS_NAME_HEADER_LEN + SNS_RECORD_V2_HEADER_LEN)?;
+    let staleness = u16::from_le_bytes([header[0], header[1]]);
+    let right_of_association = u16::from_le_bytes([header[2], header[3]]);
+    let content_len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+    let staleness_len = sns_validation_id_len(staleness)?;
+    let roa_len = sns_validation_id_len(right_of_association)?;
+    let body = &data[SNS_NAME_HEADER_LEN + SNS_RECORD_V2_HEADER_LEN..];
+    let staleness_id = body.get(..staleness_len)?;
+    let roa_id = body.get(staleness_len..staleness_len + roa_len)?;
+    let content = body.get(staleness_len + roa_len..staleness_len + roa_len + content_len)?;
+    let destination: [u8; 32] = content.try_into().ok()?;
+    let fresh = staleness == SNS_VALIDATION_SOLANA && staleness_id == domain_owner.as_slice();
+    let associated =
+        right_of_association == SNS_VALIDATION_SOLANA && roa_id == destination.as_slice();
+    (fresh && associated).then_some(destination)
+}
+
+/// Destination of a V1 `SOL` record, only if its signature by the current domain owner over
+/// `hex(destination || record_key)` verifies.
+pub fn parse_sns_sol_record_v1(
+    data: &[u8],
+    record_key: &[u8; 32],
+    domain_owner: &[u8; 32],
+) -> Option<[u8; 32]> {
+    let content = data.get(SNS_NAME_HEADER_LEN..SNS_NAME_HEADER_LEN + 96)?;
+    let destination: [u8; 32] = content[..32].try_into().ok()?;
+    let signature = Signature::from_slice(&content[32..]).ok()?;
+    let mut signed = destination.to_vec();
+    signed.extend_from_slice(record_key);
+    let expected = crate::addressing::hex_encode(&signed);
+    VerifyingKey::from_bytes(domain_owner)
+        .ok()?
+        .verify(expected.as_bytes(), &signature)
+        .ok()
+        .map(|_| destination)
+}
+
+fn sns_validation_id_len(validation: u16) -> Option<usize> {
+    match validation {
+        SNS_VALIDATION_NONE => Some(0),
+        SNS_VALIDATION_SOLANA | SNS_VALIDATION_UNVERIFIED_SOLANA => Some(32),
+        SNS_VALIDATION_ETHEREUM => Some(20),
+        _ => None,
+    }
+}
+
 fn base58_decode(input: &str) -> WalletResult<Vec<u8>> {
     if input.is_empty() {
         return Err(WalletError::invalid_input("base58 string is required"));
@@ -242,3 +970,351 @@ fn encode_shortvec_len(mut value: usize, out: &mut Vec<u8>) {
         out.push(elem);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compiles_system_transfer_in_legacy_layout() {
+        let from = [1u8; 32];
+        let to = [2u8; 32];
+        let blockhash = [3u8; 32];
+        let message = compile_legacy_message(
+            &from,
+            &[system_transfer_instruction(&from, &to, 5_000)],
+            &blockhash,
+        )
+        .unwrap();
+
+        let mut expected = vec![1, 0, 1, 3];
+        expected.extend_from_slice(&from);
+        expected.extend_from_slice(&to);
+        expected.extend_from_slice(&SOLANA_SYSTEM_PROGRAM_ID);
+        expected.extend_from_slice(&blockhash);
+        expected.extend_from_slice(&[1, 2, 2, 0, 1, 12]);
+        expected.extend_from_slice(&2u32.to_le_bytes());
+        expected.extend_from_slice(&5_000u64.to_le_bytes());
+        assert_eq!(message, expected);
+    }
+
+    #[test]
+    fn prepends_compute_budget_instructions() {
+        let budget = ComputeBudget {
+            unit_limit: 1_000,
+            unit_price_micro_lamports: 25_000,
+        };
+        let ixs = budget.instructions().unwrap();
+        assert_eq!(ixs.len(), 2);
+        assert_eq!(ixs[0].data, [2, 0xe8, 0x03, 0, 0]);
+        assert_eq!(ixs[1].data, [3, 0xa8, 0x61, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(budget.max_priority_fee_lamports(), 25);
+
+        let free = ComputeBudget {
+            unit_price_micro_lamports: 0,
+            ..budget
+        };
+        assert_eq!(free.instructions().unwrap().len(), 1);
+
+        let from = [1u8; 32];
+        let message = encode_system_transfer_message(
+            &from,
+            &[2u8; 32],
+            &TxLifetime::Blockhash([3u8; 32]),
+            1,
+            None,
+            &budget,
+            &[],
+        )
+        .unwrap();
+        // payer, recipient, then the read-only ComputeBudget and System programs.
+        assert_eq!(&message[..4], &[1, 0, 2, 4]);
+        let compute_budget_program =
+            decode_solana_pubkey(COMPUTE_BUDGET_PROGRAM_ID_BASE58).unwrap();
+        assert_eq!(&message[4 + 64..4 + 96], &compute_budget_program);
+        assert_eq!(message[4 + 128 + 32], 3, "three instructions");
+        assert_eq!(
+            message[4 + 128 + 33],
+            2,
+            "first instruction targets ComputeBudget"
+        );
+    }
+
+    #[test]
+    fn token_2022_transfer_fee_and_memo_layout() {
+        assert_eq!(calculate_transfer_fee(1_000_000, 50, u64::MAX), 5_000);
+        assert_eq!(calculate_transfer_fee(1, 50, u64::MAX), 1);
+        assert_eq!(calculate_transfer_fee(1_000_000, 50, 3_000), 3_000);
+        assert_eq!(calculate_transfer_fee(1_000_000, 0, 3_000), 0);
+
+        let token_2022 = decode_solana_pubkey(TOKEN_2022_PROGRAM_ID_BASE58).unwrap();
+        let transfer = SplTransfer {
+            owner: [1u8; 32],
+            source_token_account: [2u8; 32],
+            dest_token_account: [3u8; 32],
+            destination_owner: [4u8; 32],
+            mint: [5u8; 32],
+            token_program_id: token_2022,
+            amount_raw: 1_000_000,
+            decimals: 6,
+            transfer_fee: Some(5_000),
+            create_destination_ata: false,
+            memo: Some(b"invoice-7".to_vec()),
+        };
+        let budget = ComputeBudget {
+            unit_limit: 20_000,
+            unit_price_micro_lamports: 0,
+        };
+        let message = encode_spl_transfer_checked_message(
+            &transfer,
+            &TxLifetime::Blockhash([0u8; 32]),
+            &budget,
+            &[],
+        )
+        .unwrap();
+
+        let mut transfer_data = vec![26, 1];
+        transfer_data.extend_from_slice(&1_000_000u64.to_le_bytes());
+        transfer_data.push(6);
+        transfer_data.extend_from_slice(&5_000u64.to_le_bytes());
+        // Keys: owner, source, dest, ComputeBudget, Memo, mint, Token-2022.
+        let memo_then_transfer = [
+            &[4, 0, 9][..],
+            b"invoice-7",
+            &[6, 4, 1, 5, 2, 0, transfer_data.len() as u8],
+            &transfer_data,
+        ]
+        .concat();
+        assert!(message.ends_with(&memo_then_transfer));
+
+        let ata = derive_associated_token_address(&[4u8; 32], &[5u8; 32], &token_2022).unwrap();
+        let classic_program = decode_solana_pubkey(SPL_TOKEN_PROGRAM_ID_BASE58).unwrap();
+        let classic = derive_associated_token_address(&[4u8; 32], &[5u8; 32], &classic_program);
+        assert_ne!(ata, classic.unwrap());
+    }
+
+    #[test]
+    fn system_transfer_carries_memo() {
+        let budget = ComputeBudget {
+            unit_limit: 1_000,
+            unit_price_micro_lamports: 0,
+        };
+        let message = encode_system_transfer_message(
+            &[1u8; 32],
+            &[2u8; 32],
+            &TxLifetime::Blockhash([3u8; 32]),
+            1,
+            Some(b"deposit-42"),
+            &budget,
+            &[],
+        )
+        .unwrap();
+        // Keys: payer, recipient, ComputeBudget, Memo, System.
+        assert_eq!(&message[..4], &[1, 0, 3, 5]);
+        let memo_program = decode_solana_pubkey(SPL_MEMO_PROGRAM_ID_BASE58).unwrap();
+        assert_eq!(&message[4 + 96..4 + 128], &memo_program);
+        let memo_ix = [&[3, 0, 10][..], b"deposit-42"].concat();
+        let ixs = &message[4 + 160 + 32..];
+        assert_eq!(ixs[0], 3, "compute limit, memo, transfer");
+        assert_eq!(&ixs[1 + 8..1 + 8 + memo_ix.len()], memo_ix.as_slice());
+    }
+
+    #[test]
+    fn durable_nonce_advances_first_and_replaces_blockhash() {
+        let authority = [1u8; 32];
+        let nonce_account = derive_nonce_account(&authority);
+        let budget = ComputeBudget {
+            unit_limit: 2_000,
+            unit_price_micro_lamports: 0,
+        };
+        let message = encode_system_transfer_message(
+            &authority,
+            &[2u8; 32],
+            &TxLifetime::DurableNonce {
+                nonce_account,
+                authority,
+                nonce: [9u8; 32],
+            },
+            1,
+            None,
+            &budget,
+            &[],
+        )
+        .unwrap();
+        // Keys: authority, nonce account, recipient, RecentBlockhashes, System, ComputeBudget.
+        assert_eq!(&message[..4], &[1, 0, 3, 6]);
+        assert_eq!(&message[4 + 32..4 + 64], &nonce_account);
+        let blockhash_at = 4 + 6 * 32;
+        assert_eq!(&message[blockhash_at..blockhash_at + 32], &[9u8; 32]);
+        let ixs = &message[blockhash_at + 32..];
+        assert_eq!(ixs[0], 3);
+        assert_eq!(&ixs[1..11], &[4, 3, 1, 3, 0, 4, 4, 0, 0, 0]);
+
+        let create =
+            encode_create_nonce_account_message(&authority, 1_447_680, &[7u8; 32], &budget)
+                .unwrap();
+        // Only the authority signs; the seed-derived account needs no keypair.
+        assert_eq!(&create[..3], &[1, 0, 4]);
+        assert_eq!(&create[4 + 32..4 + 64], &nonce_account);
+    }
+
+    #[test]
+    fn compiles_v0_message_with_lookup_table() {
+        let payer = [1u8; 32];
+        let nonce_account = derive_nonce_account(&payer);
+        let table = AddressLookupTable {
+            key: [8u8; 32],
+            addresses: vec![[7u8; 32], [2u8; 32], nonce_account, [4u8; 32]],
+        };
+        let budget = ComputeBudget {
+            unit_limit: 2_000,
+            unit_price_micro_lamports: 0,
+        };
+        let ix = Instruction {
+            program_id: [9u8; 32],
+            accounts: vec![
+                AccountMeta::writable([2u8; 32]),
+                AccountMeta::readonly([4u8; 32]),
+                AccountMeta::signer(payer, true),
+            ],
+            data: vec![0xaa],
+        };
+        let lifetime = TxLifetime::DurableNonce {
+            nonce_account,
+            authority: payer,
+            nonce: [3u8; 32],
+        };
+        let encoded = compile_with_lifetime(
+            &payer,
+            &lifetime,
+            &budget,
+            std::slice::from_ref(&table),
+            vec![ix],
+        )
+        .unwrap();
+        let message = V0Message::parse(&encoded).unwrap();
+        assert_eq!(message.encode(), encoded);
+
+        // The nonce account stays static even though the table lists it.
+        assert_eq!(message.static_keys[1], nonce_account);
+        assert_eq!(message.static_keys.len(), 6);
+        assert_eq!(message.num_readonly_unsigned, 4);
+        assert_eq!(message.recent_blockhash, [3u8; 32]);
+        assert_eq!(
+            message.address_table_lookups,
+            vec![MessageAddressTableLookup {
+                account_key: table.key,
+                writable_indexes: vec![1],
+                readonly_indexes: vec![3],
+            }]
+        );
+        let last = message.instructions.last().unwrap();
+        assert_eq!(last.account_indexes, vec![6, 7, 0]);
+        assert_eq!(
+            message.static_keys[usize::from(last.program_id_index)],
+            [9u8; 32]
+        );
+
+        let mut trailing = encoded.clone();
+        trailing.push(0);
+        assert!(V0Message::parse(&trailing).is_err());
+        assert!(V0Message::parse(&encoded[..encoded.len() - 1]).is_err());
+        let legacy = compile_legacy_message(&payer, &[], &[0u8; 32]).unwrap();
+        assert!(V0Message::parse(&legacy).is_err());
+    }
+
+    #[test]
+    fn closes_token_accounts_back_to_owner() {
+        let owner = [1u8; 32];
+        let classic = decode_solana_pubkey(SPL_TOKEN_PROGRAM_ID_BASE58).unwrap();
+        let token_2022 = decode_solana_pubkey(TOKEN_2022_PROGRAM_ID_BASE58).unwrap();
+        let budget = ComputeBudget {
+            unit_limit: 10_000,
+            unit_price_micro_lamports: 0,
+        };
+        let message = encode_close_token_accounts_message(
+            &owner,
+            &[([2u8; 32], classic), ([3u8; 32], token_2022)],
+            &[0u8; 32],
+            &budget,
+        )
+        .unwrap();
+        // Keys: owner, two token accounts, ComputeBudget, Tokenkeg, Token-2022.
+        assert_eq!(&message[..4], &[1, 0, 3, 6]);
+        let ixs = &message[4 + 6 * 32 + 32..];
+        assert_eq!(ixs[0], 3);
+        assert!(ixs.ends_with(&[4, 3, 1, 0, 0, 1, 9, 5, 3, 2, 0, 0, 1, 9]));
+    }
+
+    #[test]
+    fn rejects_extra_signers() {
+        let ix = Instruction {
+            program_id: [9u8; 32],
+            accounts: vec![AccountMeta::signer([4u8; 32], false)],
+            data: Vec::new(),
+        };
+        assert!(compile_legacy_message(&[1u8; 32], &[ix], &[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn derives_sns_domain_account() {
+        assert_eq!(
+            derive_sns_domain_account("bonfida").unwrap(),
+            decode_solana_pubkey("Crf8hzfthWGbGbLTVCiqRqV5MVnbpHB1L9KQMd6gsinb").unwrap()
+        );
+    }
+
+    fn sns_record_v2(staleness: (u16, &[u8]), roa: (u16, &[u8]), content: &[u8]) -> Vec<u8> {
+        let mut data = vec![0u8; SNS_NAME_HEADER_LEN];
+        data.extend_from_slice(&staleness.0.to_le_bytes());
+        data.extend_from_slice(&roa.0.to_le_bytes());
+        data.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        data.extend_from_slice(staleness.1);
+        data.extend_from_slice(roa.1);
+        data.extend_from_slice(content);
+        data
+    }
+
+    #[test]
+    fn sns_v2_sol_record_requires_current_owner_and_destination_validation() {
+        let owner = [1u8; 32];
+        let dest = [2u8; 32];
+        let valid = sns_record_v2((1, &owner), (1, &dest), &dest);
+        assert_eq!(parse_sns_sol_record_v2(&valid, &owner), Some(dest));
+        // Signed by a previous owner: stale.
+        assert_eq!(parse_sns_sol_record_v2(&valid, &[9u8; 32]), None);
+        let no_roa = sns_record_v2((1, &owner), (0, &[]), &dest);
+        assert_eq!(parse_sns_sol_record_v2(&no_roa, &owner), None);
+        let eth_roa = sns_record_v2((1, &owner), (2, &[2u8; 20]), &dest);
+        assert_eq!(parse_sns_sol_record_v2(&eth_roa, &owner), None);
+        let truncated = &valid[..valid.len() - 1];
+        assert_eq!(parse_sns_sol_record_v2(truncated, &owner), None);
+    }
+
+    #[test]
+    fn sns_v1_sol_record_requires_owner_signature() {
+        use ed25519_dalek::{Signer, SigningKey};
+        let owner_key = SigningKey::from_bytes(&[7u8; 32]);
+        let owner = owner_key.verifying_key().to_bytes();
+        let record_key = [5u8; 32];
+        let dest = [2u8; 32];
+        let mut signed = dest.to_vec();
+        signed.extend_from_slice(&record_key);
+        let signature = owner_key.sign(crate::addressing::hex_encode(&signed).as_bytes());
+        let mut data = vec![0u8; SNS_NAME_HEADER_LEN];
+        data.extend_from_slice(&dest);
+        data.extend_from_slice(&signature.to_bytes());
+        assert_eq!(
+            parse_sns_sol_record_v1(&data, &record_key, &owner),
+            Some(dest)
+        );
+        assert_eq!(parse_sns_sol_record_v1(&data, &[6u8; 32], &owner), None);
+        let other_owner = SigningKey::from_bytes(&[8u8; 32])
+            .verifying_key()
+            .to_bytes();
+        assert_eq!(
+            parse_sns_sol_record_v1(&data, &record_key, &other_owner),
+            None
+        );
+    }
+}
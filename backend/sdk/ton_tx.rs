@@ -781,6 +781,10 @@ fn read_be_var(data: &[u8], p: &mut usize, width: usize) -> WalletResult<u64> {
     Ok(out)
 }
 
+pub fn base64_encode_std(data: &[u8]) -> String {
+    base64_encode(data, BASE64_STD_ALPHABET, true)
+}
+
 pub fn base64_encode_std_nopad(data: &[u8]) -> String {
     base64_encode(data, BASE64_STD_ALPHABET, false)
 }
@@ -827,7 +831,7 @@ fn base64_encode(data: &[u8], alphabet: &[u8; 64], pad: bool) -> String {
     out
 }
 
-fn base64_decode_url_or_std(text: &str) -> WalletResult<Vec<u8>> {
+pub fn base64_decode_url_or_std(text: &str) -> WalletResult<Vec<u8>> {
     let mut filtered = text.trim().as_bytes().to_vec();
     filtered.retain(|b| !b" \n\r\t".contains(b));
     while filtered.len() % 4 != 0 {
@@ -6,14 +6,25 @@ use serde_json::{json, Value};
 use crate::addressing;
 use crate::config;
 use crate::error::{WalletError, WalletResult};
-use crate::sdk::sol_tx;
+use crate::sdk::{sol_tx, ton_tx};
 use crate::types::{
     self, AddressResponse, BalanceRequest, BalanceResponse, BroadcastHttpRequest,
-    ConfiguredTokenResponse, TransferRequest, TransferResponse,
+    ConfiguredTokenResponse, SolanaCloseTokenAccountsResponse, SolanaNonceAccountResponse,
+    TransferRequest, TransferResponse,
 };
 
 const NETWORK_NAME: &str = types::networks::SOLANA;
 const SOL_DECIMALS: u8 = 9;
+const SOL_TRANSFER_COMPUTE_UNITS: u32 = 1_000;
+const SPL_TRANSFER_COMPUTE_UNITS: u32 = 20_000;
+const SPL_TRANSFER_WITH_ATA_COMPUTE_UNITS: u32 = 60_000;
+const MEMO_COMPUTE_UNITS: u32 = 10_000;
+const ADVANCE_NONCE_COMPUTE_UNITS: u32 = 1_000;
+const CREATE_NONCE_ACCOUNT_COMPUTE_UNITS: u32 = 5_000;
+const CLOSE_ACCOUNT_COMPUTE_UNITS: u32 = 5_000;
+// Keeps each close transaction well inside the 1232-byte packet limit.
+const CLOSE_ACCOUNTS_PER_TX: usize = 20;
+const SOLANA_MAX_TX_BYTES: usize = 1232;
 const BASE64_ALPHABET: &[u8; 64] =
     b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
 
@@ -37,6 +48,53 @@ struct SolanaJsonRpcError {
     message: String,
 }
 
+struct SplMint {
+    token_program_id: [u8; 32],
+    decimals: u8,
+    transfer_fee: Option<SplTransferFeeConfig>,
+}
+
+struct SplTransferFeeConfig {
+    older: SplTransferFee,
+    newer: SplTransferFee,
+}
+
+struct SplTransferFee {
+    epoch: u64,
+    maximum_fee: u64,
+    basis_points: u16,
+}
+
+struct NonceState {
+    authority: [u8; 32],
+    nonce: [u8; 32],
+    lamports: u64,
+}
+
+struct SplTokenAccount {
+    pubkey: [u8; 32],
+    requires_incoming_memo: bool,
+}
+
+impl SplMint {
+    fn is_token_2022(&self) -> bool {
+        decode_solana_pubkey(sol_tx::TOKEN_2022_PROGRAM_ID_BASE58)
+            .is_ok_and(|id| id == self.token_program_id)
+    }
+}
+
+impl SplTransferFeeConfig {
+    /// The newer fee schedule applies from its epoch onwards.
+    fn fee_for(&self, epoch: u64, amount: u64) -> u64 {
+        let schedule = if epoch >= self.newer.epoch {
+            &self.newer
+        } else {
+            &self.older
+        };
+        sol_tx::calculate_transfer_fee(amount, schedule.basis_points, schedule.maximum_fee)
+    }
+}
+
 pub async fn request_address() -> WalletResult<AddressResponse> {
     request_address_for_network(NETWORK_NAME).await
 }
@@ -80,13 +138,13 @@ pub async fn get_balance_for_network(
     {
         let owner = decode_solana_pubkey(&req.account)?;
         let mint = decode_solana_pubkey(token_text)?;
-        let decimals = fetch_spl_decimals(network_name, &mint).await?;
+        let decimals = fetch_spl_mint(network_name, &mint).await?.decimals;
         let maybe_token_account =
             fetch_token_account_for_owner_optional(network_name, &owner, &mint).await?;
 
         let (amount, block_ref, message) = match maybe_token_account {
             Some(token_account) => {
-                let token_account_b58 = addressing::base58_encode(&token_account);
+                let token_account_b58 = addressing::base58_encode(&token_account.pubkey);
                 let rpc_result = solana_rpc_call(
                     network_name,
                     "getTokenAccountBalance",
@@ -217,13 +275,33 @@ pub async fn transfer_sol_for_network(
     }
 
     let to_pubkey = decode_solana_pubkey(&req.to)?;
-    let recent_blockhash = fetch_recent_blockhash(network_name).await?;
+    let memo = transfer_memo(&req);
+    let lifetime = resolve_tx_lifetime(network_name, &req.metadata, &from_pubkey).await?;
+    let lookup_tables = resolve_lookup_tables(network_name, &req.metadata).await?;
+    let mut unit_limit = SOL_TRANSFER_COMPUTE_UNITS;
+    if memo.is_some() {
+        unit_limit += MEMO_COMPUTE_UNITS;
+    }
+    if matches!(lifetime, sol_tx::TxLifetime::DurableNonce { .. }) {
+        unit_limit += ADVANCE_NONCE_COMPUTE_UNITS;
+    }
+    let compute_budget = resolve_compute_budget(
+        network_name,
+        &req.metadata,
+        unit_limit,
+        &[from_pubkey, to_pubkey],
+    )
+    .await?;
     let message = sol_tx::encode_system_transfer_message(
         &from_pubkey,
         &to_pubkey,
-        &recent_blockhash,
+        &lifetime,
         amount_lamports,
-    );
+        memo.map(str::as_bytes),
+        &compute_budget,
+        &lookup_tables,
+    )?;
+    ensure_transaction_size(&message)?;
     let signature = sign_solana_message(&message).await?;
     if signature.len() != 64 {
         return Err(WalletError::Internal(format!(
@@ -245,8 +323,11 @@ pub async fn transfer_sol_for_network(
         signed_tx_encoding: Some("base64".to_string()),
         broadcast_request: Some(broadcast_request),
         message: format!(
-            "signed Solana transaction prepared; frontend should broadcast via sendTransaction: {tx_sig}"
+            "signed Solana transaction prepared; frontend should broadcast via sendTransaction: {tx_sig} ({}{})",
+            describe_compute_budget(&compute_budget),
+            describe_lifetime(&lifetime)
         ),
+        resolved_to: None,
     })
 }
 
@@ -261,8 +342,8 @@ pub async fn transfer_spl_for_network(
         .ok_or_else(|| WalletError::invalid_input("token (SPL mint) is required"))?;
     let mint = decode_solana_pubkey(mint_text)?;
     let destination_owner = decode_solana_pubkey(&req.to)?;
-    let amount_decimals = fetch_spl_decimals(network_name, &mint).await?;
-    let amount_raw = parse_decimal_u64_units(&req.amount, amount_decimals)?;
+    let spl_mint = fetch_spl_mint(network_name, &mint).await?;
+    let amount_raw = parse_decimal_u64_units(&req.amount, spl_mint.decimals)?;
     if amount_raw == 0 {
         return Err(WalletError::invalid_input("amount must be > 0"));
     }
@@ -291,27 +372,151 @@ pub async fn transfer_spl_for_network(
 
     let source_token_account =
         fetch_token_account_for_owner(network_name, &owner_pubkey, &mint).await?;
-    let (dest_token_account, create_destination_ata) =
+    let (dest_token_account, create_destination_ata, memo_required) =
         match fetch_token_account_for_owner_optional(network_name, &destination_owner, &mint)
             .await?
         {
-            Some(account) => (account, false),
+            Some(account) => (account.pubkey, false, account.requires_incoming_memo),
             None => (
-                sol_tx::derive_associated_token_address(&destination_owner, &mint)?,
+                sol_tx::derive_associated_token_address(
+                    &destination_owner,
+                    &mint,
+                    &spl_mint.token_program_id,
+                )?,
                 true,
+                false,
             ),
         };
-    let recent_blockhash = fetch_recent_blockhash(network_name).await?;
+    let memo = transfer_memo(&req);
+    if memo_required && memo.is_none() {
+        return Err(WalletError::invalid_input(
+            "destination token account requires a memo on incoming transfers",
+        ));
+    }
+    let transfer_fee = match spl_mint.transfer_fee.as_ref() {
+        Some(config) => Some(config.fee_for(fetch_epoch(network_name).await?, amount_raw)),
+        None => None,
+    };
+
+    let mut unit_limit = if create_destination_ata {
+        SPL_TRANSFER_WITH_ATA_COMPUTE_UNITS
+    } else {
+        SPL_TRANSFER_COMPUTE_UNITS
+    };
+    if memo.is_some() {
+        unit_limit += MEMO_COMPUTE_UNITS;
+    }
+    let lifetime = resolve_tx_lifetime(network_name, &req.metadata, &owner_pubkey).await?;
+    let lookup_tables = resolve_lookup_tables(network_name, &req.metadata).await?;
+    if matches!(lifetime, sol_tx::TxLifetime::DurableNonce { .. }) {
+        unit_limit += ADVANCE_NONCE_COMPUTE_UNITS;
+    }
+    let compute_budget = resolve_compute_budget(
+        network_name,
+        &req.metadata,
+        unit_limit,
+        &[owner_pubkey, source_token_account, dest_token_account],
+    )
+    .await?;
     let message = sol_tx::encode_spl_transfer_checked_message(
-        &owner_pubkey,
-        &source_token_account,
-        &dest_token_account,
-        &destination_owner,
-        &mint,
+        &sol_tx::SplTransfer {
+            owner: owner_pubkey,
+            source_token_account,
+            dest_token_account,
+            destination_owner,
+            mint,
+            token_program_id: spl_mint.token_program_id,
+            amount_raw,
+            decimals: spl_mint.decimals,
+            transfer_fee,
+            create_destination_ata,
+            memo: memo.map(|m| m.as_bytes().to_vec()),
+        },
+        &lifetime,
+        &compute_budget,
+        &lookup_tables,
+    )?;
+    ensure_transaction_size(&message)?;
+    let signature = sign_solana_message(&message).await?;
+    if signature.len() != 64 {
+        return Err(WalletError::Internal(format!(
+            "unexpected ed25519 signature length: {}",
+            signature.len()
+        )));
+    }
+    let raw_tx = sol_tx::encode_signed_transaction(&signature, &message);
+    let tx_sig = addressing::base58_encode(&signature);
+    let (raw_tx_b64, broadcast_request) =
+        build_send_raw_transaction_request(network_name, &raw_tx)?;
+
+    Ok(TransferResponse {
+        network: network_name.to_string(),
+        accepted: false,
+        tx_id: Some(tx_sig.clone()),
+        signed_tx: Some(raw_tx_b64),
+        signed_tx_encoding: Some("base64".to_string()),
+        broadcast_request: Some(broadcast_request),
+        message: format!(
+            "signed SPL transfer prepared; frontend should broadcast via sendTransaction: {tx_sig} ({}{}{})",
+            describe_compute_budget(&compute_budget),
+            describe_lifetime(&lifetime),
+            transfer_fee
+                .map(|fee| format!(
+                    ", Token-2022 transfer fee withheld from recipient: {}",
+                    format_u64_units(fee, spl_mint.decimals)
+                ))
+                .unwrap_or_default()
+        ),
+        resolved_to: None,
+    })
+}
+
+pub async fn create_nonce_account() -> WalletResult<TransferResponse> {
+    create_nonce_account_for_network(NETWORK_NAME).await
+}
+
+pub async fn nonce_account() -> WalletResult<SolanaNonceAccountResponse> {
+    nonce_account_for_network(NETWORK_NAME).await
+}
+
+/// Signs creation of the managed key's seed-derived durable nonce account (rent-exempt, 80 bytes).
+pub async fn create_nonce_account_for_network(
+    network_name: &str,
+) -> WalletResult<TransferResponse> {
+    let authority = fetch_managed_pubkey().await?;
+    let nonce_account = sol_tx::derive_nonce_account(&authority);
+    let nonce_b58 = addressing::base58_encode(&nonce_account);
+    if fetch_account_value(network_name, &nonce_account, "base64")
+        .await?
+        .is_some()
+    {
+        return Err(WalletError::invalid_input(format!(
+            "durable nonce account {nonce_b58} already exists"
+        )));
+    }
+    let rent_lamports = solana_rpc_call(
+        network_name,
+        "getMinimumBalanceForRentExemption",
+        json!([sol_tx::NONCE_ACCOUNT_SPACE]),
+    )
+    .await?
+    .as_u64()
+    .ok_or_else(|| {
+        WalletError::Internal("solana rpc getMinimumBalanceForRentExemption is not u64".into())
+    })?;
+    let compute_budget = resolve_compute_budget(
+        network_name,
+        &[],
+        CREATE_NONCE_ACCOUNT_COMPUTE_UNITS,
+        &[authority, nonce_account],
+    )
+    .await?;
+    let recent_blockhash = fetch_recent_blockhash(network_name).await?;
+    let message = sol_tx::encode_create_nonce_account_message(
+        &authority,
+        rent_lamports,
         &recent_blockhash,
-        amount_raw,
-        amount_decimals,
-        create_destination_ata,
+        &compute_budget,
     )?;
     let signature = sign_solana_message(&message).await?;
     if signature.len() != 64 {
@@ -333,8 +538,273 @@ pub async fn transfer_spl_for_network(
         signed_tx_encoding: Some("base64".to_string()),
         broadcast_request: Some(broadcast_request),
         message: format!(
-            "signed SPL transfer prepared; frontend should broadcast via sendTransaction: {tx_sig}"
+            "signed durable nonce account creation for {nonce_b58} (rent {} SOL); frontend should broadcast via sendTransaction: {tx_sig}",
+            format_lamports(rent_lamports)
+        ),
+        resolved_to: None,
+    })
+}
+
+pub async fn nonce_account_for_network(
+    network_name: &str,
+) -> WalletResult<SolanaNonceAccountResponse> {
+    let authority = fetch_managed_pubkey().await?;
+    let nonce_account = sol_tx::derive_nonce_account(&authority);
+    let state = fetch_nonce_state(network_name, &nonce_account).await?;
+    Ok(SolanaNonceAccountResponse {
+        network: network_name.to_string(),
+        address: addressing::base58_encode(&nonce_account),
+        exists: state.is_some(),
+        authority: state
+            .as_ref()
+            .map(|s| addressing::base58_encode(&s.authority)),
+        nonce: state.as_ref().map(|s| addressing::base58_encode(&s.nonce)),
+        lamports: state.as_ref().map(|s| s.lamports),
+    })
+}
+
+pub async fn sign_v0_message(message_base64: &str) -> WalletResult<TransferResponse> {
+    sign_v0_message_for_network(NETWORK_NAME, message_base64).await
+}
+
+/// Signs a caller-built v0 message (e.g. a DEX aggregator route) as its sole signer and fee payer.
+/// Accepts the bare message or an unsigned single-signature transaction wrapping it.
+pub async fn sign_v0_message_for_network(
+    network_name: &str,
+    message_base64: &str,
+) -> WalletResult<TransferResponse> {
+    let bytes = ton_tx::base64_decode_url_or_std(message_base64)
+        .map_err(|_| WalletError::invalid_input("message must be base64"))?;
+    let message = match bytes.first() {
+        Some(&prefix) if prefix & 0x80 != 0 => bytes.as_slice(),
+        Some(1) if bytes.len() > 65 => &bytes[65..],
+        _ => {
+            return Err(WalletError::invalid_input(
+                "expected a v0 message or an unsigned transaction with exactly one signature",
+            ))
+        }
+    };
+    let parsed = sol_tx::V0Message::parse(message)?;
+    let owner_pubkey = fetch_managed_pubkey().await?;
+    if parsed.static_keys.first() != Some(&owner_pubkey) {
+        return Err(WalletError::invalid_input(
+            "fee payer of the v0 message is not the canister-managed Solana address",
+        ));
+    }
+    if parsed.num_required_signatures != 1 || parsed.num_readonly_signed != 0 {
+        return Err(WalletError::invalid_input(
+            "v0 message must require exactly one signature, from the writable fee payer",
+        ));
+    }
+    ensure_transaction_size(message)?;
+
+    let signature = sign_solana_message(message).await?;
+    if signature.len() != 64 {
+        return Err(WalletError::Internal(format!(
+            "unexpected ed25519 signature length: {}",
+            signature.len()
+        )));
+    }
+    let raw_tx = sol_tx::encode_signed_transaction(&signature, message);
+    let tx_sig = addressing::base58_encode(&signature);
+    let (raw_tx_b64, broadcast_request) =
+        build_send_raw_transaction_request(network_name, &raw_tx)?;
+
+    Ok(TransferResponse {
+        network: network_name.to_string(),
+        accepted: false,
+        tx_id: Some(tx_sig.clone()),
+        signed_tx: Some(raw_tx_b64),
+        signed_tx_encoding: Some("base64".to_string()),
+        broadcast_request: Some(broadcast_request),
+        message: format!(
+            "signed v0 message ({} instruction(s), {} lookup table(s)); frontend should broadcast via sendTransaction: {tx_sig}",
+            parsed.instructions.len(),
+            parsed.address_table_lookups.len()
+        ),
+        resolved_to: None,
+    })
+}
+
+/// Prepares a `requestAirdrop` of `amount` SOL to the managed address; only devnet and
+/// localnet faucets are supported. Like transfers, the caller submits the request.
+pub async fn request_airdrop_for_network(
+    network_name: &str,
+    amount: &str,
+) -> WalletResult<TransferResponse> {
+    let lamports = validate_airdrop_request(network_name, amount)?;
+    let address = addressing::base58_encode(&fetch_managed_pubkey().await?);
+    let rpc_url = config::rpc_config::resolve_rpc_url(network_name, None)
+        .map_err(|err| WalletError::Internal(format!("rpc url resolution failed: {err}")))?;
+    let body = serde_json::to_string(&SolanaJsonRpcRequest {
+        jsonrpc: "2.0",
+        method: "requestAirdrop",
+        params: json!([address, lamports]),
+        id: 1,
+    })
+    .map_err(|err| WalletError::Internal(format!("serialize solana rpc request failed: {err}")))?;
+
+    Ok(TransferResponse {
+        network: network_name.to_string(),
+        accepted: false,
+        tx_id: None,
+        signed_tx: None,
+        signed_tx_encoding: None,
+        broadcast_request: Some(BroadcastHttpRequest {
+            url: rpc_url,
+            method: "POST".to_string(),
+            headers: vec![
+                ("content-type".to_string(), "application/json".to_string()),
+                ("accept".to_string(), "application/json".to_string()),
+            ],
+            body: Some(body),
+        }),
+        message: format!(
+            "requestAirdrop of {} SOL to {address} prepared; submit broadcast_request, the result is the airdrop signature",
+            format_lamports(lamports)
+        ),
+        resolved_to: None,
+    })
+}
+
+/// Returns the airdrop amount in lamports, rejecting networks without a faucet.
+fn validate_airdrop_request(network_name: &str, amount: &str) -> WalletResult<u64> {
+    if network_name != types::networks::SOLANA_DEVNET
+        && network_name != types::networks::SOLANA_LOCALNET
+    {
+        return Err(WalletError::invalid_input(format!(
+            "airdrops are only available on {} and {}",
+            types::networks::SOLANA_DEVNET,
+            types::networks::SOLANA_LOCALNET
+        )));
+    }
+    let lamports = parse_decimal_lamports(amount)?;
+    if lamports == 0 {
+        return Err(WalletError::invalid_input("amount must be > 0"));
+    }
+    Ok(lamports)
+}
+
+pub async fn close_token_accounts() -> WalletResult<SolanaCloseTokenAccountsResponse> {
+    close_token_accounts_for_network(NETWORK_NAME).await
+}
+
+/// Signs `CloseAccount` for every empty Tokenkeg / Token-2022 account of the managed address,
+/// in batches of `CLOSE_ACCOUNTS_PER_TX`, returning the rent to the managed address.
+pub async fn close_token_accounts_for_network(
+    network_name: &str,
+) -> WalletResult<SolanaCloseTokenAccountsResponse> {
+    let owner = fetch_managed_pubkey().await?;
+    let mut closable = Vec::new();
+    let mut skipped = 0usize;
+    for program in [
+        sol_tx::SPL_TOKEN_PROGRAM_ID_BASE58,
+        sol_tx::TOKEN_2022_PROGRAM_ID_BASE58,
+    ] {
+        let program_id = decode_solana_pubkey(program)?;
+        let rpc_result = solana_rpc_call(
+            network_name,
+            "getTokenAccountsByOwner",
+            json!([
+                addressing::base58_encode(&owner),
+                { "programId": program },
+                { "encoding": "jsonParsed", "commitment": "confirmed" }
+            ]),
+        )
+        .await?;
+        for item in rpc_result
+            .get("value")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let Some(pubkey) = item.get("pubkey").and_then(Value::as_str) else {
+                continue;
+            };
+            let account = item.get("account");
+            let lamports = account
+                .and_then(|a| a.get("lamports"))
+                .and_then(Value::as_u64)
+                .unwrap_or(0);
+            let info = account
+                .and_then(|a| a.get("data"))
+                .and_then(|d| d.get("parsed"))
+                .and_then(|p| p.get("info"));
+            if info.is_some_and(|info| is_closable_token_account(info, &owner)) {
+                closable.push((decode_solana_pubkey(pubkey)?, program_id, lamports));
+            } else if info.is_some_and(is_empty_token_account) {
+                skipped += 1;
+            }
+        }
+    }
+
+    let mut transactions = Vec::new();
+    for batch in closable.chunks(CLOSE_ACCOUNTS_PER_TX) {
+        let compute_budget = resolve_compute_budget(
+            network_name,
+            &[],
+            CLOSE_ACCOUNT_COMPUTE_UNITS * batch.len() as u32,
+            &batch
+                .iter()
+                .map(|(account, _, _)| *account)
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+        let recent_blockhash = fetch_recent_blockhash(network_name).await?;
+        let accounts = batch
+            .iter()
+            .map(|(account, program_id, _)| (*account, *program_id))
+            .collect::<Vec<_>>();
+        let message = sol_tx::encode_close_token_accounts_message(
+            &owner,
+            &accounts,
+            &recent_blockhash,
+            &compute_budget,
+        )?;
+        ensure_transaction_size(&message)?;
+        let signature = sign_solana_message(&message).await?;
+        if signature.len() != 64 {
+            return Err(WalletError::Internal(format!(
+                "unexpected ed25519 signature length: {}",
+                signature.len()
+            )));
+        }
+        let raw_tx = sol_tx::encode_signed_transaction(&signature, &message);
+        let tx_sig = addressing::base58_encode(&signature);
+        let (raw_tx_b64, broadcast_request) =
+            build_send_raw_transaction_request(network_name, &raw_tx)?;
+        let batch_lamports: u64 = batch.iter().map(|(_, _, lamports)| lamports).sum();
+        transactions.push(TransferResponse {
+            network: network_name.to_string(),
+            accepted: false,
+            tx_id: Some(tx_sig.clone()),
+            signed_tx: Some(raw_tx_b64),
+            signed_tx_encoding: Some("base64".to_string()),
+            broadcast_request: Some(broadcast_request),
+            message: format!(
+                "signed CloseAccount for {} token account(s), reclaiming {} SOL; frontend should broadcast via sendTransaction: {tx_sig}",
+                batch.len(),
+                format_lamports(batch_lamports)
+            ),
+            resolved_to: None,
+        });
+    }
+
+    let reclaimable_lamports = closable.iter().map(|(_, _, lamports)| lamports).sum();
+    Ok(SolanaCloseTokenAccountsResponse {
+        network: network_name.to_string(),
+        closable_accounts: closable
+            .iter()
+            .map(|(account, _, _)| addressing::base58_encode(account))
+            .collect(),
+        reclaimable_lamports,
+        message: format!(
+            "{} empty token account(s) signed for closing in {} transaction(s), pending broadcast; {} SOL reclaimable once confirmed; {skipped} empty account(s) skipped (frozen, foreign close authority or withheld fees)",
+            closable.len(),
+            transactions.len(),
+            format_lamports(reclaimable_lamports)
         ),
+        transactions,
     })
 }
 
@@ -344,7 +814,12 @@ pub async fn discover_spl_token(
 ) -> WalletResult<ConfiguredTokenResponse> {
     let mint = decode_solana_pubkey(mint_text)?;
     let mint_b58 = addressing::base58_encode(&mint);
-    let decimals = fetch_spl_decimals(network_name, &mint).await?;
+    let spl_mint = fetch_spl_mint(network_name, &mint).await?;
+    let program_label = if spl_mint.is_token_2022() {
+        "Token-2022"
+    } else {
+        "SPL Token"
+    };
     let static_match = config::token_list_config::configured_tokens(network_name)
         .iter()
         .find(|t| t.token_address.trim() == mint_b58);
@@ -353,16 +828,61 @@ pub async fn discover_spl_token(
         .unwrap_or_else(|| format!("SPL{}", short_b58_suffix(&mint_b58)));
     let name = static_match
         .map(|t| t.name.to_string())
-        .unwrap_or_else(|| format!("SPL Token {}", short_b58_suffix(&mint_b58)));
+        .unwrap_or_else(|| format!("{program_label} {}", short_b58_suffix(&mint_b58)));
     Ok(ConfiguredTokenResponse {
         network: network_name.to_string(),
         symbol,
         name,
         token_address: mint_b58,
-        decimals: u64::from(decimals),
+        decimals: u64::from(spl_mint.decimals),
     })
 }
 
+pub async fn resolve_sns_name(network_name: &str, name: &str) -> WalletResult<String> {
+    if network_name != NETWORK_NAME {
+        return Err(WalletError::invalid_input(format!(
+            ".sol names are only resolvable on {NETWORK_NAME}"
+        )));
+    }
+    let label = name
+        .strip_suffix(".sol")
+        .filter(|l| !l.is_empty() && !l.contains('.'))
+        .ok_or_else(|| {
+            WalletError::invalid_input("only top-level .sol names (e.g. alice.sol) are supported")
+        })?;
+    let domain_account = sol_tx::derive_sns_domain_account(label)?;
+    let domain = fetch_account_data(network_name, &domain_account)
+        .await?
+        .ok_or_else(|| WalletError::invalid_input(format!("{name} is not registered")))?;
+    let owner: [u8; 32] = domain
+        .get(32..64)
+        .and_then(|owner| owner.try_into().ok())
+        .ok_or_else(|| WalletError::Internal("SNS name account data too short".into()))?;
+
+    // The SOL record is the owner's chosen destination; records signed by a previous owner are
+    // stale and ignored.
+    let record_v2 = sol_tx::derive_sns_sol_record_v2(&domain_account)?;
+    if let Some(destination) = fetch_account_data(network_name, &record_v2)
+        .await?
+        .and_then(|data| sol_tx::parse_sns_sol_record_v2(&data, &owner))
+    {
+        return Ok(addressing::base58_encode(&destination));
+    }
+    let record_v1 = sol_tx::derive_sns_sol_record_v1(&domain_account)?;
+    if let Some(destination) = fetch_account_data(network_name, &record_v1)
+        .await?
+        .and_then(|data| sol_tx::parse_sns_sol_record_v1(&data, &record_v1, &owner))
+    {
+        return Ok(addressing::base58_encode(&destination));
+    }
+    if owner == sol_tx::derive_sns_tokenizer_escrow(&domain_account)? {
+        return Err(WalletError::invalid_input(format!(
+            "{name} is tokenized and has no valid SOL record; its registry owner is the tokenizer escrow"
+        )));
+    }
+    Ok(addressing::base58_encode(&owner))
+}
+
 fn validate_account(account: &str) -> WalletResult<()> {
     if account.trim().is_empty() {
         return Err(WalletError::invalid_input("account is required"));
@@ -370,6 +890,50 @@ fn validate_account(account: &str) -> WalletResult<()> {
     Ok(())
 }
 
+fn is_empty_token_account(info: &Value) -> bool {
+    info.get("tokenAmount")
+        .and_then(|t| t.get("amount"))
+        .and_then(Value::as_str)
+        == Some("0")
+}
+
+/// Empty, not frozen, closable by the managed key, and (Token-2022) holding no withheld fees.
+fn is_closable_token_account(info: &Value, owner: &[u8; 32]) -> bool {
+    let owner_b58 = addressing::base58_encode(owner);
+    let close_authority = info
+        .get("closeAuthority")
+        .and_then(Value::as_str)
+        .unwrap_or(&owner_b58);
+    // RPCs encode `withheldAmount` as a number or a string; anything unreadable blocks closing.
+    let withheld = match find_extension(info, "transferFeeAmount")
+        .and_then(|state| state.get("withheldAmount"))
+    {
+        None => Some(0),
+        Some(Value::String(amount)) => amount.parse::<u64>().ok(),
+        Some(amount) => amount.as_u64(),
+    };
+    is_empty_token_account(info)
+        && info.get("state").and_then(Value::as_str) != Some("frozen")
+        && info.get("owner").and_then(Value::as_str) == Some(owner_b58.as_str())
+        && close_authority == owner_b58
+        && withheld == Some(0)
+}
+
+fn transfer_memo(req: &TransferRequest) -> Option<&str> {
+    req.memo.as_deref().map(str::trim).filter(|m| !m.is_empty())
+}
+
+/// One signature (shortvec length + 64 bytes) plus the message must fit a single packet.
+fn ensure_transaction_size(message: &[u8]) -> WalletResult<()> {
+    let size = 1 + 64 + message.len();
+    if size > SOLANA_MAX_TX_BYTES {
+        return Err(WalletError::invalid_input(format!(
+            "solana transaction would be {size} bytes (max {SOLANA_MAX_TX_BYTES}); shorten the memo"
+        )));
+    }
+    Ok(())
+}
+
 fn validate_transfer(req: &TransferRequest) -> WalletResult<()> {
     if req.to.trim().is_empty() {
         return Err(WalletError::invalid_input("to is required"));
@@ -397,6 +961,252 @@ async fn fetch_recent_blockhash(network_name: &str) -> WalletResult<[u8; 32]> {
     decode_solana_pubkey(blockhash)
 }
 
+/// Prices compute units from `getRecentPrioritizationFees` over the accounts the transaction
+/// writes. `fee_tier` (slow/standard/fast) picks the percentile and `max_compute_unit_price`
+/// (micro-lamports per CU) caps the result.
+async fn resolve_compute_budget(
+    network_name: &str,
+    metadata: &[(String, String)],
+    unit_limit: u32,
+    writable_accounts: &[[u8; 32]],
+) -> WalletResult<sol_tx::ComputeBudget> {
+    let percentile = match metadata_value(metadata, "fee_tier") {
+        None => 50,
+        Some(tier) if tier.eq_ignore_ascii_case("slow") => 25,
+        Some(tier) if tier.eq_ignore_ascii_case("standard") => 50,
+        Some(tier) if tier.eq_ignore_ascii_case("fast") => 75,
+        Some(_) => {
+            return Err(WalletError::invalid_input(
+                "metadata fee_tier must be slow, standard or fast",
+            ))
+        }
+    };
+    let cap = metadata_value(metadata, "max_compute_unit_price")
+        .map(|v| {
+            v.parse::<u64>().map_err(|_| {
+                WalletError::invalid_input(
+                    "metadata max_compute_unit_price must be an integer (micro-lamports per CU)",
+                )
+            })
+        })
+        .transpose()?;
+
+    // Some providers do not serve getRecentPrioritizationFees; send without a price then.
+    let fees = fetch_recent_prioritization_fees(network_name, writable_accounts)
+        .await
+        .unwrap_or_default();
+    let price = fee_percentile(fees, percentile).min(cap.unwrap_or(u64::MAX));
+    Ok(sol_tx::ComputeBudget {
+        unit_limit,
+        unit_price_micro_lamports: price,
+    })
+}
+
+async fn fetch_recent_prioritization_fees(
+    network_name: &str,
+    writable_accounts: &[[u8; 32]],
+) -> WalletResult<Vec<u64>> {
+    let accounts = writable_accounts
+        .iter()
+        .map(|a| addressing::base58_encode(a))
+        .collect::<Vec<_>>();
+    let rpc_result = solana_rpc_call(
+        network_name,
+        "getRecentPrioritizationFees",
+        json!([accounts]),
+    )
+    .await?;
+    let entries = rpc_result.as_array().ok_or_else(|| {
+        WalletError::Internal("solana rpc getRecentPrioritizationFees is not an array".into())
+    })?;
+    Ok(entries
+        .iter()
+        .filter_map(|e| e.get("prioritizationFee").and_then(Value::as_u64))
+        .collect())
+}
+
+fn fee_percentile(mut fees: Vec<u64>, percentile: usize) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    fees[(fees.len() - 1) * percentile / 100]
+}
+
+fn describe_compute_budget(budget: &sol_tx::ComputeBudget) -> String {
+    format!(
+        "compute_unit_limit={}, compute_unit_price={} micro-lamports, max priority fee={} lamports",
+        budget.unit_limit,
+        budget.unit_price_micro_lamports,
+        budget.max_priority_fee_lamports()
+    )
+}
+
+/// `("durable_nonce", "true")` builds on the managed nonce account instead of a recent blockhash.
+async fn resolve_tx_lifetime(
+    network_name: &str,
+    metadata: &[(String, String)],
+    authority: &[u8; 32],
+) -> WalletResult<sol_tx::TxLifetime> {
+    let durable =
+        metadata_value(metadata, "durable_nonce").is_some_and(|v| v.eq_ignore_ascii_case("true"));
+    if !durable {
+        return Ok(sol_tx::TxLifetime::Blockhash(
+            fetch_recent_blockhash(network_name).await?,
+        ));
+    }
+    let nonce_account = sol_tx::derive_nonce_account(authority);
+    let state = fetch_nonce_state(network_name, &nonce_account)
+        .await?
+        .ok_or_else(|| {
+            WalletError::invalid_input(format!(
+                "durable nonce account {} does not exist; create it with {network_name}_create_nonce_account first",
+                addressing::base58_encode(&nonce_account)
+            ))
+        })?;
+    if &state.authority != authority {
+        return Err(WalletError::invalid_input(
+            "durable nonce account authority is not the canister-managed Solana address",
+        ));
+    }
+    Ok(sol_tx::TxLifetime::DurableNonce {
+        nonce_account,
+        authority: *authority,
+        nonce: state.nonce,
+    })
+}
+
+/// `("address_lookup_tables", "<table>,<table>")` compiles a v0 message against those tables.
+async fn resolve_lookup_tables(
+    network_name: &str,
+    metadata: &[(String, String)],
+) -> WalletResult<Vec<sol_tx::AddressLookupTable>> {
+    let Some(list) = metadata_value(metadata, "address_lookup_tables") else {
+        return Ok(Vec::new());
+    };
+    let mut tables = Vec::new();
+    for table_text in list.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        let key = decode_solana_pubkey(table_text)?;
+        let value = fetch_account_value(network_name, &key, "jsonParsed")
+            .await?
+            .ok_or_else(|| {
+                WalletError::invalid_input(format!("address lookup table {table_text} not found"))
+            })?;
+        let parsed = value
+            .get("data")
+            .and_then(|d| d.get("parsed"))
+            .filter(|p| p.get("type").and_then(Value::as_str) == Some("lookupTable"))
+            .and_then(|p| p.get("info"))
+            .ok_or_else(|| {
+                WalletError::invalid_input(format!("{table_text} is not an address lookup table"))
+            })?;
+        let active = parsed
+            .get("deactivationSlot")
+            .and_then(Value::as_str)
+            .is_none_or(|slot| slot == u64::MAX.to_string());
+        if !active {
+            return Err(WalletError::invalid_input(format!(
+                "address lookup table {table_text} is deactivated"
+            )));
+        }
+        let addresses = parsed
+            .get("addresses")
+            .and_then(Value::as_array)
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(Value::as_str)
+                    .map(decode_solana_pubkey)
+                    .collect::<WalletResult<Vec<_>>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+        tables.push(sol_tx::AddressLookupTable { key, addresses });
+    }
+    Ok(tables)
+}
+
+fn describe_lifetime(lifetime: &sol_tx::TxLifetime) -> String {
+    match lifetime {
+        sol_tx::TxLifetime::Blockhash(_) => String::new(),
+        sol_tx::TxLifetime::DurableNonce { nonce_account, .. } => format!(
+            ", durable nonce {}: valid until the nonce advances",
+            addressing::base58_encode(nonce_account)
+        ),
+    }
+}
+
+async fn fetch_nonce_state(
+    network_name: &str,
+    nonce_account: &[u8; 32],
+) -> WalletResult<Option<NonceState>> {
+    let Some(value) = fetch_account_value(network_name, nonce_account, "jsonParsed").await? else {
+        return Ok(None);
+    };
+    let parsed = value.get("data").and_then(|d| d.get("parsed"));
+    if parsed.and_then(|p| p.get("type")).and_then(Value::as_str) != Some("initialized") {
+        return Err(WalletError::invalid_input(format!(
+            "{} is not an initialized nonce account",
+            addressing::base58_encode(nonce_account)
+        )));
+    }
+    let info_field = |name: &str| {
+        parsed
+            .and_then(|p| p.get("info"))
+            .and_then(|info| info.get(name))
+            .and_then(Value::as_str)
+            .ok_or_else(|| WalletError::Internal(format!("solana nonce account missing {name}")))
+    };
+    Ok(Some(NonceState {
+        authority: decode_solana_pubkey(info_field("authority")?)?,
+        nonce: decode_solana_pubkey(info_field("blockhash")?)?,
+        lamports: value.get("lamports").and_then(Value::as_u64).unwrap_or(0),
+    }))
+}
+
+async fn fetch_account_value(
+    network_name: &str,
+    account: &[u8; 32],
+    encoding: &str,
+) -> WalletResult<Option<Value>> {
+    let rpc_result = solana_rpc_call(
+        network_name,
+        "getAccountInfo",
+        json!([
+            addressing::base58_encode(account),
+            { "encoding": encoding, "commitment": "confirmed" }
+        ]),
+    )
+    .await?;
+    Ok(rpc_result.get("value").filter(|v| !v.is_null()).cloned())
+}
+
+async fn fetch_account_data(
+    network_name: &str,
+    account: &[u8; 32],
+) -> WalletResult<Option<Vec<u8>>> {
+    let Some(value) = fetch_account_value(network_name, account, "base64").await? else {
+        return Ok(None);
+    };
+    let data_b64 = value
+        .get("data")
+        .and_then(Value::as_array)
+        .and_then(|arr| arr.first())
+        .and_then(Value::as_str)
+        .ok_or_else(|| WalletError::Internal("account data is not base64".into()))?;
+    ton_tx::base64_decode_url_or_std(data_b64).map(Some)
+}
+
+async fn fetch_managed_pubkey() -> WalletResult<[u8; 32]> {
+    let (public_key, _key_name) =
+        addressing::fetch_schnorr_public_key(SchnorrAlgorithm::Ed25519).await?;
+    public_key.as_slice().try_into().map_err(|_| {
+        WalletError::Internal(format!(
+            "unexpected ed25519 public key length: {}",
+            public_key.len()
+        ))
+    })
+}
+
 async fn sign_solana_message(message: &[u8]) -> WalletResult<Vec<u8>> {
     let key_name = config::app_config::default_schnorr_key_name().to_string();
     let args = SignWithSchnorrArgs {
@@ -449,23 +1259,84 @@ fn build_send_raw_transaction_request(
     ))
 }
 
-async fn fetch_spl_decimals(network_name: &str, mint: &[u8; 32]) -> WalletResult<u8> {
+/// Reads the mint with `jsonParsed`; its owner tells Tokenkeg and Token-2022 mints apart.
+async fn fetch_spl_mint(network_name: &str, mint: &[u8; 32]) -> WalletResult<SplMint> {
     let mint_b58 = addressing::base58_encode(mint);
-    let rpc_result = solana_rpc_call(
+    let value = fetch_account_value(network_name, mint, "jsonParsed")
+        .await?
+        .ok_or_else(|| WalletError::invalid_input(format!("SPL mint {mint_b58} not found")))?;
+    let owner = value
+        .get("owner")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if owner != sol_tx::SPL_TOKEN_PROGRAM_ID_BASE58 && owner != sol_tx::TOKEN_2022_PROGRAM_ID_BASE58
+    {
+        return Err(WalletError::invalid_input(format!(
+            "{mint_b58} is not owned by the SPL Token or Token-2022 program"
+        )));
+    }
+    let info = value
+        .get("data")
+        .and_then(|d| d.get("parsed"))
+        .filter(|p| p.get("type").and_then(Value::as_str) == Some("mint"))
+        .and_then(|p| p.get("info"))
+        .ok_or_else(|| WalletError::invalid_input(format!("{mint_b58} is not an SPL mint")))?;
+    let decimals = info
+        .get("decimals")
+        .and_then(Value::as_u64)
+        .and_then(|d| u8::try_from(d).ok())
+        .ok_or_else(|| WalletError::Internal("solana mint account missing decimals".into()))?;
+    let transfer_fee = find_extension(info, "transferFeeConfig")
+        .map(|state| -> WalletResult<SplTransferFeeConfig> {
+            Ok(SplTransferFeeConfig {
+                older: parse_transfer_fee(state.get("olderTransferFee"))?,
+                newer: parse_transfer_fee(state.get("newerTransferFee"))?,
+            })
+        })
+        .transpose()?;
+    Ok(SplMint {
+        token_program_id: decode_solana_pubkey(owner)?,
+        decimals,
+        transfer_fee,
+    })
+}
+
+fn find_extension<'a>(info: &'a Value, name: &str) -> Option<&'a Value> {
+    info.get("extensions")
+        .and_then(Value::as_array)?
+        .iter()
+        .find(|e| e.get("extension").and_then(Value::as_str) == Some(name))
+        .and_then(|e| e.get("state"))
+}
+
+fn parse_transfer_fee(value: Option<&Value>) -> WalletResult<SplTransferFee> {
+    let field = |name: &str| {
+        value
+            .and_then(|v| v.get(name))
+            .and_then(Value::as_u64)
+            .ok_or_else(|| {
+                WalletError::Internal(format!("solana transferFeeConfig missing {name}"))
+            })
+    };
+    Ok(SplTransferFee {
+        epoch: field("epoch")?,
+        maximum_fee: field("maximumFee")?,
+        basis_points: u16::try_from(field("transferFeeBasisPoints")?).map_err(|_| {
+            WalletError::Internal("solana transferFeeBasisPoints out of range".into())
+        })?,
+    })
+}
+
+async fn fetch_epoch(network_name: &str) -> WalletResult<u64> {
+    solana_rpc_call(
         network_name,
-        "getTokenSupply",
-        json!([mint_b58, { "commitment": "confirmed" }]),
+        "getEpochInfo",
+        json!([{ "commitment": "confirmed" }]),
     )
-    .await?;
-    let decimals = rpc_result
-        .get("value")
-        .and_then(|v| v.get("decimals"))
-        .and_then(Value::as_u64)
-        .ok_or_else(|| {
-            WalletError::Internal("solana rpc getTokenSupply missing decimals".into())
-        })?;
-    u8::try_from(decimals)
-        .map_err(|_| WalletError::Internal("solana rpc token decimals out of range".into()))
+    .await?
+    .get("epoch")
+    .and_then(Value::as_u64)
+    .ok_or_else(|| WalletError::Internal("solana rpc getEpochInfo missing epoch".into()))
 }
 
 async fn fetch_token_account_for_owner(
@@ -478,13 +1349,14 @@ async fn fetch_token_account_for_owner(
         .ok_or_else(|| {
             WalletError::invalid_input("destination/source token account not found for this mint")
         })
+        .map(|account| account.pubkey)
 }
 
 async fn fetch_token_account_for_owner_optional(
     network_name: &str,
     owner: &[u8; 32],
     mint: &[u8; 32],
-) -> WalletResult<Option<[u8; 32]>> {
+) -> WalletResult<Option<SplTokenAccount>> {
     let owner_b58 = addressing::base58_encode(owner);
     let mint_b58 = addressing::base58_encode(mint);
     let rpc_result = solana_rpc_call(
@@ -498,16 +1370,30 @@ async fn fetch_token_account_for_owner_optional(
     )
     .await?;
 
-    let first_pubkey = rpc_result
+    let Some(first) = rpc_result
         .get("value")
         .and_then(Value::as_array)
         .and_then(|arr| arr.first())
-        .and_then(|item| item.get("pubkey"))
-        .and_then(Value::as_str);
-    match first_pubkey {
-        Some(pubkey) => decode_solana_pubkey(pubkey).map(Some),
-        None => Ok(None),
-    }
+    else {
+        return Ok(None);
+    };
+    let pubkey = first
+        .get("pubkey")
+        .and_then(Value::as_str)
+        .ok_or_else(|| WalletError::Internal("solana token account missing pubkey".into()))?;
+    let requires_incoming_memo = first
+        .get("account")
+        .and_then(|a| a.get("data"))
+        .and_then(|d| d.get("parsed"))
+        .and_then(|p| p.get("info"))
+        .and_then(|info| find_extension(info, "memoTransfer"))
+        .and_then(|state| state.get("requireIncomingTransferMemos"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    Ok(Some(SplTokenAccount {
+        pubkey: decode_solana_pubkey(pubkey)?,
+        requires_incoming_memo,
+    }))
 }
 
 async fn solana_rpc_call(
@@ -571,6 +1457,14 @@ fn format_u64_units(amount: u64, decimals: u8) -> String {
     format!("{whole}.{frac_text}")
 }
 
+fn metadata_value<'a>(metadata: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    metadata
+        .iter()
+        .find(|(k, _)| k.trim().eq_ignore_ascii_case(key))
+        .map(|(_, v)| v.trim())
+        .filter(|v| !v.is_empty())
+}
+
 fn decode_solana_pubkey(value: &str) -> WalletResult<[u8; 32]> {
     sol_tx::decode_solana_pubkey(value)
 }
@@ -720,3 +1614,134 @@ fn base64_encode(data: &[u8]) -> String {
 fn short_b58_suffix(s: &str) -> String {
     s.get(s.len().saturating_sub(6)..).unwrap_or(s).to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn airdrops_only_on_faucet_networks_with_positive_amounts() {
+        for network in [types::networks::SOLANA, types::networks::SOLANA_TESTNET] {
+            assert!(validate_airdrop_request(network, "1").is_err(), "{network}");
+        }
+        for network in [
+            types::networks::SOLANA_DEVNET,
+            types::networks::SOLANA_LOCALNET,
+        ] {
+            assert!(validate_airdrop_request(network, "0").is_err(), "{network}");
+            assert!(
+                validate_airdrop_request(network, "0.000").is_err(),
+                "{network}"
+            );
+            assert_eq!(
+                validate_airdrop_request(network, "1.5").unwrap(),
+                1_500_000_000
+            );
+        }
+    }
+
+    fn token_account_info(owner: &str, amount: &str, state: &str, extra: Value) -> Value {
+        let mut info = json!({
+            "isNative": false,
+            "mint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "owner": owner,
+            "state": state,
+            "tokenAmount": {
+                "amount": amount,
+                "decimals": 6,
+                "uiAmount": 0.0,
+                "uiAmountString": amount
+            }
+        });
+        if let (Some(info), Some(extra)) = (info.as_object_mut(), extra.as_object()) {
+            info.extend(extra.clone());
+        }
+        info
+    }
+
+    fn withheld(amount: Value) -> Value {
+        json!({
+            "extensions": [
+                { "extension": "immutableOwner" },
+                { "extension": "transferFeeAmount", "state": { "withheldAmount": amount } }
+            ]
+        })
+    }
+
+    #[test]
+    fn only_empty_unfrozen_owner_closable_accounts_without_withheld_fees_are_closed() {
+        let owner = [7u8; 32];
+        let owner_b58 = addressing::base58_encode(&owner);
+        let foreign = addressing::base58_encode(&[9u8; 32]);
+        let cases = [
+            (
+                "empty",
+                token_account_info(&owner_b58, "0", "initialized", json!({})),
+                true,
+            ),
+            (
+                "own close authority",
+                token_account_info(
+                    &owner_b58,
+                    "0",
+                    "initialized",
+                    json!({ "closeAuthority": owner_b58 }),
+                ),
+                true,
+            ),
+            (
+                "non-zero balance",
+                token_account_info(&owner_b58, "1", "initialized", json!({})),
+                false,
+            ),
+            (
+                "frozen",
+                token_account_info(&owner_b58, "0", "frozen", json!({})),
+                false,
+            ),
+            (
+                "foreign owner",
+                token_account_info(&foreign, "0", "initialized", json!({})),
+                false,
+            ),
+            (
+                "foreign close authority",
+                token_account_info(
+                    &owner_b58,
+                    "0",
+                    "initialized",
+                    json!({ "closeAuthority": foreign }),
+                ),
+                false,
+            ),
+            (
+                "zero withheld number",
+                token_account_info(&owner_b58, "0", "initialized", withheld(json!(0))),
+                true,
+            ),
+            (
+                "zero withheld string",
+                token_account_info(&owner_b58, "0", "initialized", withheld(json!("0"))),
+                true,
+            ),
+            (
+                "withheld number",
+                token_account_info(&owner_b58, "0", "initialized", withheld(json!(15))),
+                false,
+            ),
+            (
+                "withheld string",
+                token_account_info(&owner_b58, "0", "initialized", withheld(json!("15"))),
+                false,
+            ),
+            (
+                "unreadable withheld",
+                token_account_info(&owner_b58, "0", "initialized", withheld(json!(null))),
+                false,
+            ),
+        ];
+        for (name, info, expected) in cases {
+            assert_eq!(is_closable_token_account(&info, &owner), expected, "{name}");
+        }
+    }
+}
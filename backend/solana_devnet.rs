@@ -0,0 +1,40 @@
+use crate::chains::solana;
+use crate::error::WalletResult;
+use crate::types::{
+    self, AddressResponse, SolanaCloseTokenAccountsResponse, SolanaNonceAccountResponse,
+    TransferRequest, TransferResponse,
+};
+
+const NETWORK_NAME: &str = types::networks::SOLANA_DEVNET;
+
+pub async fn request_address() -> WalletResult<AddressResponse> {
+    solana::request_address_for_network(NETWORK_NAME).await
+}
+
+pub async fn transfer_sol(req: TransferRequest) -> WalletResult<TransferResponse> {
+    solana::transfer_sol_for_network(NETWORK_NAME, req).await
+}
+
+pub async fn transfer_spl(req: TransferRequest) -> WalletResult<TransferResponse> {
+    solana::transfer_spl_for_network(NETWORK_NAME, req).await
+}
+
+pub async fn create_nonce_account() -> WalletResult<TransferResponse> {
+    solana::create_nonce_account_for_network(NETWORK_NAME).await
+}
+
+pub async fn nonce_account() -> WalletResult<SolanaNonceAccountResponse> {
+    solana::nonce_account_for_network(NETWORK_NAME).await
+}
+
+pub async fn close_token_accounts() -> WalletResult<SolanaCloseTokenAccountsResponse> {
+    solana::close_token_accounts_for_network(NETWORK_NAME).await
+}
+
+pub async fn sign_v0_message(message_base64: &str) -> WalletResult<TransferResponse> {
+    solana::sign_v0_message_for_network(NETWORK_NAME, message_base64).await
+}
+
+pub async fn request_airdrop(amount: &str) -> WalletResult<TransferResponse> {
+    solana::request_airdrop_for_network(NETWORK_NAME, amount).await
+}
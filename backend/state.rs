@@ -2,7 +2,14 @@ use candid::{CandidType, Principal};
 use serde::Deserialize;
 use std::cell::RefCell;
 
-use crate::types::{ConfiguredRpcResponse, ConfiguredTokenResponse};
+use crate::config;
+use crate::types::{
+    networks, BitcoinDataSource, BitcoinSentTxRecord, ConfiguredNftCollectionResponse,
+    ConfiguredRpcResponse, ConfiguredTokenResponse, CustomEvmNetworkResponse, NameResolutionRecord,
+};
+
+const NAME_RESOLUTION_HISTORY_LIMIT: usize = 200;
+const BITCOIN_SENT_TX_HISTORY_LIMIT: usize = 100;
 
 #[derive(CandidType, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
 pub struct TokenKey {
@@ -22,6 +29,16 @@ pub struct State {
     pub removed_tokens: Vec<TokenKey>,
     #[serde(default)]
     pub runtime_rpcs: Vec<ConfiguredRpcResponse>,
+    #[serde(default)]
+    pub nft_collections: Vec<ConfiguredNftCollectionResponse>,
+    #[serde(default)]
+    pub custom_evm_networks: Vec<CustomEvmNetworkResponse>,
+    #[serde(default)]
+    pub name_resolutions: Vec<NameResolutionRecord>,
+    #[serde(default)]
+    pub bitcoin_sent_txs: Vec<BitcoinSentTxRecord>,
+    #[serde(default)]
+    pub bitcoin_data_source: Option<BitcoinDataSource>,
 }
 
 thread_local! {
@@ -149,6 +166,184 @@ pub fn is_removed_token(network: &str, token_address: &str) -> bool {
     })
 }
 
+pub fn nft_collections_for_network(network: &str) -> Vec<ConfiguredNftCollectionResponse> {
+    STATE.with(|state| {
+        state
+            .borrow()
+            .nft_collections
+            .iter()
+            .filter(|c| c.network == network)
+            .cloned()
+            .collect()
+    })
+}
+
+pub fn upsert_nft_collection(collection: ConfiguredNftCollectionResponse) -> bool {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if let Some(existing) = state.nft_collections.iter_mut().find(|c| {
+            c.network == collection.network && c.contract_address == collection.contract_address
+        }) {
+            *existing = collection;
+            true
+        } else {
+            state.nft_collections.push(collection);
+            false
+        }
+    })
+}
+
+pub fn remove_nft_collection(network: &str, contract_address: &str) -> bool {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let before = state.nft_collections.len();
+        state
+            .nft_collections
+            .retain(|c| !(c.network == network && c.contract_address == contract_address));
+        state.nft_collections.len() != before
+    })
+}
+
+pub fn custom_evm_networks() -> Vec<CustomEvmNetworkResponse> {
+    STATE.with(|state| {
+        let mut items = state.borrow().custom_evm_networks.clone();
+        items.sort_by(|a, b| a.id.cmp(&b.id));
+        items
+    })
+}
+
+pub fn custom_evm_network(id: &str) -> Option<CustomEvmNetworkResponse> {
+    STATE.with(|state| {
+        state
+            .borrow()
+            .custom_evm_networks
+            .iter()
+            .find(|n| n.id == id)
+            .cloned()
+    })
+}
+
+pub fn upsert_custom_evm_network(network: CustomEvmNetworkResponse) -> bool {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if let Some(existing) = state
+            .custom_evm_networks
+            .iter_mut()
+            .find(|n| n.id == network.id)
+        {
+            *existing = network;
+            true
+        } else {
+            state.custom_evm_networks.push(network);
+            false
+        }
+    })
+}
+
+pub fn remove_custom_evm_network(id: &str) -> bool {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let before = state.custom_evm_networks.len();
+        state.custom_evm_networks.retain(|n| n.id != id);
+        state.custom_evm_networks.len() != before
+    })
+}
+
+pub fn name_resolutions() -> Vec<NameResolutionRecord> {
+    STATE.with(|state| {
+        state
+            .borrow()
+            .name_resolutions
+            .iter()
+            .rev()
+            .cloned()
+            .collect()
+    })
+}
+
+pub fn record_name_resolution(record: NameResolutionRecord) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.name_resolutions.push(record);
+        let overflow = state
+            .name_resolutions
+            .len()
+            .saturating_sub(NAME_RESOLUTION_HISTORY_LIMIT);
+        state.name_resolutions.drain(..overflow);
+    });
+}
+
+pub fn bitcoin_sent_txs() -> Vec<BitcoinSentTxRecord> {
+    STATE.with(|state| {
+        state
+            .borrow()
+            .bitcoin_sent_txs
+            .iter()
+            .rev()
+            .cloned()
+            .collect()
+    })
+}
+
+pub fn bitcoin_sent_tx(network: &str, txid: &str) -> Option<BitcoinSentTxRecord> {
+    STATE.with(|state| {
+        state
+            .borrow()
+            .bitcoin_sent_txs
+            .iter()
+            .find(|tx| {
+                tx.network.as_deref().unwrap_or(networks::BITCOIN) == network
+                    && tx.txid.eq_ignore_ascii_case(txid)
+            })
+            .cloned()
+    })
+}
+
+pub fn record_bitcoin_sent_tx(record: BitcoinSentTxRecord) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.bitcoin_sent_txs.push(record);
+        let overflow = state
+            .bitcoin_sent_txs
+            .len()
+            .saturating_sub(BITCOIN_SENT_TX_HISTORY_LIMIT);
+        state.bitcoin_sent_txs.drain(..overflow);
+    });
+}
+
+pub fn mark_bitcoin_tx_replaced(txid: &str, replaced_by: &str) {
+    STATE.with(|state| {
+        if let Some(tx) = state
+            .borrow_mut()
+            .bitcoin_sent_txs
+            .iter_mut()
+            .find(|tx| tx.txid.eq_ignore_ascii_case(txid))
+        {
+            tx.replaced_by = Some(replaced_by.to_string());
+        }
+    });
+}
+
+pub fn bitcoin_data_source() -> BitcoinDataSource {
+    STATE.with(|state| {
+        state
+            .borrow()
+            .bitcoin_data_source
+            .unwrap_or_else(config::app_config::default_bitcoin_data_source)
+    })
+}
+
+pub fn set_bitcoin_data_source(source: BitcoinDataSource) -> BitcoinDataSource {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let prev = state
+            .bitcoin_data_source
+            .unwrap_or_else(config::app_config::default_bitcoin_data_source);
+        state.bitcoin_data_source = Some(source);
+        prev
+    })
+}
+
 pub fn configured_rpcs() -> Vec<ConfiguredRpcResponse> {
     STATE.with(|state| {
         let mut items = state.borrow().runtime_rpcs.clone();
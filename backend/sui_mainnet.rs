@@ -186,6 +186,7 @@ pub async fn transfer(req: TransferRequest) -> WalletResult<TransferResponse> {
         message:
             "signed Sui transaction prepared; frontend should call sui_executeTransactionBlock"
                 .to_string(),
+        resolved_to: None,
     })
 }
 
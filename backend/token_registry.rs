@@ -1,9 +1,10 @@
 use crate::chains::{
     aptos_mainnet, internet_computer, near_mainnet, solana, sui_mainnet, ton_mainnet, tron,
 };
+use crate::config;
 use crate::error::{WalletError, WalletResult};
 use crate::evm_rpc;
-use crate::types::{self, ConfiguredTokenResponse};
+use crate::types::{self, ConfiguredNftCollectionResponse, ConfiguredTokenResponse};
 
 pub async fn discover_token_metadata(
     network: &str,
@@ -28,7 +29,10 @@ pub async fn discover_token_metadata(
         types::networks::INTERNET_COMPUTER => {
             internet_computer::discover_icrc_token(token_address).await
         }
-        types::networks::SOLANA | types::networks::SOLANA_TESTNET => {
+        types::networks::SOLANA
+        | types::networks::SOLANA_TESTNET
+        | types::networks::SOLANA_DEVNET
+        | types::networks::SOLANA_LOCALNET => {
             solana::discover_spl_token(&network, token_address).await
         }
         types::networks::TRON => tron::discover_trc20_token(token_address).await,
@@ -38,6 +42,9 @@ pub async fn discover_token_metadata(
             aptos_mainnet::discover_coin_type_token(token_address).await
         }
         types::networks::SUI_MAINNET => sui_mainnet::discover_coin_type_token(token_address).await,
+        other if config::rpc_config::custom_evm_network(other).is_some() => {
+            evm_rpc::discover_erc20_token(&network, token_address).await
+        }
         other => Err(WalletError::Unimplemented {
             network: other.to_string(),
             operation: "token metadata discovery".to_string(),
@@ -45,6 +52,38 @@ pub async fn discover_token_metadata(
     }
 }
 
+pub async fn discover_nft_collection(
+    network: &str,
+    contract_address: &str,
+) -> WalletResult<ConfiguredNftCollectionResponse> {
+    let network = normalize_network_name(network);
+    let contract_address = contract_address.trim();
+    if contract_address.is_empty() {
+        return Err(WalletError::invalid_input("contract_address is required"));
+    }
+
+    match network.as_str() {
+        types::networks::ETHEREUM
+        | types::networks::SEPOLIA
+        | types::networks::BASE
+        | types::networks::BSC
+        | types::networks::ARBITRUM
+        | types::networks::OPTIMISM
+        | types::networks::AVALANCHE
+        | types::networks::OKX
+        | types::networks::POLYGON => {
+            evm_rpc::discover_nft_collection(&network, contract_address).await
+        }
+        other if config::rpc_config::custom_evm_network(other).is_some() => {
+            evm_rpc::discover_nft_collection(&network, contract_address).await
+        }
+        other => Err(WalletError::Unimplemented {
+            network: other.to_string(),
+            operation: "NFT collection discovery".to_string(),
+        }),
+    }
+}
+
 pub fn normalize_network_name(input: &str) -> String {
     input.trim().to_lowercase().replace('-', "_")
 }
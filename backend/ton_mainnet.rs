@@ -181,6 +181,7 @@ pub async fn transfer(req: TransferRequest) -> WalletResult<TransferResponse> {
             body: Some(broadcast_body),
         }),
         message: "signed TON message BOC prepared; frontend should broadcast via /sendBocReturnHash (fallback /sendBoc may be needed on some providers)".to_string(),
+        resolved_to: None,
     })
 }
 
@@ -223,6 +224,35 @@ struct JettonWalletInfo {
     balance: BigUint,
 }
 
+pub async fn resolve_dns_name(name: &str) -> WalletResult<String> {
+    if name.len() <= ".ton".len() || !name.ends_with(".ton") {
+        return Err(WalletError::invalid_input(
+            "TON DNS name must end with .ton",
+        ));
+    }
+    let path = format!(
+        "/dns/records?domain={}&limit=1&offset=0",
+        percent_encode(name)
+    );
+    let payload = ton_v3_get_json(&path).await?;
+    let record = payload
+        .get("records")
+        .and_then(Value::as_array)
+        .and_then(|records| {
+            records
+                .iter()
+                .find(|r| r.get("domain").and_then(Value::as_str) == Some(name))
+        })
+        .ok_or_else(|| WalletError::invalid_input(format!("{name} is not registered")))?;
+    let wallet = record
+        .get("dns_wallet")
+        .and_then(Value::as_str)
+        .filter(|w| !w.is_empty())
+        .ok_or_else(|| WalletError::invalid_input(format!("{name} has no wallet record")))?;
+    let address = ton_tx::parse_ton_address(wallet)?;
+    Ok(ton_tx::format_user_friendly_address(&address, false, false))
+}
+
 async fn fetch_managed_ton_wallet() -> WalletResult<ManagedTonWallet> {
     let (pubkey, _key_name) =
         addressing::fetch_schnorr_public_key(SchnorrAlgorithm::Ed25519).await?;
@@ -161,6 +161,7 @@ pub async fn transfer(req: TransferRequest) -> WalletResult<TransferResponse> {
         message: format!(
             "signed TRON transaction prepared; frontend should broadcast via wallet/broadcasttransaction: {txid}"
         ),
+        resolved_to: None,
     })
 }
 
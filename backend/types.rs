@@ -5,6 +5,9 @@ pub type Network = String;
 
 pub mod networks {
     pub const BITCOIN: &str = "bitcoin";
+    pub const BITCOIN_TESTNET: &str = "bitcoin_testnet";
+    pub const BITCOIN_SIGNET: &str = "bitcoin_signet";
+    pub const BITCOIN_REGTEST: &str = "bitcoin_regtest";
     pub const ETHEREUM: &str = "ethereum";
     pub const SEPOLIA: &str = "sepolia";
     pub const BASE: &str = "base";
@@ -17,6 +20,8 @@ pub mod networks {
     pub const INTERNET_COMPUTER: &str = "internet_computer";
     pub const SOLANA: &str = "solana";
     pub const SOLANA_TESTNET: &str = "solana_testnet";
+    pub const SOLANA_DEVNET: &str = "solana_devnet";
+    pub const SOLANA_LOCALNET: &str = "solana_localnet";
     pub const TRON: &str = "tron";
     pub const TON_MAINNET: &str = "ton_mainnet";
     pub const NEAR_MAINNET: &str = "near_mainnet";
@@ -54,6 +59,80 @@ pub struct RemoveConfiguredTokenRequest {
     pub token_address: String,
 }
 
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ConfiguredNftCollectionResponse {
+    pub network: Network,
+    pub standard: String,
+    pub symbol: String,
+    pub name: String,
+    pub contract_address: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AddConfiguredNftCollectionRequest {
+    pub network: Network,
+    pub contract_address: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RemoveConfiguredNftCollectionRequest {
+    pub network: Network,
+    pub contract_address: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct NftOwnershipRequest {
+    pub contract_address: String,
+    pub token_id: String,
+    pub account: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct NftOwnershipResponse {
+    pub network: Network,
+    pub contract_address: String,
+    pub token_id: String,
+    pub standard: String,
+    pub account: String,
+    pub owner: Option<String>,
+    pub balance: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Erc20AllowanceRequest {
+    pub token: String,
+    pub spender: String,
+    pub owner: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Erc20AllowanceResponse {
+    pub network: Network,
+    pub token: String,
+    pub owner: String,
+    pub spender: String,
+    pub amount: String,
+    pub raw_amount: String,
+    pub decimals: Option<u8>,
+    pub unlimited: bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Erc20ApprovalsRequest {
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+    pub tokens: Vec<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Erc20ApprovalsResponse {
+    pub network: Network,
+    pub approvals: Vec<Erc20AllowanceResponse>,
+    pub from_block: u64,
+    pub to_block: u64,
+    pub complete: bool,
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct ConfiguredRpcResponse {
     pub network: Network,
@@ -71,6 +150,23 @@ pub struct RemoveConfiguredRpcRequest {
     pub network: Network,
 }
 
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AddEvmNetworkRequest {
+    pub id: Network,
+    pub chain_id: u64,
+    pub rpc_url: String,
+    pub native_symbol: String,
+    pub explorer_template: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CustomEvmNetworkResponse {
+    pub id: Network,
+    pub chain_id: u64,
+    pub native_symbol: String,
+    pub explorer_template: Option<String>,
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct ConfiguredExplorerResponse {
     pub network: Network,
@@ -116,6 +212,169 @@ pub struct TransferResponse {
     pub signed_tx_encoding: Option<String>,
     pub broadcast_request: Option<BroadcastHttpRequest>,
     pub message: String,
+    pub resolved_to: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct TransferItem {
+    pub to: String,
+    pub amount: String,
+    pub token: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EvmBatchTransferRequest {
+    pub from: Option<String>,
+    pub items: Vec<TransferItem>,
+    pub mode: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EvmBatchTransferResponse {
+    pub network: Network,
+    pub mode: String,
+    pub transfers: Vec<TransferResponse>,
+    pub message: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EvmFeeEstimateResponse {
+    pub network: Network,
+    pub gas_limit: u64,
+    pub max_fee_per_gas: String,
+    pub max_priority_fee_per_gas: String,
+    pub execution_fee: String,
+    pub l1_data_fee: String,
+    pub total_fee: String,
+    pub value: String,
+    pub total_cost: String,
+    pub balance: String,
+    pub sufficient: bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EvmSwapRequest {
+    pub from: Option<String>,
+    pub token_in: String,
+    pub token_out: String,
+    pub amount: String,
+    pub max_slippage_bps: Option<u32>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EvmSwapQuoteResponse {
+    pub network: Network,
+    pub token_in: String,
+    pub token_out: String,
+    pub amount_in: String,
+    pub amount_out: String,
+    pub raw_amount_out: String,
+    pub decimals_out: u8,
+    pub route: Vec<String>,
+    pub fee_tiers: Vec<u32>,
+    pub gas_estimate: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EvmSwapResponse {
+    pub quote: EvmSwapQuoteResponse,
+    pub max_slippage_bps: u32,
+    pub min_amount_out: String,
+    pub transactions: Vec<TransferResponse>,
+    pub message: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct NameResolutionRecord {
+    pub network: Network,
+    pub name: String,
+    pub address: String,
+    pub resolved_at_ns: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct BitcoinTxInputRecord {
+    pub txid: String,
+    pub vout: u32,
+    pub value: u64,
+    pub script_pubkey_hex: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct BitcoinTxOutputRecord {
+    pub script_pubkey_hex: String,
+    pub value: u64,
+    pub is_change: bool,
+}
+
+/// Where `bitcoin.rs` reads UTXOs / fees / balances from and whether it broadcasts itself.
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitcoinDataSource {
+    /// Esplora HTTP API (`configured_rpcs` url); the frontend broadcasts.
+    Esplora,
+    /// ICP Bitcoin canister (`bitcoin_get_utxos` etc.); the canister broadcasts.
+    BitcoinCanister,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct BitcoinSentTxRecord {
+    // Absent in records kept before testnets were added; those are all mainnet.
+    #[serde(default)]
+    pub network: Option<Network>,
+    pub txid: String,
+    pub inputs: Vec<BitcoinTxInputRecord>,
+    pub outputs: Vec<BitcoinTxOutputRecord>,
+    pub vsize: u64,
+    pub fee_sats: u64,
+    pub replaceable: bool,
+    pub created_at_ns: u64,
+    pub replaced_by: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct BitcoinPsbtResponse {
+    pub network: Network,
+    pub psbt_base64: String,
+    pub fee_sats: u64,
+    pub input_count: u32,
+    pub signed_inputs: u32,
+    pub message: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CkbtcUtxoStatus {
+    pub status: String,
+    pub txid: String,
+    pub vout: u32,
+    pub value_sats: u64,
+    pub minted_sats: Option<u64>,
+    pub mint_block_index: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CkbtcUpdateBalanceResponse {
+    pub minted_sats: u64,
+    pub utxos: Vec<CkbtcUtxoStatus>,
+    pub message: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SolanaNonceAccountResponse {
+    pub network: Network,
+    pub address: String,
+    pub exists: bool,
+    pub authority: Option<String>,
+    pub nonce: Option<String>,
+    pub lamports: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SolanaCloseTokenAccountsResponse {
+    pub network: Network,
+    pub closable_accounts: Vec<String>,
+    pub reclaimable_lamports: u64,
+    pub transactions: Vec<TransferResponse>,
+    pub message: String,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
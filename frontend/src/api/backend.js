@@ -17,6 +17,10 @@ function formatWalletError(err) {
     const v = err.Unimplemented || {};
     return `未实现: ${v.network || 'unknown'}/${v.operation || 'unknown'}`;
   }
+  if ('ExecutionReverted' in err) {
+    const v = err.ExecutionReverted || {};
+    return `模拟执行回滚（未签名）: ${v.reason || 'unknown'}`;
+  }
   return '未知错误';
 }
 